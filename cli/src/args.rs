@@ -117,6 +117,8 @@ pub enum DLMMCommand {
     ShowPair(ShowPairParams),
     /// Show information of the given position.
     ShowPosition(ShowPositionParams),
+    /// Decode any DLMM program account and print it as JSON.
+    DecodeAccount(DecodeAccountParams),
     ClaimReward(ClaimRewardParams),
     UpdateRewardDuration(UpdateRewardDurationParams),
     UpdateRewardFunder(UpdateRewardFunderParams),
@@ -1,5 +1,5 @@
 use crate::*;
-use commons::dlmm::accounts::{LbPair, PositionV2};
+use commons::dlmm::accounts::LbPair;
 use instructions::*;
 
 #[derive(Debug, Parser)]
@@ -46,8 +46,6 @@ pub async fn execute_add_liquidity<C: Deref<Target = impl Signer> + Clone>(
         })
         .await?;
 
-    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
-
     let bin_liquidity_distribution = bin_liquidity_distribution
         .into_iter()
         .map(|(bin_id, dist_x, dist_y)| BinLiquidityDistribution {
@@ -57,25 +55,6 @@ pub async fn execute_add_liquidity<C: Deref<Target = impl Signer> + Clone>(
         })
         .collect::<Vec<_>>();
 
-    let position_state: PositionV2 = rpc_client
-        .get_account_and_deserialize(&position, |account| {
-            pod_read_unaligned_skip_disc(&account.data)
-        })
-        .await?;
-
-    let min_bin_id = bin_liquidity_distribution
-        .first()
-        .map(|bld| bld.bin_id)
-        .context("No bin liquidity distribution provided")?;
-
-    let max_bin_id = bin_liquidity_distribution
-        .last()
-        .map(|bld| bld.bin_id)
-        .context("No bin liquidity distribution provided")?;
-
-    let bin_arrays_account_meta =
-        position_state.get_bin_array_accounts_meta_coverage_by_chunk(min_bin_id, max_bin_id)?;
-
     let user_token_x = get_or_create_ata(
         program,
         transaction_config,
@@ -94,69 +73,27 @@ pub async fn execute_add_liquidity<C: Deref<Target = impl Signer> + Clone>(
     )
     .await?;
 
-    let (bin_array_bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
-
-    let bin_array_bitmap_extension = rpc_client
-        .get_account(&bin_array_bitmap_extension)
-        .await
-        .map(|_| bin_array_bitmap_extension)
-        .ok()
-        .or(Some(dlmm::ID));
-
-    let (event_authority, _bump) = derive_event_authority_pda();
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &lb_pair_state,
+        &program.rpc(),
+        ActionType::Liquidity,
+    )
+    .await?;
 
-    let main_accounts = dlmm::client::accounts::AddLiquidity2 {
-        lb_pair,
-        bin_array_bitmap_extension,
+    let add_liquidity_ix = commons::instructions::add_liquidity2(
         position,
-        reserve_x: lb_pair_state.reserve_x,
-        reserve_y: lb_pair_state.reserve_y,
-        token_x_mint: lb_pair_state.token_x_mint,
-        token_y_mint: lb_pair_state.token_y_mint,
-        sender: program.payer(),
+        lb_pair,
+        &lb_pair_state,
+        program.payer(),
         user_token_x,
         user_token_y,
-        token_x_program,
-        token_y_program,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-    let mut remaining_accounts = vec![];
-
-    if let Some((slices, transfer_hook_remaining_accounts)) =
-        get_potential_token_2022_related_ix_data_and_accounts(
-            &lb_pair_state,
-            program.rpc(),
-            ActionType::Liquidity,
-        )
-        .await?
-    {
-        remaining_accounts_info.slices = slices;
-        remaining_accounts.extend(transfer_hook_remaining_accounts);
-    };
-
-    remaining_accounts.extend(bin_arrays_account_meta);
-
-    let data = dlmm::client::args::AddLiquidity2 {
-        liquidity_parameter: LiquidityParameter {
+        transfer_hook_accounts,
+        LiquidityParameter {
             amount_x,
             amount_y,
             bin_liquidity_dist: bin_liquidity_distribution,
         },
-        remaining_accounts_info,
-    }
-    .data();
-
-    let accounts = [main_accounts.to_vec(), remaining_accounts].concat();
-
-    let add_liquidity_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    )?;
 
     let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
 
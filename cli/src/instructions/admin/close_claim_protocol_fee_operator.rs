@@ -13,22 +13,11 @@ pub async fn execute_close_claim_protocol_fee_operator<C: Deref<Target = impl Si
 ) -> Result<()> {
     let CloseClaimFeeOperatorParams { operator } = params;
 
-    let (claim_fee_operator, _bump) = derive_claim_protocol_fee_operator_pda(operator);
-
-    let accounts = dlmm::client::accounts::CloseClaimFeeOperatorAccount {
-        claim_fee_operator,
-        rent_receiver: program.payer(),
-        signer: program.payer(),
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::CloseClaimFeeOperatorAccount {}.data();
-
-    let instruction = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    let instruction = commons::instructions::close_claim_fee_operator_account(
+        operator,
+        program.payer(),
+        program.payer(),
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
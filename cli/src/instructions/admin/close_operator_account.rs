@@ -14,22 +14,11 @@ pub async fn execute_close_operator_account<C: Deref<Target = impl Signer> + Clo
 ) -> Result<()> {
     let CloseOperatorAccountParams { whitelisted_signer } = params;
 
-    let (operator, _bump) = derive_operator_pda(whitelisted_signer);
-
-    let accounts = dlmm::client::accounts::CloseOperatorAccount {
-        operator,
-        signer: program.payer(),
-        rent_receiver: program.payer(),
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::CloseOperatorAccount {}.data();
-
-    let instruction = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    let instruction = commons::instructions::close_operator_account(
+        whitelisted_signer,
+        program.payer(),
+        program.payer(),
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
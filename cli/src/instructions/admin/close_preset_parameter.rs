@@ -19,40 +19,18 @@ pub async fn execute_close_preset_parameter<C: Deref<Target = impl Signer> + Clo
 
     let disc = &preset_parameter_account.data[..8];
 
-    let (operator, _bump) = derive_operator_pda(program.payer());
-
     let instruction = if disc == dlmm::accounts::PresetParameter::DISCRIMINATOR {
-        let accounts = dlmm::client::accounts::ClosePresetParameter {
+        commons::instructions::close_preset_parameter(
             preset_parameter,
-            operator,
-            signer: program.payer(),
-            rent_receiver: program.payer(),
-        }
-        .to_account_metas(None);
-
-        let data = dlmm::client::args::ClosePresetParameter {}.data();
-
-        Instruction {
-            program_id: dlmm::ID,
-            accounts,
-            data,
-        }
+            program.payer(),
+            program.payer(),
+        )?
     } else if disc == dlmm::accounts::PresetParameter2::DISCRIMINATOR {
-        let accounts = dlmm::client::accounts::ClosePresetParameter2 {
+        commons::instructions::close_preset_parameter2(
             preset_parameter,
-            operator,
-            signer: program.payer(),
-            rent_receiver: program.payer(),
-        }
-        .to_account_metas(None);
-
-        let data = dlmm::client::args::ClosePresetParameter2 {}.data();
-
-        Instruction {
-            program_id: dlmm::ID,
-            accounts,
-            data,
-        }
+            program.payer(),
+            program.payer(),
+        )?
     } else {
         bail!("Not a valid preset parameter account");
     };
@@ -14,24 +14,8 @@ pub async fn execute_close_token_badge<C: Deref<Target = impl Signer> + Clone>(
 ) -> Result<()> {
     let CloseTokenBadgeParams { mint } = params;
 
-    let (token_badge, _bump) = derive_token_badge_pda(mint);
-    let (operator, _bump) = derive_operator_pda(program.payer());
-
-    let accounts = dlmm::client::accounts::CloseTokenBadge {
-        token_badge,
-        rent_receiver: program.payer(),
-        operator,
-        signer: program.payer(),
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::CloseTokenBadge {}.data();
-
-    let instruction = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    let instruction =
+        commons::instructions::close_token_badge(mint, program.payer(), program.payer())?;
 
     let request_builder = program.request();
     let signature = request_builder
@@ -18,19 +18,8 @@ pub async fn execute_set_activation_point<C: Deref<Target = impl Signer> + Clone
         activation_point,
     } = params;
 
-    let accounts = dlmm::client::accounts::SetActivationPoint {
-        lb_pair,
-        signer: program.payer(),
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::SetActivationPoint { activation_point }.data();
-
-    let set_activation_point_ix = Instruction {
-        accounts,
-        data,
-        program_id: dlmm::ID,
-    };
+    let set_activation_point_ix =
+        commons::instructions::set_activation_point(lb_pair, program.payer(), activation_point)?;
 
     let request_builder = program.request();
     let signature = request_builder
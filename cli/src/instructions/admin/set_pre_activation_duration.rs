@@ -16,22 +16,11 @@ pub async fn execute_set_pre_activation_duration<C: Deref<Target = impl Signer>
         pre_activation_duration,
     } = params;
 
-    let accounts = dlmm::client::accounts::SetPreActivationDuration {
+    let set_pre_activation_slot_duration_ix = commons::instructions::set_pre_activation_duration(
         lb_pair,
-        signer: program.payer(),
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::SetPreActivationDuration {
-        pre_activation_duration: pre_activation_duration as u64,
-    }
-    .data();
-
-    let set_pre_activation_slot_duration_ix = Instruction {
-        accounts,
-        data,
-        program_id: dlmm::ID,
-    };
+        program.payer(),
+        pre_activation_duration as u64,
+    )?;
 
     let request_builder = program.request();
 
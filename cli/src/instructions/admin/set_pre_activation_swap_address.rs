@@ -16,22 +16,12 @@ pub async fn execute_set_pre_activation_swap_address<C: Deref<Target = impl Sign
         pre_activation_swap_address,
     } = params;
 
-    let accounts = dlmm::client::accounts::SetPreActivationSwapAddress {
-        lb_pair,
-        signer: program.payer(),
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::SetPreActivationSwapAddress {
-        pre_activation_swap_address,
-    }
-    .data();
-
-    let set_pre_activation_swap_address_ix = Instruction {
-        accounts,
-        data,
-        program_id: dlmm::ID,
-    };
+    let set_pre_activation_swap_address_ix =
+        commons::instructions::set_pre_activation_swap_address(
+            lb_pair,
+            program.payer(),
+            pre_activation_swap_address,
+        )?;
 
     let request_builder = program.request();
 
@@ -70,68 +70,27 @@ pub async fn execute_claim_fee<C: Deref<Target = impl Signer> + Clone>(
         (user_token_x, user_token_y)
     };
 
-    let [token_program_x, token_program_y] = lb_pair_state.get_token_programs()?;
-
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let main_accounts = dlmm::client::accounts::ClaimFee2 {
-        lb_pair: position_state.lb_pair,
-        sender: program.payer(),
-        position,
-        reserve_x: lb_pair_state.reserve_x,
-        reserve_y: lb_pair_state.reserve_y,
-        token_program_x,
-        token_program_y,
-        token_x_mint: lb_pair_state.token_x_mint,
-        token_y_mint: lb_pair_state.token_y_mint,
-        user_token_x,
-        user_token_y,
-        event_authority,
-        program: dlmm::ID,
-        memo_program: spl_memo::id(),
-    }
-    .to_account_metas(None);
-
-    let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-    let mut token_2022_remaining_accounts = vec![];
-
-    if let Some((slices, transfer_hook_remaining_accounts)) =
-        get_potential_token_2022_related_ix_data_and_accounts(
-            &lb_pair_state,
-            program.rpc(),
-            ActionType::Liquidity,
-        )
-        .await?
-    {
-        remaining_accounts_info.slices = slices;
-        token_2022_remaining_accounts.extend(transfer_hook_remaining_accounts);
-    };
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &lb_pair_state,
+        &program.rpc(),
+        ActionType::Liquidity,
+    )
+    .await?;
 
     for (min_bin_id, max_bin_id) in
         position_bin_range_chunks(position_state.lower_bin_id, position_state.upper_bin_id)
     {
-        let data = dlmm::client::args::ClaimFee2 {
+        let claim_fee_ix = commons::instructions::claim_fee2(
+            position,
+            position_state.lb_pair,
+            &lb_pair_state,
+            program.payer(),
+            user_token_x,
+            user_token_y,
+            transfer_hook_accounts.clone(),
             min_bin_id,
             max_bin_id,
-            remaining_accounts_info: remaining_accounts_info.clone(),
-        }
-        .data();
-
-        let bin_arrays_account_meta =
-            position_state.get_bin_array_accounts_meta_coverage_by_chunk(min_bin_id, max_bin_id)?;
-
-        let accounts = [
-            main_accounts.to_vec(),
-            token_2022_remaining_accounts.clone(),
-            bin_arrays_account_meta,
-        ]
-        .concat();
-
-        let claim_fee_ix = Instruction {
-            program_id: dlmm::ID,
-            accounts,
-            data,
-        };
+        )?;
 
         let mut request_builder = program.request();
 
@@ -21,8 +21,6 @@ pub async fn execute_claim_reward<C: Deref<Target = impl Signer> + Clone>(
     } = params;
 
     let rpc_client = program.rpc();
-    let (reward_vault, _bump) = derive_reward_vault_pda(lb_pair, reward_index);
-
     let lb_pair_state: LbPair = rpc_client
         .get_account_and_deserialize(&lb_pair, |account| {
             pod_read_unaligned_skip_disc(&account.data)
@@ -35,8 +33,7 @@ pub async fn execute_claim_reward<C: Deref<Target = impl Signer> + Clone>(
         })
         .await?;
 
-    let reward_info = lb_pair_state.reward_infos[reward_index as usize];
-    let reward_mint = reward_info.mint;
+    let reward_mint = lb_pair_state.reward_infos[reward_index as usize].mint;
 
     let reward_mint_program = rpc_client.get_account(&reward_mint).await?.owner;
 
@@ -49,63 +46,28 @@ pub async fn execute_claim_reward<C: Deref<Target = impl Signer> + Clone>(
     )
     .await?;
 
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let main_accounts = dlmm::client::accounts::ClaimReward2 {
-        lb_pair,
-        reward_vault,
-        reward_mint,
-        memo_program: spl_memo::ID,
-        token_program: reward_mint_program,
-        position,
-        user_token_account,
-        sender: program.payer(),
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-    let mut token_2022_remaining_accounts = vec![];
-
-    if let Some((slices, transfer_hook_remaining_accounts)) =
-        get_potential_token_2022_related_ix_data_and_accounts(
-            &lb_pair_state,
-            program.rpc(),
-            ActionType::Reward(reward_index as usize),
-        )
-        .await?
-    {
-        remaining_accounts_info.slices = slices;
-        token_2022_remaining_accounts.extend(transfer_hook_remaining_accounts);
-    };
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &lb_pair_state,
+        &program.rpc(),
+        ActionType::Reward(reward_index as usize),
+    )
+    .await?;
 
     for (min_bin_id, max_bin_id) in
         position_bin_range_chunks(position_state.lower_bin_id, position_state.upper_bin_id)
     {
-        let data = dlmm::client::args::ClaimReward2 {
+        let claim_reward_ix = commons::instructions::claim_reward2(
+            position,
+            lb_pair,
+            &lb_pair_state,
+            reward_mint_program,
+            program.payer(),
+            user_token_account,
+            transfer_hook_accounts.clone(),
             reward_index,
             min_bin_id,
             max_bin_id,
-            remaining_accounts_info: remaining_accounts_info.clone(),
-        }
-        .data();
-
-        let bin_arrays_account_meta =
-            position_state.get_bin_array_accounts_meta_coverage_by_chunk(min_bin_id, max_bin_id)?;
-
-        let accounts = [
-            main_accounts.to_vec(),
-            token_2022_remaining_accounts.clone(),
-            bin_arrays_account_meta,
-        ]
-        .concat();
-
-        let claim_reward_ix = Instruction {
-            program_id: dlmm::ID,
-            accounts,
-            data,
-        };
+        )?;
 
         let request_builder = program.request();
         let signature = request_builder
@@ -14,24 +14,11 @@ pub async fn execute_close_limit_order_if_empty<C: Deref<Target = impl Signer> +
 ) -> Result<()> {
     let CloseLimitOrderIfEmptyParams { limit_order } = params;
 
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let accounts = dlmm::client::accounts::CloseLimitOrderIfEmpty {
+    let instruction = commons::instructions::close_limit_order_if_empty(
         limit_order,
-        owner: program.payer(),
-        rent_receiver: program.payer(),
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::CloseLimitOrderIfEmpty {}.data();
-
-    let instruction = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+        program.payer(),
+        program.payer(),
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
@@ -0,0 +1,27 @@
+use crate::*;
+
+#[derive(Debug, Parser)]
+pub struct DecodeAccountParams {
+    /// Address of an account owned by the DLMM program.
+    pub account: Pubkey,
+}
+
+pub async fn execute_decode_account<C: Deref<Target = impl Signer> + Clone>(
+    params: DecodeAccountParams,
+    program: &Program<C>,
+) -> Result<()> {
+    let DecodeAccountParams { account } = params;
+
+    let rpc_client = program.rpc();
+    let account_state = rpc_client.get_account(&account).await?;
+    ensure!(
+        account_state.owner == program.id(),
+        "Account {} is not owned by the DLMM program",
+        account
+    );
+
+    let decoded_account = DlmmAccount::decode(&account_state.data)?;
+    println!("{}", serde_json::to_string_pretty(&decoded_account)?);
+
+    Ok(())
+}
@@ -22,16 +22,13 @@ pub async fn execute_fund_reward<C: Deref<Target = impl Signer> + Clone>(
 
     let rpc_client = program.rpc();
 
-    let (reward_vault, _bump) = derive_reward_vault_pda(lb_pair, reward_index);
-
     let lb_pair_state: LbPair = rpc_client
         .get_account_and_deserialize(&lb_pair, |account| {
             pod_read_unaligned_skip_disc(&account.data)
         })
         .await?;
 
-    let reward_info = lb_pair_state.reward_infos[reward_index as usize];
-    let reward_mint = reward_info.mint;
+    let reward_mint = lb_pair_state.reward_infos[reward_index as usize].mint;
 
     let reward_mint_program = rpc_client.get_account(&reward_mint).await?.owner;
 
@@ -44,49 +41,24 @@ pub async fn execute_fund_reward<C: Deref<Target = impl Signer> + Clone>(
     )
     .await?;
 
-    let active_bin_array_idx = BinArray::bin_id_to_bin_array_index(lb_pair_state.active_id)?;
-    let (bin_array, _bump) = derive_bin_array_pda(lb_pair, active_bin_array_idx as i64);
-
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let reward_transfer_hook_accounts =
-        get_extra_account_metas_for_transfer_hook(reward_mint, program.rpc()).await?;
-
-    let remaining_accounts_info = RemainingAccountsInfo {
-        slices: vec![RemainingAccountsSlice {
-            accounts_type: AccountsType::TransferHookReward,
-            length: reward_transfer_hook_accounts.len() as u8,
-        }],
-    };
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &lb_pair_state,
+        &program.rpc(),
+        ActionType::Reward(reward_index as usize),
+    )
+    .await?;
 
-    let main_accounts = dlmm::client::accounts::FundReward {
+    let fund_reward_ix = commons::instructions::fund_reward(
         lb_pair,
-        reward_vault,
-        reward_mint,
-        funder: program.payer(),
+        &lb_pair_state,
+        reward_mint_program,
+        program.payer(),
         funder_token_account,
-        bin_array,
-        token_program: reward_mint_program,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::FundReward {
+        transfer_hook_accounts,
         reward_index,
-        amount: funding_amount,
-        carry_forward: true,
-        remaining_accounts_info,
-    }
-    .data();
-
-    let accounts = [main_accounts.to_vec(), reward_transfer_hook_accounts].concat();
-
-    let fund_reward_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+        funding_amount,
+        true,
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
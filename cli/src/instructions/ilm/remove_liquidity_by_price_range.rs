@@ -37,7 +37,6 @@ pub async fn execute_remove_liquidity_by_price_range<C: Deref<Target = impl Sign
         .await?;
 
     let bin_step = lb_pair_state.bin_step;
-    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
 
     let mut accounts = rpc_client
         .get_multiple_accounts(&[lb_pair_state.token_x_mint, lb_pair_state.token_y_mint])
@@ -89,32 +88,14 @@ pub async fn execute_remove_liquidity_by_price_range<C: Deref<Target = impl Sign
     )
     .await?;
 
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let (bin_array_bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
-    let bin_array_bitmap_extension = rpc_client
-        .get_account(&bin_array_bitmap_extension)
-        .await
-        .map(|_| bin_array_bitmap_extension)
-        .ok()
-        .or(Some(dlmm::ID));
-
     let width = DEFAULT_BIN_PER_POSITION as i32;
 
-    let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-    let mut transfer_hook_remaining_accounts = vec![];
-
-    if let Some((slices, remaining_accounts)) =
-        get_potential_token_2022_related_ix_data_and_accounts(
-            &lb_pair_state,
-            program.rpc(),
-            ActionType::Liquidity,
-        )
-        .await?
-    {
-        remaining_accounts_info.slices = slices;
-        transfer_hook_remaining_accounts.extend(remaining_accounts);
-    };
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &lb_pair_state,
+        &program.rpc(),
+        ActionType::Liquidity,
+    )
+    .await?;
 
     for i in min_active_id..=max_active_id {
         let (position, _bump) = derive_position_pda(lb_pair, base_position_key, i, width);
@@ -123,105 +104,44 @@ pub async fn execute_remove_liquidity_by_price_range<C: Deref<Target = impl Sign
         if let std::result::Result::Ok(account) = position_account {
             let position_state: PositionV2 = pod_read_unaligned_skip_disc(&account.data)?;
 
-            let bin_arrays_account_meta = position_state.get_bin_array_accounts_meta_coverage()?;
-
-            let remaining_accounts = [
-                transfer_hook_remaining_accounts.clone(),
-                bin_arrays_account_meta,
-            ]
-            .concat();
-
             let mut instructions =
                 vec![ComputeBudgetInstruction::set_compute_unit_limit(1_400_000)];
 
-            let main_accounts = dlmm::client::accounts::RemoveLiquidityByRange2 {
+            let withdraw_all_ix = commons::instructions::remove_liquidity_by_range2(
                 position,
                 lb_pair,
-                bin_array_bitmap_extension,
+                &lb_pair_state,
+                program.payer(),
                 user_token_x,
                 user_token_y,
-                reserve_x: lb_pair_state.reserve_x,
-                reserve_y: lb_pair_state.reserve_y,
-                token_x_mint: lb_pair_state.token_x_mint,
-                token_y_mint: lb_pair_state.token_y_mint,
-                sender: program.payer(),
-                token_x_program,
-                token_y_program,
-                memo_program: spl_memo::ID,
-                event_authority,
-                program: dlmm::ID,
-            }
-            .to_account_metas(None);
-
-            let data = dlmm::client::args::RemoveLiquidityByRange2 {
-                from_bin_id: position_state.lower_bin_id,
-                to_bin_id: position_state.upper_bin_id,
-                bps_to_remove: BASIS_POINT_MAX as u16,
-                remaining_accounts_info: remaining_accounts_info.clone(),
-            }
-            .data();
-
-            let accounts = [main_accounts.to_vec(), remaining_accounts.clone()].concat();
-
-            let withdraw_all_ix = Instruction {
-                program_id: dlmm::ID,
-                accounts,
-                data,
-            };
+                transfer_hook_accounts.clone(),
+                position_state.lower_bin_id,
+                position_state.upper_bin_id,
+                BASIS_POINT_MAX as u16,
+            )?;
 
             instructions.push(withdraw_all_ix);
 
-            let main_accounts = dlmm::client::accounts::ClaimFee2 {
-                lb_pair,
+            let claim_fee_ix = commons::instructions::claim_fee2(
                 position,
-                sender: program.payer(),
-                reserve_x: lb_pair_state.reserve_x,
-                reserve_y: lb_pair_state.reserve_y,
-                token_x_mint: lb_pair_state.token_x_mint,
-                token_y_mint: lb_pair_state.token_y_mint,
-                token_program_x: token_x_program,
-                token_program_y: token_y_program,
-                memo_program: spl_memo::ID,
-                event_authority,
-                program: dlmm::ID,
+                lb_pair,
+                &lb_pair_state,
+                program.payer(),
                 user_token_x,
                 user_token_y,
-            }
-            .to_account_metas(None);
-
-            let data = dlmm::client::args::ClaimFee2 {
-                min_bin_id: position_state.lower_bin_id,
-                max_bin_id: position_state.upper_bin_id,
-                remaining_accounts_info: remaining_accounts_info.clone(),
-            }
-            .data();
-
-            let accounts = [main_accounts.to_vec(), remaining_accounts.clone()].concat();
-
-            let claim_fee_ix = Instruction {
-                program_id: dlmm::ID,
-                accounts,
-                data,
-            };
+                transfer_hook_accounts.clone(),
+                position_state.lower_bin_id,
+                position_state.upper_bin_id,
+            )?;
 
             instructions.push(claim_fee_ix);
 
-            let accounts = dlmm::client::accounts::ClosePosition2 {
+            let close_position_ix = commons::instructions::close_position2(
                 position,
-                sender: program.payer(),
-                rent_receiver: program.payer(),
-                event_authority,
-                program: dlmm::ID,
-            }
-            .to_account_metas(None);
-
-            let data = dlmm::client::args::ClosePosition2 {}.data();
-
-            let close_position_ix = Instruction {
-                program_id: dlmm::ID,
-                accounts,
-                data,
-            };
+                &position_state,
+                program.payer(),
+                program.payer(),
+            )?;
 
             instructions.push(close_position_ix);
 
@@ -190,7 +190,7 @@ pub async fn execute_seed_liquidity_by_operator<C: Deref<Target = impl Signer> +
 
     let bin_step = lb_pair_state.bin_step;
 
-    let (mut bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
+    let (bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
 
     let mut accounts = rpc_client
         .get_multiple_accounts(&[
@@ -269,7 +269,6 @@ pub async fn execute_seed_liquidity_by_operator<C: Deref<Target = impl Signer> +
     let mut position_and_bin_array_setup_ixs = vec![];
     let mut liquidity_setup_ixs = vec![];
 
-    let (event_authority, _bump) = derive_event_authority_pda();
     let seeder = program.payer();
 
     let token_mint_base_owner = token_mint_base_account.owner;
@@ -294,11 +293,11 @@ pub async fn execute_seed_liquidity_by_operator<C: Deref<Target = impl Signer> +
     );
 
     let transfer_hook_x_account =
-        get_extra_account_metas_for_transfer_hook(lb_pair_state.token_x_mint, program.rpc())
+        get_extra_account_metas_for_transfer_hook(lb_pair_state.token_x_mint, &program.rpc())
             .await?;
 
     let transfer_hook_y_account =
-        get_extra_account_metas_for_transfer_hook(lb_pair_state.token_y_mint, program.rpc())
+        get_extra_account_metas_for_transfer_hook(lb_pair_state.token_y_mint, &program.rpc())
             .await?;
 
     let accounts = rpc_client
@@ -379,26 +378,8 @@ pub async fn execute_seed_liquidity_by_operator<C: Deref<Target = impl Signer> +
         upper_bin_array_index > max_bitmap_id || lower_bin_array_index < min_bitmap_id;
 
     if overflow_internal_bitmap_range && bitmap_extension_account.is_none() {
-        let accounts = dlmm::client::accounts::InitializeBinArrayBitmapExtension {
-            lb_pair,
-            bin_array_bitmap_extension: bitmap_extension,
-            funder: seeder,
-            system_program: solana_sdk::system_program::ID,
-            rent: solana_sdk::sysvar::rent::ID,
-        }
-        .to_account_metas(None);
-
-        let ix_data = dlmm::client::args::InitializeBinArrayBitmapExtension {}.data();
-
-        let init_bitmap_ext_ix = Instruction {
-            program_id: dlmm::ID,
-            accounts,
-            data: ix_data,
-        };
-
-        token_account_and_bitmap_ext_and_token_prove_setup_ixs.push(init_bitmap_ext_ix);
-    } else {
-        bitmap_extension = dlmm::ID;
+        token_account_and_bitmap_ext_and_token_prove_setup_ixs
+            .push(commons::instructions::initialize_bin_array_bitmap_extension(lb_pair, seeder)?);
     }
 
     for i in 0..position_number {
@@ -411,9 +392,6 @@ pub async fn execute_seed_liquidity_by_operator<C: Deref<Target = impl Signer> +
         let (position, _bump) =
             derive_position_pda(lb_pair, position_base_kp.pubkey(), lower_bin_id, width);
 
-        let bin_array_account_metas =
-            BinArray::get_bin_array_account_metas_coverage(lower_bin_id, upper_bin_id, lb_pair)?;
-
         let bin_array_indexes =
             BinArray::get_bin_array_indexes_coverage(lower_bin_id, upper_bin_id)?;
 
@@ -430,63 +408,30 @@ pub async fn execute_seed_liquidity_by_operator<C: Deref<Target = impl Signer> +
 
         let position_account = accounts.index(0).to_owned();
         if position_account.is_none() {
-            let account = dlmm::client::accounts::InitializePositionByOperator {
-                position,
-                payer: seeder,
-                base: position_base_kp.pubkey(),
+            instructions.push(commons::instructions::initialize_position_by_operator(
                 lb_pair,
-                owner: position_owner,
-                operator: seeder,
-                operator_token_x: seeder_token_x,
+                seeder,
+                position_base_kp.pubkey(),
+                position_owner,
+                seeder,
+                seeder_token_x,
                 owner_token_x,
-                system_program: solana_sdk::system_program::ID,
-                event_authority,
-                program: dlmm::ID,
-            }
-            .to_account_metas(None);
-
-            let ix_data = dlmm::client::args::InitializePositionByOperator {
                 lower_bin_id,
                 width,
                 fee_owner,
                 lock_release_point,
-            }
-            .data();
-
-            let init_position_ix = Instruction {
-                program_id: dlmm::ID,
-                accounts: account.to_vec(),
-                data: ix_data,
-            };
-
-            instructions.push(init_position_ix);
+            )?);
         }
 
         let bin_array_account = &accounts[1..];
 
         for (account, index) in bin_array_account.iter().zip(bin_array_indexes) {
             if account.is_none() {
-                let bin_array = derive_bin_array_pda(lb_pair, index.into()).0;
-                let accounts = dlmm::client::accounts::InitializeBinArray {
-                    bin_array,
+                instructions.push(commons::instructions::initialize_bin_array(
                     lb_pair,
-                    funder: seeder,
-                    system_program: solana_sdk::system_program::ID,
-                }
-                .to_account_metas(None);
-
-                let ix_data = dlmm::client::args::InitializeBinArray {
-                    index: index.into(),
-                }
-                .data();
-
-                let init_bin_array_ix = Instruction {
-                    program_id: dlmm::ID,
-                    accounts,
-                    data: ix_data,
-                };
-
-                instructions.push(init_bin_array_ix);
+                    seeder,
+                    index.into(),
+                )?);
             }
         }
 
@@ -519,44 +464,26 @@ pub async fn execute_seed_liquidity_by_operator<C: Deref<Target = impl Signer> +
                 });
             }
 
-            let ix_data = dlmm::client::args::AddLiquidityOneSidePrecise2 {
-                liquidity_parameter: AddLiquiditySingleSidePreciseParameter2 {
-                    bins,
-                    decompress_multiplier,
-                    max_amount: u64::MAX,
-                },
-                remaining_accounts_info: RemainingAccountsInfo {
-                    slices: vec![RemainingAccountsSlice {
+            let add_liquidity_ix = commons::instructions::add_liquidity_one_side_precise2(
+                position,
+                lb_pair,
+                &lb_pair_state,
+                program.payer(),
+                seeder_token_x,
+                true,
+                Some((
+                    vec![RemainingAccountsSlice {
                         accounts_type: AccountsType::TransferHookX,
                         length: transfer_hook_x_account.len() as u8,
                     }],
+                    transfer_hook_x_account.clone(),
+                )),
+                AddLiquiditySingleSidePreciseParameter2 {
+                    bins,
+                    decompress_multiplier,
+                    max_amount: u64::MAX,
                 },
-            }
-            .data();
-
-            let accounts = dlmm::client::accounts::AddLiquidityOneSidePrecise2 {
-                position,
-                lb_pair,
-                bin_array_bitmap_extension: Some(bitmap_extension),
-                user_token: seeder_token_x,
-                reserve: lb_pair_state.reserve_x,
-                token_mint: lb_pair_state.token_x_mint,
-                sender: program.payer(),
-                token_program: token_mint_base_owner,
-                event_authority,
-                program: dlmm::ID,
-            }
-            .to_account_metas(None);
-
-            let mut accounts = accounts.to_vec();
-            accounts.extend_from_slice(&transfer_hook_x_account);
-            accounts.extend_from_slice(&bin_array_account_metas);
-
-            let add_liquidity_ix = Instruction {
-                program_id: dlmm::ID,
-                accounts,
-                data: ix_data,
-            };
+            )?;
 
             if instructions.is_empty() {
                 if let Some(cu_price_ix) = compute_unit_price.clone() {
@@ -576,24 +503,15 @@ pub async fn execute_seed_liquidity_by_operator<C: Deref<Target = impl Signer> +
                 )?
                 .amount;
 
-                let bin_array_account_metas = BinArray::get_bin_array_account_metas_coverage(
-                    upper_bin_id,
-                    upper_bin_id,
+                let add_liquidity_ix = commons::instructions::add_liquidity2(
+                    position,
                     lb_pair,
-                )?;
-
-                let ix_data = dlmm::client::args::AddLiquidity2 {
-                    liquidity_parameter: LiquidityParameter {
-                        amount_x: loss_includes_transfer_fee,
-                        amount_y: 0,
-                        bin_liquidity_dist: vec![BinLiquidityDistribution {
-                            bin_id: upper_bin_id,
-                            distribution_x: BASIS_POINT_MAX as u16,
-                            distribution_y: BASIS_POINT_MAX as u16,
-                        }],
-                    },
-                    remaining_accounts_info: RemainingAccountsInfo {
-                        slices: vec![
+                    &lb_pair_state,
+                    program.payer(),
+                    seeder_token_x,
+                    seeder_token_y,
+                    Some((
+                        vec![
                             RemainingAccountsSlice {
                                 accounts_type: AccountsType::TransferHookX,
                                 length: transfer_hook_x_account.len() as u8,
@@ -603,38 +521,22 @@ pub async fn execute_seed_liquidity_by_operator<C: Deref<Target = impl Signer> +
                                 length: transfer_hook_y_account.len() as u8,
                             },
                         ],
+                        [
+                            transfer_hook_x_account.clone(),
+                            transfer_hook_y_account.clone(),
+                        ]
+                        .concat(),
+                    )),
+                    LiquidityParameter {
+                        amount_x: loss_includes_transfer_fee,
+                        amount_y: 0,
+                        bin_liquidity_dist: vec![BinLiquidityDistribution {
+                            bin_id: upper_bin_id,
+                            distribution_x: BASIS_POINT_MAX as u16,
+                            distribution_y: BASIS_POINT_MAX as u16,
+                        }],
                     },
-                }
-                .data();
-
-                let accounts = dlmm::client::accounts::AddLiquidity2 {
-                    position,
-                    lb_pair,
-                    bin_array_bitmap_extension: Some(bitmap_extension),
-                    user_token_x: seeder_token_x,
-                    user_token_y: seeder_token_y,
-                    reserve_x: lb_pair_state.reserve_x,
-                    reserve_y: lb_pair_state.reserve_y,
-                    token_x_mint: lb_pair_state.token_x_mint,
-                    token_y_mint: lb_pair_state.token_y_mint,
-                    token_x_program: token_mint_base_owner,
-                    token_y_program: token_mint_quote_owner,
-                    sender: program.payer(),
-                    event_authority,
-                    program: dlmm::ID,
-                }
-                .to_account_metas(None);
-
-                let mut accounts = accounts.to_vec();
-                accounts.extend_from_slice(&transfer_hook_x_account);
-                accounts.extend_from_slice(&transfer_hook_y_account);
-                accounts.extend_from_slice(&bin_array_account_metas);
-
-                let add_liquidity_ix = Instruction {
-                    program_id: dlmm::ID,
-                    accounts,
-                    data: ix_data,
-                };
+                )?;
 
                 if instructions.is_empty() {
                     if let Some(cu_price_ix) = compute_unit_price.clone() {
@@ -102,7 +102,7 @@ pub async fn execute_seed_liquidity_single_bin_by_operator<
         &token_x_owner,
     );
 
-    let (mut bin_array_bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
+    let (bin_array_bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
 
     let mut accounts = rpc_client
         .get_multiple_accounts(&[
@@ -152,12 +152,6 @@ pub async fn execute_seed_liquidity_single_bin_by_operator<
         "bin id doesn't match active bin id"
     );
 
-    let (event_authority, _bump) = derive_event_authority_pda();
-    let (position, _bump) = derive_position_pda(lb_pair, base_pubkey, bin_id, 1);
-
-    let bin_array_accounts_meta =
-        BinArray::get_bin_array_account_metas_coverage(bin_id, bin_id, lb_pair)?;
-
     let bin_array_index = BinArray::bin_id_to_bin_array_index(bin_id)?;
 
     let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(1_400_000)];
@@ -172,48 +166,16 @@ pub async fn execute_seed_liquidity_single_bin_by_operator<
         bin_array_index > max_bitmap_id || bin_array_index < min_bitmap_id;
 
     if overflow_internal_bitmap_range && bin_array_bitmap_extension_account.is_none() {
-        let accounts = dlmm::client::accounts::InitializeBinArrayBitmapExtension {
-            lb_pair,
-            bin_array_bitmap_extension,
-            funder: program.payer(),
-            system_program: solana_sdk::system_program::ID,
-            rent: solana_sdk::sysvar::rent::ID,
-        }
-        .to_account_metas(None);
-
-        let data = dlmm::client::args::InitializeBinArrayBitmapExtension {}.data();
-
-        let initialize_bitmap_extension_ix = Instruction {
-            accounts,
-            program_id: dlmm::ID,
-            data,
-        };
-
-        instructions.push(initialize_bitmap_extension_ix);
-    } else {
-        bin_array_bitmap_extension = dlmm::ID;
+        instructions.push(
+            commons::instructions::initialize_bin_array_bitmap_extension(lb_pair, program.payer())?,
+        );
     }
 
-    let account = dlmm::client::accounts::InitializeBinArray {
+    instructions.push(commons::instructions::initialize_bin_array(
         lb_pair,
-        bin_array: bin_array_accounts_meta[0].pubkey,
-        funder: program.payer(),
-        system_program: solana_sdk::system_program::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::InitializeBinArray {
-        index: bin_array_index.into(),
-    }
-    .data();
-
-    let initialize_bin_array_ix = Instruction {
-        accounts: account.to_vec(),
-        program_id: dlmm::ID,
-        data,
-    };
-
-    instructions.push(initialize_bin_array_ix);
+        program.payer(),
+        bin_array_index.into(),
+    )?);
 
     let require_token_prove = if let Some(account) = owner_token_x_account {
         let token_account = TokenAccount::try_deserialize(&mut account.data.as_ref())?;
@@ -246,74 +208,38 @@ pub async fn execute_seed_liquidity_single_bin_by_operator<
         )?);
     }
 
-    let accounts = dlmm::client::accounts::InitializePositionByOperator {
+    instructions.push(commons::instructions::initialize_position_by_operator(
         lb_pair,
-        base: base_pubkey,
-        owner: position_owner,
-        operator: program.payer(),
-        payer: program.payer(),
-        position,
-        system_program: solana_sdk::system_program::ID,
-        event_authority,
+        program.payer(),
+        base_pubkey,
+        position_owner,
+        program.payer(),
         operator_token_x,
         owner_token_x,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::InitializePositionByOperator {
-        lower_bin_id: bin_id,
-        width: 1,
+        bin_id,
+        1,
         fee_owner,
         lock_release_point,
-    }
-    .data();
+    )?);
 
-    let initialize_position_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
-
-    instructions.push(initialize_position_ix);
-
-    let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-    let mut remaining_accounts = vec![];
-
-    if let Some((slice, transfer_hook_remaining_accounts)) =
-        get_potential_token_2022_related_ix_data_and_accounts(
-            &lb_pair_state,
-            program.rpc(),
-            ActionType::Liquidity,
-        )
-        .await?
-    {
-        remaining_accounts_info.slices = slice;
-        remaining_accounts.extend(transfer_hook_remaining_accounts);
-    }
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &lb_pair_state,
+        &program.rpc(),
+        ActionType::Liquidity,
+    )
+    .await?;
 
-    remaining_accounts.extend(bin_array_accounts_meta);
+    let (position, _bump) = derive_position_pda(lb_pair, base_pubkey, bin_id, 1);
 
-    let main_accounts = dlmm::client::accounts::AddLiquidity2 {
+    instructions.push(commons::instructions::add_liquidity2(
         position,
         lb_pair,
-        bin_array_bitmap_extension: Some(bin_array_bitmap_extension),
-        user_token_x: operator_token_x,
-        user_token_y: operator_token_y,
-        reserve_x: lb_pair_state.reserve_x,
-        reserve_y: lb_pair_state.reserve_y,
-        token_x_mint: lb_pair_state.token_x_mint,
-        token_y_mint: lb_pair_state.token_y_mint,
-        sender: program.payer(),
-        token_x_program: token_mint_base_account.owner,
-        token_y_program: token_mint_quote_account.owner,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::AddLiquidity2 {
-        liquidity_parameter: LiquidityParameter {
+        &lb_pair_state,
+        program.payer(),
+        operator_token_x,
+        operator_token_y,
+        transfer_hook_accounts,
+        LiquidityParameter {
             amount_x: native_amount,
             amount_y: 0,
             bin_liquidity_dist: vec![BinLiquidityDistribution {
@@ -322,19 +248,7 @@ pub async fn execute_seed_liquidity_single_bin_by_operator<
                 distribution_y: 10000,
             }],
         },
-        remaining_accounts_info,
-    }
-    .data();
-
-    let accounts = [main_accounts.to_vec(), remaining_accounts].concat();
-
-    let deposit_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
-
-    instructions.push(deposit_ix);
+    )?);
 
     let mut builder = program.request();
     builder = builder.signer(position_base_kp);
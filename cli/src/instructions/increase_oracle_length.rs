@@ -1,5 +1,4 @@
 use crate::*;
-use anchor_client::solana_sdk;
 
 #[derive(Debug, Parser)]
 pub struct IncreaseOracleLengthParams {
@@ -18,24 +17,8 @@ pub async fn execute_increase_oracle_length<C: Deref<Target = impl Signer> + Clo
     } = params;
 
     let (oracle, _) = derive_oracle_pda(lb_pair);
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let accounts = dlmm::client::accounts::IncreaseOracleLength {
-        funder: program.payer(),
-        oracle,
-        system_program: solana_sdk::system_program::ID,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::IncreaseOracleLength { length_to_add }.data();
-
-    let increase_length_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    let increase_length_ix =
+        commons::instructions::increase_oracle_length(lb_pair, program.payer(), length_to_add)?;
 
     let request_builder = program.request();
     let signature = request_builder
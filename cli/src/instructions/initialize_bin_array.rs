@@ -21,24 +21,8 @@ pub async fn execute_initialize_bin_array<C: Deref<Target = impl Signer> + Clone
 
     let (bin_array, _bump) = derive_bin_array_pda(lb_pair, bin_array_index);
 
-    let accounts = dlmm::client::accounts::InitializeBinArray {
-        bin_array,
-        funder: program.payer(),
-        lb_pair,
-        system_program: solana_sdk::system_program::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::InitializeBinArray {
-        index: bin_array_index,
-    }
-    .data();
-
-    let init_bin_array_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    let init_bin_array_ix =
+        commons::instructions::initialize_bin_array(lb_pair, program.payer(), bin_array_index)?;
 
     let request_builder = program.request();
     let signature = request_builder
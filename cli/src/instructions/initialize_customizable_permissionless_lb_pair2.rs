@@ -91,12 +91,6 @@ pub async fn execute_initialize_customizable_permissionless_lb_pair2<
         return Ok(lb_pair);
     }
 
-    let (reserve_x, _bump) = derive_reserve_pda(token_mint_x, lb_pair);
-    let (reserve_y, _bump) = derive_reserve_pda(token_mint_y, lb_pair);
-    let (oracle, _bump) = derive_oracle_pda(lb_pair);
-
-    let (event_authority, _bump) = derive_event_authority_pda();
-
     let user_token_x = get_or_create_ata(
         program,
         transaction_config,
@@ -122,35 +116,21 @@ pub async fn execute_initialize_customizable_permissionless_lb_pair2<
         .get_multiple_accounts(&[token_badge_x, token_badge_y])
         .await?;
 
-    let token_badge_x = accounts[0].take().map(|_| token_badge_x).or(Some(dlmm::ID));
-    let token_badge_y = accounts[1].take().map(|_| token_badge_y).or(Some(dlmm::ID));
+    let (base_factor, base_fee_power_factor) =
+        compute_base_factor_from_fee_bps(bin_step, base_fee_bps)?;
 
-    let accounts = dlmm::client::accounts::InitializeCustomizablePermissionlessLbPair2 {
-        lb_pair,
-        bin_array_bitmap_extension: Some(dlmm::ID),
-        reserve_x,
-        reserve_y,
+    let init_pair_ix = commons::instructions::initialize_customizable_permissionless_lb_pair2(
         token_mint_x,
         token_mint_y,
-        oracle,
-        funder: program.payer(),
-        system_program: solana_sdk::system_program::ID,
-        token_program_x: token_mint_base_account.owner,
-        token_program_y: token_mint_quote_account.owner,
-        token_badge_x,
-        token_badge_y,
-        event_authority,
+        [
+            token_mint_base_account.owner,
+            token_mint_quote_account.owner,
+        ],
+        [accounts[0].is_some(), accounts[1].is_some()],
+        program.payer(),
         user_token_x,
         user_token_y,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let (base_factor, base_fee_power_factor) =
-        compute_base_factor_from_fee_bps(bin_step, base_fee_bps)?;
-
-    let data = dlmm::client::args::InitializeCustomizablePermissionlessLbPair2 {
-        params: CustomizableParams {
+        CustomizableParams {
             active_id: computed_active_id,
             bin_step,
             base_factor,
@@ -163,14 +143,7 @@ pub async fn execute_initialize_customizable_permissionless_lb_pair2<
             collect_fee_mode: 0,
             padding: [0u8; 60],
         },
-    }
-    .data();
-
-    let init_pair_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
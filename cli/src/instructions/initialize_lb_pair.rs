@@ -69,41 +69,15 @@ pub async fn execute_initialize_lb_pair<C: Deref<Target = impl Signer> + Clone>(
         return Ok(lb_pair);
     }
 
-    let (reserve_x, _bump) = derive_reserve_pda(token_mint_x, lb_pair);
-    let (reserve_y, _bump) = derive_reserve_pda(token_mint_y, lb_pair);
-    let (oracle, _bump) = derive_oracle_pda(lb_pair);
-
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let accounts = dlmm::client::accounts::InitializeLbPair {
-        lb_pair,
-        bin_array_bitmap_extension: Some(dlmm::ID),
-        reserve_x,
-        reserve_y,
+    let init_pair_ix = commons::instructions::initialize_lb_pair(
         token_mint_x,
         token_mint_y,
-        oracle,
-        funder: program.payer(),
-        token_program: token_mint_base_account.owner,
         preset_parameter,
-        system_program: solana_sdk::system_program::ID,
-        event_authority,
-        program: dlmm::ID,
-        rent: solana_sdk::sysvar::rent::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::InitializeLbPair {
-        active_id: computed_active_id,
+        preset_parameter_state.base_factor,
+        program.payer(),
+        computed_active_id,
         bin_step,
-    }
-    .data();
-
-    let init_pair_ix = Instruction {
-        program_id: dlmm::ID,
-        data,
-        accounts,
-    };
+    )?;
 
     let request_builder = program.request();
 
@@ -63,11 +63,6 @@ pub async fn execute_initialize_lb_pair2<C: Deref<Target = impl Signer> + Clone>
         return Ok(lb_pair);
     }
 
-    let (reserve_x, _bump) = derive_reserve_pda(token_mint_x, lb_pair);
-    let (reserve_y, _bump) = derive_reserve_pda(token_mint_y, lb_pair);
-    let (oracle, _bump) = derive_oracle_pda(lb_pair);
-
-    let (event_authority, _bump) = derive_event_authority_pda();
     let (token_badge_x, _bump) = derive_token_badge_pda(token_mint_x);
     let (token_badge_y, _bump) = derive_token_badge_pda(token_mint_y);
 
@@ -75,49 +70,21 @@ pub async fn execute_initialize_lb_pair2<C: Deref<Target = impl Signer> + Clone>
         .get_multiple_accounts(&[token_badge_x, token_badge_y])
         .await?;
 
-    let token_badge_x = accounts[0]
-        .as_ref()
-        .map(|_| token_badge_x)
-        .or(Some(dlmm::ID));
-
-    let token_badge_y = accounts[1]
-        .as_ref()
-        .map(|_| token_badge_y)
-        .or(Some(dlmm::ID));
-
-    let accounts = dlmm::client::accounts::InitializeLbPair2 {
-        lb_pair,
-        bin_array_bitmap_extension: Some(dlmm::ID),
-        reserve_x,
-        reserve_y,
+    let init_pair_ix = commons::instructions::initialize_lb_pair2(
         token_mint_x,
         token_mint_y,
-        oracle,
-        funder: program.payer(),
-        token_badge_x,
-        token_badge_y,
-        token_program_x: token_mint_base_account.owner,
-        token_program_y: token_mint_quote_account.owner,
+        [
+            token_mint_base_account.owner,
+            token_mint_quote_account.owner,
+        ],
+        [accounts[0].is_some(), accounts[1].is_some()],
         preset_parameter,
-        system_program: solana_sdk::system_program::ID,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::InitializeLbPair2 {
-        params: InitializeLbPair2Params {
+        program.payer(),
+        InitializeLbPair2Params {
             active_id: computed_active_id,
             padding: [0u8; 96],
         },
-    }
-    .data();
-
-    let init_pair_ix = Instruction {
-        program_id: dlmm::ID,
-        data,
-        accounts,
-    };
+    )?;
 
     let request_builder = program.request();
 
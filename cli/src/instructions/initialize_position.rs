@@ -26,31 +26,14 @@ pub async fn execute_initialize_position<C: Deref<Target = impl Signer> + Clone>
 
     let position_keypair = Arc::new(Keypair::new());
 
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let accounts = dlmm::client::accounts::InitializePosition {
+    let init_position_ix = commons::instructions::initialize_position(
         lb_pair,
-        payer: program.payer(),
-        position: position_keypair.pubkey(),
-        owner: program.payer(),
-        rent: solana_sdk::sysvar::rent::ID,
-        system_program: solana_sdk::system_program::ID,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::InitializePosition {
+        program.payer(),
+        position_keypair.pubkey(),
+        program.payer(),
         lower_bin_id,
         width,
-    }
-    .data();
-
-    let init_position_ix = Instruction {
-        program_id: dlmm::ID,
-        data,
-        accounts,
-    };
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
@@ -67,6 +67,9 @@ pub use show_position::*;
 pub mod show_preset_parameters;
 pub use show_preset_parameters::*;
 
+pub mod decode_account;
+pub use decode_account::*;
+
 pub mod set_pair_status_permissionless;
 
 pub mod admin;
@@ -70,12 +70,6 @@ pub async fn execute_initialize_permission_lb_pair<C: Deref<Target = impl Signer
         return Ok(lb_pair);
     }
 
-    let (reserve_x, _bump) = derive_reserve_pda(token_mint_x, lb_pair);
-    let (reserve_y, _bump) = derive_reserve_pda(token_mint_y, lb_pair);
-    let (oracle, _bump) = derive_oracle_pda(lb_pair);
-
-    let (event_authority, _bump) = derive_event_authority_pda();
-
     let (token_badge_x, _bump) = derive_token_badge_pda(token_mint_x);
     let (token_badge_y, _bump) = derive_token_badge_pda(token_mint_y);
 
@@ -83,45 +77,21 @@ pub async fn execute_initialize_permission_lb_pair<C: Deref<Target = impl Signer
         .get_multiple_accounts(&[token_badge_x, token_badge_y])
         .await?;
 
-    let token_badge_x = accounts[0]
-        .as_ref()
-        .map(|_| token_badge_x)
-        .or(Some(dlmm::ID));
-
-    let token_badge_y = accounts[1]
-        .as_ref()
-        .map(|_| token_badge_y)
-        .or(Some(dlmm::ID));
-
-    let (operator, _bump) = derive_operator_pda(program.payer());
-
-    let accounts = dlmm::client::accounts::InitializePermissionLbPair {
-        base: base_keypair.pubkey(),
-        lb_pair,
-        bin_array_bitmap_extension: Some(dlmm::ID),
-        token_mint_x,
-        token_mint_y,
-        reserve_x,
-        reserve_y,
-        oracle,
-        payer: program.payer(),
-        operator,
-        signer: program.payer(),
-        token_badge_x,
-        token_badge_y,
-        token_program_x: token_mint_base_account.owner,
-        token_program_y: token_mint_quote_account.owner,
-        system_program: solana_sdk::system_program::ID,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
     let (base_factor, base_fee_power_factor) =
         compute_base_factor_from_fee_bps(bin_step, base_fee_bps)?;
 
-    let data = dlmm::client::args::InitializePermissionLbPair {
-        ix_data: InitPermissionPairIx {
+    let init_pair_ix = commons::instructions::initialize_permission_lb_pair(
+        base_keypair.pubkey(),
+        token_mint_x,
+        token_mint_y,
+        [
+            token_mint_base_account.owner,
+            token_mint_quote_account.owner,
+        ],
+        [accounts[0].is_some(), accounts[1].is_some()],
+        program.payer(),
+        program.payer(),
+        InitPermissionPairIx {
             active_id: computed_active_id,
             bin_step,
             base_factor,
@@ -131,14 +101,7 @@ pub async fn execute_initialize_permission_lb_pair<C: Deref<Target = impl Signer
             concrete_function_type: 0,
             collect_fee_mode: 0,
         },
-    }
-    .data();
-
-    let init_pair_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
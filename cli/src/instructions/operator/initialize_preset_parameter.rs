@@ -75,19 +75,10 @@ pub async fn execute_initialize_preset_parameter<C: Deref<Target = impl Signer>
     let (preset_parameter, _bump) =
         derive_preset_parameter_pda_v2(preset_parameter_v2_count as u16);
 
-    let (operator, _bump) = derive_operator_pda(program.payer());
-
-    let accounts = dlmm::client::accounts::InitializePresetParameter {
-        preset_parameter,
-        operator,
-        signer: program.payer(),
-        payer: program.payer(),
-        system_program: solana_sdk::system_program::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::InitializePresetParameter {
-        ix: InitPresetParametersIx {
+    let init_preset_param_ix = commons::instructions::initialize_preset_parameter(
+        program.payer(),
+        program.payer(),
+        InitPresetParametersIx {
             index,
             bin_step,
             base_factor,
@@ -101,14 +92,7 @@ pub async fn execute_initialize_preset_parameter<C: Deref<Target = impl Signer>
             concrete_function_type: 0,
             collect_fee_mode: 0,
         },
-    }
-    .data();
-
-    let init_preset_param_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
@@ -22,49 +22,23 @@ pub async fn execute_initialize_reward<C: Deref<Target = impl Signer> + Clone>(
         funder,
     } = params;
 
-    let (reward_vault, _bump) = derive_reward_vault_pda(lb_pair, reward_index);
-    let (event_authority, _bump) = derive_event_authority_pda();
-
     let rpc_client = program.rpc();
     let reward_mint_account = rpc_client.get_account(&reward_mint).await?;
 
     let (token_badge, _bump) = derive_token_badge_pda(reward_mint);
-    let token_badge = rpc_client
-        .get_account(&token_badge)
-        .await
-        .ok()
-        .map(|_| token_badge)
-        .or(Some(dlmm::ID));
-
-    let (operator, _bump) = derive_operator_pda(program.payer());
+    let has_token_badge = rpc_client.get_account(&token_badge).await.is_ok();
 
-    let accounts = dlmm::client::accounts::InitializeReward {
+    let instruction = commons::instructions::initialize_reward(
         lb_pair,
-        reward_vault,
         reward_mint,
-        token_badge,
-        operator,
-        signer: program.payer(),
-        payer: program.payer(),
-        token_program: reward_mint_account.owner,
-        system_program: solana_sdk::system_program::ID,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::InitializeReward {
+        reward_mint_account.owner,
+        has_token_badge,
+        program.payer(),
+        program.payer(),
         reward_index,
         reward_duration,
         funder,
-    }
-    .data();
-
-    let instruction = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
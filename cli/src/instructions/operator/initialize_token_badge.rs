@@ -1,5 +1,4 @@
 use crate::*;
-use solana_sdk::system_program;
 
 #[derive(Debug, Parser)]
 pub struct InitializeTokenBadgeParams {
@@ -14,27 +13,8 @@ pub async fn execute_initialize_token_badge<C: Deref<Target = impl Signer> + Clo
 ) -> Result<()> {
     let InitializeTokenBadgeParams { mint } = params;
 
-    let (token_badge, _bump) = derive_token_badge_pda(mint);
-
-    let (operator, _bump) = derive_operator_pda(program.payer());
-
-    let accounts = dlmm::client::accounts::InitializeTokenBadge {
-        token_mint: mint,
-        token_badge,
-        operator,
-        signer: program.payer(),
-        payer: program.payer(),
-        system_program: system_program::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::InitializeTokenBadge {}.data();
-
-    let instruction = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    let instruction =
+        commons::instructions::initialize_token_badge(mint, program.payer(), program.payer())?;
 
     let request_builder = program.request();
     let signature = request_builder
@@ -18,25 +18,8 @@ pub async fn execute_set_pair_status<C: Deref<Target = impl Signer> + Clone>(
         pair_status,
     } = params;
 
-    let (operator, _bump) = derive_operator_pda(program.payer());
-
-    let accounts = dlmm::client::accounts::SetPairStatus {
-        lb_pair,
-        operator,
-        signer: program.payer(),
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::SetPairStatus {
-        status: pair_status,
-    }
-    .data();
-
-    let instruction = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    let instruction =
+        commons::instructions::set_pair_status(lb_pair, program.payer(), pair_status)?;
 
     let request_builder = program.request();
     let signature = request_builder
@@ -25,33 +25,15 @@ pub async fn execute_update_base_fee<C: Deref<Target = impl Signer> + Clone>(
     let (base_factor, base_fee_power_factor) =
         compute_base_factor_from_fee_bps(lb_pair_state.bin_step, base_fee_bps)?;
 
-    let ix_data = dlmm::client::args::UpdateBaseFeeParameters {
-        fee_parameter: BaseFeeParameter {
+    let ix = commons::instructions::update_base_fee_parameters(
+        lb_pair,
+        program.payer(),
+        BaseFeeParameter {
             protocol_share: lb_pair_state.parameters.protocol_share,
             base_factor,
             base_fee_power_factor,
         },
-    }
-    .data();
-
-    let event_authority = derive_event_authority_pda().0;
-
-    let (operator, _bump) = derive_operator_pda(program.payer());
-
-    let accounts = dlmm::client::accounts::UpdateBaseFeeParameters {
-        lb_pair,
-        operator,
-        signer: program.payer(),
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let ix = Instruction {
-        program_id: program.id(),
-        data: ix_data,
-        accounts: accounts.to_vec(),
-    };
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
@@ -1,5 +1,5 @@
 use crate::*;
-use commons::dlmm::accounts::{BinArray, LbPair};
+use commons::dlmm::accounts::LbPair;
 
 #[derive(Debug, Parser)]
 pub struct UpdateRewardDurationParams {
@@ -26,34 +26,13 @@ pub async fn execute_update_reward_duration<C: Deref<Target = impl Signer> + Clo
         })
         .await?;
 
-    let active_bin_array_idx = BinArray::bin_id_to_bin_array_index(lb_pair_state.active_id)?;
-    let (bin_array, _bump) = derive_bin_array_pda(lb_pair, active_bin_array_idx as i64);
-
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let (operator, _bump) = derive_operator_pda(program.payer());
-
-    let accounts = dlmm::client::accounts::UpdateRewardDuration {
+    let ix = commons::instructions::update_reward_duration(
         lb_pair,
-        operator,
-        signer: program.payer(),
-        bin_array,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::UpdateRewardDuration {
+        &lb_pair_state,
+        program.payer(),
         reward_index,
-        new_duration: reward_duration,
-    }
-    .data();
-
-    let ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+        reward_duration,
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
@@ -18,30 +18,12 @@ pub async fn execute_update_reward_funder<C: Deref<Target = impl Signer> + Clone
         funder,
     } = params;
 
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let (operator, _bump) = derive_operator_pda(program.payer());
-
-    let accounts = dlmm::client::accounts::UpdateRewardFunder {
+    let ix = commons::instructions::update_reward_funder(
         lb_pair,
-        operator,
-        signer: program.payer(),
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::UpdateRewardFunder {
+        program.payer(),
         reward_index,
-        new_funder: funder,
-    }
-    .data();
-
-    let ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+        funder,
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
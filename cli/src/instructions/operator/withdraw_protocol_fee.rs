@@ -36,52 +36,23 @@ pub async fn execute_withdraw_protocol_fee<C: Deref<Target = impl Signer> + Clon
         &token_y_program,
     );
 
-    let (operator, _bump) = derive_operator_pda(program.payer());
-
-    let main_accounts = dlmm::client::accounts::WithdrawProtocolFee {
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &lb_pair_state,
+        &program.rpc(),
+        ActionType::Liquidity,
+    )
+    .await?;
+
+    let withdraw_ix = commons::instructions::withdraw_protocol_fee(
         lb_pair,
-        reserve_x: lb_pair_state.reserve_x,
-        reserve_y: lb_pair_state.reserve_y,
-        token_x_mint: lb_pair_state.token_x_mint,
-        token_y_mint: lb_pair_state.token_y_mint,
+        &lb_pair_state,
+        program.payer(),
         receiver_token_x,
         receiver_token_y,
-        operator,
-        signer: program.payer(),
-        token_x_program,
-        token_y_program,
-    }
-    .to_account_metas(None);
-
-    let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-    let mut remaining_accounts = vec![];
-
-    if let Some((slices, transfer_hook_remaining_accounts)) =
-        get_potential_token_2022_related_ix_data_and_accounts(
-            &lb_pair_state,
-            program.rpc(),
-            ActionType::Liquidity,
-        )
-        .await?
-    {
-        remaining_accounts_info.slices = slices;
-        remaining_accounts.extend(transfer_hook_remaining_accounts);
-    };
-
-    let data = dlmm::client::args::WithdrawProtocolFee {
-        max_amount_x: u64::MAX,
-        max_amount_y: u64::MAX,
-        remaining_accounts_info,
-    }
-    .data();
-
-    let accounts = [main_accounts.to_vec(), remaining_accounts].concat();
-
-    let withdraw_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+        transfer_hook_accounts,
+        u64::MAX,
+        u64::MAX,
+    )?;
 
     let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(200_000);
 
@@ -1,8 +1,7 @@
-use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use anchor_spl::associated_token::get_associated_token_address_with_program_id;
-use commons::dlmm::accounts::{BinArray, LbPair};
+use commons::dlmm::accounts::LbPair;
 use commons::dlmm::types::PlaceLimitOrderParams as PlaceLimitOrderIxParams;
 
 use crate::*;
@@ -52,10 +51,10 @@ pub async fn execute_place_limit_order<C: Deref<Target = impl Signer> + Clone>(
         })
         .await?;
 
-    let (token_mint, reserve) = if is_ask_side {
-        (lb_pair_state.token_x_mint, lb_pair_state.reserve_x)
+    let token_mint = if is_ask_side {
+        lb_pair_state.token_x_mint
     } else {
-        (lb_pair_state.token_y_mint, lb_pair_state.reserve_y)
+        lb_pair_state.token_y_mint
     };
 
     let token_mint_account = rpc_client.get_account(&token_mint).await?;
@@ -64,60 +63,23 @@ pub async fn execute_place_limit_order<C: Deref<Target = impl Signer> + Clone>(
     let user_token =
         get_associated_token_address_with_program_id(&program.payer(), &token_mint, &token_program);
 
-    let bitmap_extension_key = derive_bin_array_bitmap_extension(lb_pair).0;
-    let bin_array_bitmap_extension = rpc_client
-        .get_account(&bitmap_extension_key)
-        .await
-        .ok()
-        .map(|_| bitmap_extension_key)
-        .or(Some(dlmm::ID));
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &lb_pair_state,
+        &program.rpc(),
+        ActionType::Liquidity,
+    )
+    .await?;
 
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let main_accounts = dlmm::client::accounts::PlaceLimitOrder {
+    let place_limit_order_ix = commons::instructions::place_limit_order(
         lb_pair,
-        bin_array_bitmap_extension,
-        reserve,
-        token_mint,
-        limit_order: limit_order_keypair.pubkey(),
-        payer: program.payer(),
+        &lb_pair_state,
+        limit_order_keypair.pubkey(),
+        program.payer(),
         owner,
+        program.payer(),
         user_token,
-        sender: program.payer(),
-        token_program,
-        system_program: solana_sdk::system_program::ID,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-    let mut remaining_accounts = vec![];
-
-    if let Some((slices, transfer_hook_remaining_accounts)) =
-        get_potential_token_2022_related_ix_data_and_accounts(
-            &lb_pair_state,
-            program.rpc(),
-            ActionType::Liquidity,
-        )
-        .await?
-    {
-        remaining_accounts_info.slices = slices;
-        remaining_accounts.extend(transfer_hook_remaining_accounts);
-    };
-
-    let bin_array_indexes: BTreeSet<i64> = bins
-        .iter()
-        .map(|(id, _)| BinArray::bin_id_to_bin_array_index(*id).map(|idx| idx as i64))
-        .collect::<Result<_>>()?;
-
-    remaining_accounts.extend(bin_array_indexes.iter().map(|&idx| {
-        let (bin_array, _) = derive_bin_array_pda(lb_pair, idx);
-        AccountMeta::new(bin_array, false)
-    }));
-
-    let data = dlmm::client::args::PlaceLimitOrder {
-        params: PlaceLimitOrderIxParams {
+        transfer_hook_accounts,
+        PlaceLimitOrderIxParams {
             is_ask_side,
             padding: [0u8; 16],
             relative_bin: None,
@@ -126,17 +88,7 @@ pub async fn execute_place_limit_order<C: Deref<Target = impl Signer> + Clone>(
                 .map(|(id, amount)| BinLimitOrderAmount { id, amount })
                 .collect(),
         },
-        remaining_accounts_info,
-    }
-    .data();
-
-    let accounts = [main_accounts.to_vec(), remaining_accounts].concat();
-
-    let place_limit_order_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    )?;
 
     let request_builder = program.request();
     let signature = request_builder
@@ -30,29 +30,12 @@ pub async fn execute_remove_liquidity<C: Deref<Target = impl Signer> + Clone>(
 
     let rpc_client = program.rpc();
 
-    let mut accounts = rpc_client
-        .get_multiple_accounts(&[lb_pair, position])
+    let lb_pair_state: LbPair = rpc_client
+        .get_account_and_deserialize(&lb_pair, |account| {
+            pod_read_unaligned_skip_disc(&account.data)
+        })
         .await?;
 
-    let lb_pair_account = accounts[0].take().context("lb_pair not found")?;
-    let position_account = accounts[1].take().context("position not found")?;
-
-    let lb_pair_state: LbPair = pod_read_unaligned_skip_disc(&lb_pair_account.data)?;
-    let position_state: PositionV2 = pod_read_unaligned_skip_disc(&position_account.data)?;
-
-    let min_bin_id = bin_liquidity_removal
-        .first()
-        .map(|(bin_id, _)| *bin_id)
-        .context("bin_liquidity_removal is empty")?;
-
-    let max_bin_id = bin_liquidity_removal
-        .last()
-        .map(|(bin_id, _)| *bin_id)
-        .context("bin_liquidity_removal is empty")?;
-
-    let bin_arrays_account_meta =
-        position_state.get_bin_array_accounts_meta_coverage_by_chunk(min_bin_id, max_bin_id)?;
-
     let user_token_x = get_or_create_ata(
         program,
         transaction_config,
@@ -71,53 +54,12 @@ pub async fn execute_remove_liquidity<C: Deref<Target = impl Signer> + Clone>(
     )
     .await?;
 
-    let (bin_array_bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
-    let bin_array_bitmap_extension = rpc_client
-        .get_account(&bin_array_bitmap_extension)
-        .await
-        .map(|_| bin_array_bitmap_extension)
-        .ok()
-        .or(Some(dlmm::ID));
-
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-    let mut remaining_accounts = vec![];
-
-    if let Some((slices, transfer_hook_remaining_accounts)) =
-        get_potential_token_2022_related_ix_data_and_accounts(
-            &lb_pair_state,
-            program.rpc(),
-            ActionType::Liquidity,
-        )
-        .await?
-    {
-        remaining_accounts_info.slices = slices;
-        remaining_accounts.extend(transfer_hook_remaining_accounts);
-    };
-
-    remaining_accounts.extend(bin_arrays_account_meta);
-
-    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
-
-    let main_accounts = dlmm::client::accounts::RemoveLiquidity2 {
-        position,
-        lb_pair,
-        bin_array_bitmap_extension,
-        user_token_x,
-        user_token_y,
-        reserve_x: lb_pair_state.reserve_x,
-        reserve_y: lb_pair_state.reserve_y,
-        token_x_mint: lb_pair_state.token_x_mint,
-        token_x_program,
-        token_y_mint: lb_pair_state.token_y_mint,
-        token_y_program,
-        sender: program.payer(),
-        memo_program: spl_memo::ID,
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &lb_pair_state,
+        &program.rpc(),
+        ActionType::Liquidity,
+    )
+    .await?;
 
     let bin_liquidity_removal = bin_liquidity_removal
         .into_iter()
@@ -127,19 +69,16 @@ pub async fn execute_remove_liquidity<C: Deref<Target = impl Signer> + Clone>(
         })
         .collect::<Vec<BinLiquidityReduction>>();
 
-    let data = dlmm::client::args::RemoveLiquidity2 {
+    let remove_liquidity_ix = commons::instructions::remove_liquidity2(
+        position,
+        lb_pair,
+        &lb_pair_state,
+        program.payer(),
+        user_token_x,
+        user_token_y,
+        transfer_hook_accounts,
         bin_liquidity_removal,
-        remaining_accounts_info,
-    }
-    .data();
-
-    let accounts = [main_accounts.to_vec(), remaining_accounts].concat();
-
-    let remove_liquidity_ix = Instruction {
-        program_id: dlmm::ID,
-        data,
-        accounts,
-    };
+    )?;
 
     let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
 
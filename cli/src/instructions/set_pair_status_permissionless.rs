@@ -15,21 +15,10 @@ pub async fn execute_set_pair_status_permissionless<C: Deref<Target = impl Signe
 ) -> Result<()> {
     let SetPairStatusPermissionlessParams { lb_pair, enable } = params;
 
-    let accounts = dlmm::client::accounts::SetPairStatusPermissionless {
-        lb_pair,
-        signer: program.payer(),
-    }
-    .to_account_metas(None);
-
     let status = if enable { 1 } else { 0 };
 
-    let data = dlmm::client::args::SetPairStatusPermissionless { status }.data();
-
-    let set_pair_status_permissionless_ix = Instruction {
-        accounts,
-        data,
-        program_id: dlmm::ID,
-    };
+    let set_pair_status_permissionless_ix =
+        commons::instructions::set_pair_status_permissionless(lb_pair, program.payer(), status)?;
 
     let request_builder = program.request();
     let signature = request_builder
@@ -17,23 +17,8 @@ pub async fn execute_set_permissionless_operation_bits<C: Deref<Target = impl Si
 ) -> Result<()> {
     let SetPermissionlessOperationBitsParams { position, bits } = params;
 
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let accounts = dlmm::client::accounts::SetPermissionlessOperationBits {
-        position,
-        owner: program.payer(),
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let data = dlmm::client::args::SetPermissionlessOperationBits { bits }.data();
-
-    let instruction = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+    let instruction =
+        commons::instructions::set_permissionless_operation_bits(position, program.payer(), bits)?;
 
     let request_builder = program.request();
     let signature = request_builder
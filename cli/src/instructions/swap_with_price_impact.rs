@@ -12,6 +12,9 @@ pub struct SwapWithPriceImpactParams {
     pub swap_for_y: bool,
     /// Allowed price impact in bps.
     pub price_impact_bps: u16,
+    /// Token account receiving the host fee, in the token the trading fee is charged in.
+    #[clap(long)]
+    pub host_fee_in: Option<Pubkey>,
 }
 
 pub async fn execute_swap_with_price_impact<C: Deref<Target = impl Signer> + Clone>(
@@ -24,14 +27,12 @@ pub async fn execute_swap_with_price_impact<C: Deref<Target = impl Signer> + Clo
         lb_pair,
         swap_for_y,
         price_impact_bps,
+        host_fee_in,
     } = params;
 
     let rpc_client = program.rpc();
-    let lb_pair_state: LbPair = rpc_client
-        .get_account_and_deserialize(&lb_pair, |account| {
-            pod_read_unaligned_skip_disc(&account.data)
-        })
-        .await?;
+    let pool = PoolSnapshot::load(&rpc_client, lb_pair, 3).await?;
+    let lb_pair_state = pool.lb_pair;
 
     let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
 
@@ -63,107 +64,54 @@ pub async fn execute_swap_with_price_impact<C: Deref<Target = impl Signer> + Clo
         )
     };
 
-    let (bitmap_extension_key, _bump) = derive_bin_array_bitmap_extension(lb_pair);
-
-    let bitmap_extension = rpc_client
-        .get_account_and_deserialize(&bitmap_extension_key, |account| {
-            pod_read_unaligned_skip_disc(&account.data)
-        })
-        .await
-        .ok();
-
-    let bin_arrays_for_swap = get_bin_array_pubkeys_for_swap(
+    let bin_array_keys = get_bin_array_pubkeys_for_swap(
         lb_pair,
         &lb_pair_state,
-        bitmap_extension.as_ref(),
+        pool.bitmap_extension.as_ref(),
         swap_for_y,
         3,
     )?;
 
-    let SwapQuoteAccounts {
-        lb_pair_state,
-        clock,
-        mint_x_account,
-        mint_y_account,
-        bin_arrays,
-        bin_array_keys,
-    } = fetch_quote_required_accounts(&rpc_client, lb_pair, &lb_pair_state, bin_arrays_for_swap)
-        .await?;
-
-    let quote = quote_exact_in(
-        lb_pair,
-        &lb_pair_state,
+    let quote = pool.quote_exact_in_with_price_impact(
         amount_in,
         swap_for_y,
-        bin_arrays,
-        bitmap_extension.as_ref(),
-        &clock,
-        &mint_x_account,
-        &mint_y_account,
+        Some(lb_pair_state.active_id),
+        price_impact_bps,
+        QuoteOptions {
+            host_fee_bps: host_fee_in.map(|_| HOST_FEE_BPS),
+            user: Some(program.payer()),
+        },
     )?;
 
     println!("{:#?}", quote);
 
-    let (event_authority, _bump) = derive_event_authority_pda();
+    ensure!(
+        quote.amount_left == 0,
+        "Swap exceeds the price impact, only {} can be swapped",
+        quote.amount_in
+    );
+
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &lb_pair_state,
+        &program.rpc(),
+        ActionType::Liquidity,
+    )
+    .await?;
 
-    let main_accounts = dlmm::client::accounts::SwapWithPriceImpact2 {
+    let swap_ix = commons::instructions::swap_with_price_impact2(
         lb_pair,
-        bin_array_bitmap_extension: bitmap_extension
-            .map(|_| bitmap_extension_key)
-            .or(Some(dlmm::ID)),
-        reserve_x: lb_pair_state.reserve_x,
-        reserve_y: lb_pair_state.reserve_y,
-        token_x_mint: lb_pair_state.token_x_mint,
-        token_y_mint: lb_pair_state.token_y_mint,
-        token_x_program,
-        token_y_program,
-        user: program.payer(),
+        &lb_pair_state,
+        pool.bitmap_extension.is_some(),
+        program.payer(),
         user_token_in,
         user_token_out,
-        oracle: lb_pair_state.oracle,
-        host_fee_in: Some(dlmm::ID),
-        event_authority,
-        program: dlmm::ID,
-        memo_program: spl_memo::ID,
-    }
-    .to_account_metas(None);
-
-    let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-    let mut remaining_accounts = vec![];
-
-    if let Some((slices, transfer_hook_remaining_accounts)) =
-        get_potential_token_2022_related_ix_data_and_accounts(
-            &lb_pair_state,
-            program.rpc(),
-            ActionType::Liquidity,
-        )
-        .await?
-    {
-        remaining_accounts_info.slices = slices;
-        remaining_accounts.extend(transfer_hook_remaining_accounts);
-    }
-
-    remaining_accounts.extend(
-        bin_array_keys
-            .into_iter()
-            .map(|key| AccountMeta::new(key, false)),
-    );
-
-    let data = dlmm::client::args::SwapWithPriceImpact2 {
+        host_fee_in,
+        &bin_array_keys,
+        transfer_hook_accounts,
         amount_in,
-        active_id: Some(lb_pair_state.active_id),
-        remaining_accounts_info,
-        max_price_impact_bps: price_impact_bps,
-    }
-    .data();
-
-    let accounts = [main_accounts.to_vec(), remaining_accounts].concat();
-
-    let swap_ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data,
-    };
+        Some(lb_pair_state.active_id),
+        price_impact_bps,
+    )?;
 
     let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
 
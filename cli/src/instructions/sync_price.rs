@@ -48,11 +48,6 @@ pub async fn execute_sync_price<C: Deref<Target = impl Signer> + Clone>(
         get_id_from_price(lb_pair_state.bin_step, &price_per_lamport, Rounding::Up)
             .context("get_id_from_price overflow")?;
 
-    let ix_data = dlmm::client::args::GoToABin {
-        bin_id: computed_active_id,
-    }
-    .data();
-
     let from_bin_array_idx = BinArray::bin_id_to_bin_array_index(lb_pair_state.active_id)?;
     let to_bin_array_idx = BinArray::bin_id_to_bin_array_index(computed_active_id)?;
 
@@ -66,29 +61,14 @@ pub async fn execute_sync_price<C: Deref<Target = impl Signer> + Clone>(
     let from_bin_array_account = accounts[0].take();
     let to_bin_array_account = accounts[1].take();
 
-    let (event_authority, _bump) = derive_event_authority_pda();
-
-    let accounts = dlmm::client::accounts::GoToABin {
+    let ix = commons::instructions::go_to_a_bin(
         lb_pair,
-        bin_array_bitmap_extension: bin_array_bitmap_extension_account
-            .map(|_| bin_array_bitmap_extension)
-            .or(Some(dlmm::ID)),
-        from_bin_array: from_bin_array_account
-            .map(|_| from_bin_array)
-            .or(Some(dlmm::ID)),
-        to_bin_array: to_bin_array_account
-            .map(|_| to_bin_array)
-            .or(Some(dlmm::ID)),
-        event_authority,
-        program: dlmm::ID,
-    }
-    .to_account_metas(None);
-
-    let ix = Instruction {
-        program_id: dlmm::ID,
-        accounts,
-        data: ix_data,
-    };
+        &lb_pair_state,
+        bin_array_bitmap_extension_account.is_some(),
+        from_bin_array_account.is_some(),
+        to_bin_array_account.is_some(),
+        computed_active_id,
+    )?;
 
     let mut ixs = vec![];
 
@@ -9,10 +9,7 @@ use anchor_client::{
         signer::{keypair::*, Signer},
     },
 };
-use anchor_lang::prelude::AccountMeta;
 use anchor_lang::AccountDeserialize;
-use anchor_lang::InstructionData;
-use anchor_lang::ToAccountMetas;
 use anyhow::*;
 use clap::*;
 use commons::*;
@@ -45,6 +42,10 @@ fn get_set_compute_unit_price_ix(micro_lamports: u64) -> Option<Instruction> {
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    run().await.map_err(add_dlmm_error_context)
+}
+
+async fn run() -> Result<()> {
     let cli = Cli::parse();
 
     let payer =
@@ -122,6 +123,9 @@ async fn main() -> Result<()> {
         DLMMCommand::ShowPosition(params) => {
             execute_show_position(params, &program).await?;
         }
+        DLMMCommand::DecodeAccount(params) => {
+            execute_decode_account(params, &program).await?;
+        }
         DLMMCommand::ClaimReward(params) => {
             execute_claim_reward(params, &program, transaction_config, compute_unit_price_ix)
                 .await?;
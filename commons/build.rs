@@ -0,0 +1,302 @@
+use serde_json::Value;
+use std::{env, fmt::Write, fs, path::Path};
+
+const IDL_PATH: &str = "../idls/dlmm.json";
+
+/// Generate the `DlmmError` enum, the list of events and the serde implementations of the
+/// program types from the IDL.
+fn main() {
+    println!("cargo:rerun-if-changed={IDL_PATH}");
+
+    let idl: Value =
+        serde_json::from_str(&fs::read_to_string(IDL_PATH).expect("Failed to read the IDL"))
+            .expect("Failed to parse the IDL");
+
+    let out_dir = env::var("OUT_DIR").expect("Missing OUT_DIR");
+    fs::write(
+        Path::new(&out_dir).join("dlmm_errors.rs"),
+        generate_errors(&idl),
+    )
+    .expect("Failed to write the DLMM errors");
+    fs::write(
+        Path::new(&out_dir).join("dlmm_events.rs"),
+        generate_events(&idl),
+    )
+    .expect("Failed to write the DLMM events");
+    fs::write(
+        Path::new(&out_dir).join("dlmm_serde.rs"),
+        generate_serde(&idl),
+    )
+    .expect("Failed to write the DLMM serde implementations");
+}
+
+fn as_array<'a>(value: &'a Value, name: &str) -> &'a Vec<Value> {
+    value[name]
+        .as_array()
+        .unwrap_or_else(|| panic!("Missing {name} in the IDL"))
+}
+
+fn as_str<'a>(value: &'a Value, name: &str) -> &'a str {
+    value[name]
+        .as_str()
+        .unwrap_or_else(|| panic!("Missing {name} in the IDL"))
+}
+
+fn generate_errors(idl: &Value) -> String {
+    let errors = as_array(idl, "errors")
+        .iter()
+        .map(|error| {
+            (
+                error["code"].as_u64().expect("Missing error code"),
+                as_str(error, "name"),
+                error["msg"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut code = String::new();
+    writeln!(
+        code,
+        "/// Custom errors of the DLMM program, generated from the IDL."
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]"
+    )
+    .unwrap();
+    writeln!(code, "#[repr(u32)]").unwrap();
+    writeln!(code, "pub enum DlmmError {{").unwrap();
+    for (error_code, name, msg) in errors.iter() {
+        writeln!(code, "    /// {msg}").unwrap();
+        writeln!(code, "    {name} = {error_code},").unwrap();
+    }
+    writeln!(code, "}}").unwrap();
+
+    writeln!(code, "impl DlmmError {{").unwrap();
+    writeln!(code, "    pub const ALL: [DlmmError; {}] = [", errors.len()).unwrap();
+    for (_, name, _) in errors.iter() {
+        writeln!(code, "        DlmmError::{name},").unwrap();
+    }
+    writeln!(code, "    ];").unwrap();
+
+    writeln!(code, "    pub fn from_code(code: u32) -> Option<Self> {{").unwrap();
+    writeln!(code, "        match code {{").unwrap();
+    for (error_code, name, _) in errors.iter() {
+        writeln!(code, "            {error_code} => Some(DlmmError::{name}),").unwrap();
+    }
+    writeln!(code, "            _ => None,").unwrap();
+    writeln!(code, "        }}").unwrap();
+    writeln!(code, "    }}").unwrap();
+
+    writeln!(code, "    pub fn name(&self) -> &'static str {{").unwrap();
+    writeln!(code, "        match self {{").unwrap();
+    for (_, name, _) in errors.iter() {
+        writeln!(code, "            DlmmError::{name} => {name:?},").unwrap();
+    }
+    writeln!(code, "        }}").unwrap();
+    writeln!(code, "    }}").unwrap();
+
+    writeln!(code, "    pub fn message(&self) -> &'static str {{").unwrap();
+    writeln!(code, "        match self {{").unwrap();
+    for (_, name, msg) in errors.iter() {
+        writeln!(code, "            DlmmError::{name} => {msg:?},").unwrap();
+    }
+    writeln!(code, "        }}").unwrap();
+    writeln!(code, "    }}").unwrap();
+    writeln!(code, "}}").unwrap();
+
+    code
+}
+
+/// `dlmm_events!` invocation listing every event of the IDL.
+fn generate_events(idl: &Value) -> String {
+    let mut code = String::new();
+    writeln!(code, "dlmm_events!(").unwrap();
+    for event in as_array(idl, "events") {
+        writeln!(code, "    {},", as_str(event, "name")).unwrap();
+    }
+    writeln!(code, ");").unwrap();
+
+    code
+}
+
+/// Module of the declared program holding the type `name`.
+fn type_module(idl: &Value, name: &str) -> &'static str {
+    let contains = |list: &str| {
+        as_array(idl, list)
+            .iter()
+            .any(|item| as_str(item, "name") == name)
+    };
+    if contains("accounts") {
+        "accounts"
+    } else if contains("events") {
+        "events"
+    } else {
+        "types"
+    }
+}
+
+/// Rust type of an IDL type.
+fn rust_type(idl: &Value, ty: &Value) -> String {
+    if let Some(ty) = ty.as_str() {
+        return match ty {
+            "pubkey" => "Pubkey".to_string(),
+            ty => ty.to_string(),
+        };
+    }
+    if let Some([item, len]) = ty["array"].as_array().map(Vec::as_slice) {
+        return format!("[{}; {}]", rust_type(idl, item), len);
+    }
+    if let Some(item) = ty.get("vec") {
+        return format!("Vec<{}>", rust_type(idl, item));
+    }
+    if let Some(item) = ty.get("option") {
+        return format!("Option<{}>", rust_type(idl, item));
+    }
+    if let Some(name) = ty["defined"]["name"].as_str() {
+        return format!("dlmm::{}::{}", type_module(idl, name), name);
+    }
+    panic!("Unsupported IDL type {ty}")
+}
+
+/// `Serialize` and `Deserialize` of every type of the IDL. The fields go through `IdlSerialize`
+/// and `IdlDeserialize`, which encode pubkeys and 128 bits integers as strings.
+fn generate_serde(idl: &Value) -> String {
+    let mut code = String::new();
+    for ty in as_array(idl, "types") {
+        let name = as_str(ty, "name");
+        let path = format!("dlmm::{}::{}", type_module(idl, name), name);
+        let ty = &ty["type"];
+
+        let mut serialize = String::new();
+        let mut deserialize = String::new();
+        match as_str(ty, "kind") {
+            "struct" => {
+                let fields = ty["fields"].as_array().cloned().unwrap_or_default();
+                let fields = fields
+                    .iter()
+                    .map(|field| {
+                        let field_name = field["name"]
+                            .as_str()
+                            .unwrap_or_else(|| panic!("Unsupported tuple struct {name}"));
+                        (field_name, rust_type(idl, &field["type"]))
+                    })
+                    .collect::<Vec<_>>();
+
+                writeln!(serialize, "use serde::ser::SerializeStruct;").unwrap();
+                writeln!(
+                    serialize,
+                    "let mut state = serializer.serialize_struct({name:?}, {})?;",
+                    fields.len()
+                )
+                .unwrap();
+                for (field_name, _) in fields.iter() {
+                    writeln!(
+                        serialize,
+                        "state.serialize_field({field_name:?}, &IdlSerialize(&self.{field_name}))?;"
+                    )
+                    .unwrap();
+                }
+                writeln!(serialize, "state.end()").unwrap();
+
+                writeln!(deserialize, "#[derive(serde::Deserialize)]").unwrap();
+                writeln!(deserialize, "#[serde(rename = {name:?})]").unwrap();
+                writeln!(deserialize, "struct Fields {{").unwrap();
+                for (field_name, field_type) in fields.iter() {
+                    writeln!(deserialize, "{field_name}: IdlDeserialize<{field_type}>,").unwrap();
+                }
+                writeln!(deserialize, "}}").unwrap();
+                writeln!(
+                    deserialize,
+                    "let fields = <Fields as serde::Deserialize>::deserialize(deserializer)?;"
+                )
+                .unwrap();
+                writeln!(deserialize, "std::result::Result::Ok(Self {{").unwrap();
+                for (field_name, _) in fields.iter() {
+                    writeln!(deserialize, "{field_name}: fields.{field_name}.0,").unwrap();
+                }
+                writeln!(deserialize, "}})").unwrap();
+            }
+            "enum" => {
+                writeln!(serialize, "match self {{").unwrap();
+                writeln!(deserialize, "#[derive(serde::Deserialize)]").unwrap();
+                writeln!(deserialize, "#[serde(rename = {name:?})]").unwrap();
+                writeln!(deserialize, "enum Variants {{").unwrap();
+                let mut variants_match = String::new();
+
+                for (index, variant) in as_array(ty, "variants").iter().enumerate() {
+                    let variant_name = as_str(variant, "name");
+                    let fields = variant["fields"].as_array().cloned().unwrap_or_default();
+                    match fields.as_slice() {
+                        [] => {
+                            writeln!(
+                                serialize,
+                                "Self::{variant_name} => serializer.serialize_unit_variant({name:?}, {index}, {variant_name:?}),"
+                            )
+                            .unwrap();
+                            writeln!(deserialize, "{variant_name},").unwrap();
+                            writeln!(
+                                variants_match,
+                                "Variants::{variant_name} => Self::{variant_name},"
+                            )
+                            .unwrap();
+                        }
+                        [field] if field.get("name").is_none() => {
+                            writeln!(
+                                serialize,
+                                "Self::{variant_name}(value) => serializer.serialize_newtype_variant({name:?}, {index}, {variant_name:?}, &IdlSerialize(value)),"
+                            )
+                            .unwrap();
+                            writeln!(
+                                deserialize,
+                                "{variant_name}(IdlDeserialize<{}>),",
+                                rust_type(idl, field)
+                            )
+                            .unwrap();
+                            writeln!(
+                                variants_match,
+                                "Variants::{variant_name}(value) => Self::{variant_name}(value.0),"
+                            )
+                            .unwrap();
+                        }
+                        _ => panic!("Unsupported enum variant {name}::{variant_name}"),
+                    }
+                }
+
+                writeln!(serialize, "}}").unwrap();
+                writeln!(deserialize, "}}").unwrap();
+                writeln!(
+                    deserialize,
+                    "std::result::Result::Ok(match <Variants as serde::Deserialize>::deserialize(deserializer)? {{"
+                )
+                .unwrap();
+                deserialize.push_str(&variants_match);
+                writeln!(deserialize, "}})").unwrap();
+            }
+            kind => panic!("Unsupported type kind {kind} of {name}"),
+        }
+
+        writeln!(code, "impl serde::Serialize for {path} {{").unwrap();
+        writeln!(
+            code,
+            "fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {{"
+        )
+        .unwrap();
+        code.push_str(&serialize);
+        writeln!(code, "}}\n}}").unwrap();
+
+        writeln!(code, "impl<'de> serde::Deserialize<'de> for {path} {{").unwrap();
+        writeln!(
+            code,
+            "fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {{"
+        )
+        .unwrap();
+        code.push_str(&deserialize);
+        writeln!(code, "}}\n}}").unwrap();
+
+        writeln!(code, "impl_idl_serde!({path});").unwrap();
+    }
+
+    code
+}
@@ -16,10 +16,7 @@ pub fn position_filter_by_wallet_and_pair(wallet: Pubkey, pair: Pubkey) -> Vec<R
     vec![position_pair_filter, position_owner_filter]
 }
 
-pub fn limit_order_filter_by_owner_and_pair(
-    owner: Pubkey,
-    pair: Pubkey,
-) -> Vec<RpcFilterType> {
+pub fn limit_order_filter_by_owner_and_pair(owner: Pubkey, pair: Pubkey) -> Vec<RpcFilterType> {
     let discriminator_filter =
         RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &LimitOrder::DISCRIMINATOR));
 
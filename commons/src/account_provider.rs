@@ -0,0 +1,137 @@
+use crate::*;
+use anchor_client::solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::RpcFilterType,
+    rpc_request::MAX_MULTIPLE_ACCOUNTS,
+};
+use anchor_spl::token_2022::spl_token_2022::{
+    generic_token_account::GenericTokenAccount, state::Account as TokenAccount,
+};
+use async_trait::async_trait;
+use solana_account_decoder::UiAccountEncoding;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::{collections::HashMap, sync::Arc};
+
+/// Source of account states used by the loaders. Implemented for the RPC client, an in-memory
+/// account map, and LiteSVM (`litesvm` feature).
+#[async_trait]
+pub trait AccountProvider: Send + Sync {
+    /// Accounts in the same order as `pubkeys`, `None` for accounts that do not exist.
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>>;
+
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>> {
+        let mut accounts = self.get_multiple_accounts(&[*pubkey]).await?;
+        Ok(accounts.pop().flatten())
+    }
+
+    /// Accounts owned by `program_id` matching all `filters`.
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        _filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Account)>> {
+        bail!("Program accounts of {} can't be listed", program_id)
+    }
+}
+
+#[async_trait]
+impl<P: AccountProvider + ?Sized> AccountProvider for Arc<P> {
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        P::get_multiple_accounts(self, pubkeys).await
+    }
+
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>> {
+        P::get_account(self, pubkey).await
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Account)>> {
+        P::get_program_accounts(self, program_id, filters).await
+    }
+}
+
+#[async_trait]
+impl AccountProvider for RpcClient {
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        let mut accounts = Vec::with_capacity(pubkeys.len());
+        for chunk in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            accounts.extend(RpcClient::get_multiple_accounts(self, chunk).await?);
+        }
+        Ok(accounts)
+    }
+
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>> {
+        let response = self
+            .get_account_with_commitment(pubkey, self.commitment())
+            .await?;
+        Ok(response.value)
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Account)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        Ok(self
+            .get_program_accounts_with_config(program_id, config)
+            .await?)
+    }
+}
+
+fn filter_allows(filter: &RpcFilterType, data: &[u8]) -> bool {
+    match filter {
+        RpcFilterType::DataSize(size) => data.len() as u64 == *size,
+        RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(data),
+        RpcFilterType::TokenAccountState => TokenAccount::valid_account_data(data),
+    }
+}
+
+#[async_trait]
+impl AccountProvider for HashMap<Pubkey, Account> {
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| self.get(pubkey).cloned())
+            .collect())
+    }
+
+    async fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Account)>> {
+        Ok(self
+            .iter()
+            .filter(|(_, account)| {
+                account.owner == *program_id
+                    && filters
+                        .iter()
+                        .all(|filter| filter_allows(filter, &account.data))
+            })
+            .map(|(pubkey, account)| (*pubkey, account.clone()))
+            .collect())
+    }
+}
+
+#[cfg(feature = "litesvm")]
+#[async_trait]
+impl AccountProvider for litesvm::LiteSVM {
+    async fn get_multiple_accounts(&self, pubkeys: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| litesvm::LiteSVM::get_account(self, pubkey))
+            .collect())
+    }
+}
@@ -0,0 +1,320 @@
+use crate::*;
+use anchor_lang::InstructionData;
+use solana_sdk::{account::Account, clock::Clock, instruction::AccountMeta, pubkey::Pubkey};
+use std::collections::HashMap;
+
+/// Number of bin arrays loaded, and passed to the swap, in each swap direction.
+const BIN_ARRAYS_PER_SWAP_DIRECTION: u8 = 3;
+
+#[derive(Debug, Clone)]
+pub struct KeyedAccount {
+    pub key: Pubkey,
+    pub account: Account,
+}
+
+pub type AccountMap = HashMap<Pubkey, Account>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapMode {
+    ExactIn,
+    ExactOut,
+}
+
+#[derive(Debug, Clone)]
+pub struct QuoteParams {
+    /// Amount in for `ExactIn`, amount out for `ExactOut`
+    pub amount: u64,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub swap_mode: SwapMode,
+}
+
+#[derive(Debug, Clone)]
+pub struct Quote {
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub fee_amount: u64,
+    pub fee_mint: Pubkey,
+}
+
+#[derive(Debug, Clone)]
+pub struct SwapParams {
+    pub swap_mode: SwapMode,
+    /// Amount in for `ExactIn`, maximum amount in for `ExactOut`
+    pub in_amount: u64,
+    /// Minimum amount out for `ExactIn`, amount out for `ExactOut`
+    pub out_amount: u64,
+    pub source_mint: Pubkey,
+    pub destination_mint: Pubkey,
+    pub source_token_account: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub token_transfer_authority: Pubkey,
+}
+
+#[derive(Debug, Clone)]
+pub struct SwapAndAccountMetas {
+    /// Instruction data of the swap
+    pub data: Vec<u8>,
+    /// Accounts of the swap instruction, including the bin arrays
+    pub account_metas: Vec<AccountMeta>,
+}
+
+/// Pool interface used by aggregators. The aggregator loads the pool with
+/// [`Amm::from_keyed_account`], then repeatedly fetches [`Amm::get_accounts_to_update`] and
+/// passes them to [`Amm::update`] before quoting.
+pub trait Amm {
+    fn from_keyed_account(keyed_account: &KeyedAccount) -> Result<Self>
+    where
+        Self: Sized;
+    fn key(&self) -> Pubkey;
+    fn program_id(&self) -> Pubkey;
+    fn get_reserve_mints(&self) -> Vec<Pubkey>;
+    fn get_accounts_to_update(&self) -> Vec<Pubkey>;
+    fn update(&mut self, account_map: &AccountMap) -> Result<()>;
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote>;
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas>;
+}
+
+/// [`Amm`] implementation of a DLMM pair.
+#[derive(Debug, Clone)]
+pub struct DlmmAmm {
+    key: Pubkey,
+    lb_pair: LbPair,
+    bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<BinArrayBitmapExtension>,
+    clock: Option<Clock>,
+    mint_x_account: Option<Account>,
+    mint_y_account: Option<Account>,
+}
+
+impl DlmmAmm {
+    fn get_bin_array_pubkeys(&self, swap_for_y: bool) -> Result<Vec<Pubkey>> {
+        get_bin_array_pubkeys_for_swap(
+            self.key,
+            &self.lb_pair,
+            self.bitmap_extension.as_ref(),
+            swap_for_y,
+            BIN_ARRAYS_PER_SWAP_DIRECTION,
+        )
+    }
+
+    fn get_swap_for_y(&self, input_mint: Pubkey, output_mint: Pubkey) -> Result<bool> {
+        if input_mint == self.lb_pair.token_x_mint && output_mint == self.lb_pair.token_y_mint {
+            Ok(true)
+        } else if input_mint == self.lb_pair.token_y_mint
+            && output_mint == self.lb_pair.token_x_mint
+        {
+            Ok(false)
+        } else {
+            Err(anyhow!("Invalid swap mints"))
+        }
+    }
+}
+
+impl Amm for DlmmAmm {
+    fn from_keyed_account(keyed_account: &KeyedAccount) -> Result<Self> {
+        let lb_pair: LbPair = pod_read_unaligned_skip_disc(&keyed_account.account.data)?;
+
+        Ok(Self {
+            key: keyed_account.key,
+            lb_pair,
+            bin_arrays: HashMap::new(),
+            bitmap_extension: None,
+            clock: None,
+            mint_x_account: None,
+            mint_y_account: None,
+        })
+    }
+
+    fn key(&self) -> Pubkey {
+        self.key
+    }
+
+    fn program_id(&self) -> Pubkey {
+        dlmm::ID
+    }
+
+    fn get_reserve_mints(&self) -> Vec<Pubkey> {
+        vec![self.lb_pair.token_x_mint, self.lb_pair.token_y_mint]
+    }
+
+    fn get_accounts_to_update(&self) -> Vec<Pubkey> {
+        let mut accounts = vec![
+            self.key,
+            solana_sdk::sysvar::clock::ID,
+            self.lb_pair.token_x_mint,
+            self.lb_pair.token_y_mint,
+            derive_bin_array_bitmap_extension(self.key).0,
+        ];
+
+        for swap_for_y in [true, false] {
+            accounts.extend(self.get_bin_array_pubkeys(swap_for_y).unwrap_or_default());
+        }
+
+        accounts
+    }
+
+    /// Bin arrays are reloaded from the keys of the last [`Amm::get_accounts_to_update`] call.
+    fn update(&mut self, account_map: &AccountMap) -> Result<()> {
+        let bin_array_pubkeys = [
+            self.get_bin_array_pubkeys(true)?,
+            self.get_bin_array_pubkeys(false)?,
+        ]
+        .concat();
+
+        let lb_pair_account = account_map
+            .get(&self.key)
+            .context("Failed to fetch lb pair account")?;
+        self.lb_pair = pod_read_unaligned_skip_disc(&lb_pair_account.data)?;
+
+        let clock_account = account_map
+            .get(&solana_sdk::sysvar::clock::ID)
+            .context("Failed to fetch clock account")?;
+        self.clock = Some(bincode::deserialize(clock_account.data.as_ref())?);
+
+        self.mint_x_account = Some(
+            account_map
+                .get(&self.lb_pair.token_x_mint)
+                .cloned()
+                .context("Failed to fetch mint account")?,
+        );
+        self.mint_y_account = Some(
+            account_map
+                .get(&self.lb_pair.token_y_mint)
+                .cloned()
+                .context("Failed to fetch mint account")?,
+        );
+
+        self.bitmap_extension = account_map
+            .get(&derive_bin_array_bitmap_extension(self.key).0)
+            .map(|account| pod_read_unaligned_skip_disc(&account.data))
+            .transpose()?;
+
+        let mut bin_arrays = HashMap::new();
+        for bin_array_pubkey in bin_array_pubkeys {
+            if let Some(account) = account_map.get(&bin_array_pubkey) {
+                bin_arrays.insert(
+                    bin_array_pubkey,
+                    pod_read_unaligned_skip_disc(&account.data)?,
+                );
+            }
+        }
+        self.bin_arrays = bin_arrays;
+
+        Ok(())
+    }
+
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        let swap_for_y = self.get_swap_for_y(quote_params.input_mint, quote_params.output_mint)?;
+
+        let clock = self.clock.as_ref().context("Pool is not updated")?;
+        let mint_x_account = self
+            .mint_x_account
+            .as_ref()
+            .context("Pool is not updated")?;
+        let mint_y_account = self
+            .mint_y_account
+            .as_ref()
+            .context("Pool is not updated")?;
+
+        let (in_amount, out_amount, fee_amount) = match quote_params.swap_mode {
+            SwapMode::ExactIn => {
+                let quote = quote_exact_in(
+                    self.key,
+                    &self.lb_pair,
+                    quote_params.amount,
+                    swap_for_y,
+                    QuoteOptions::default(),
+                    self.bin_arrays.clone(),
+                    self.bitmap_extension.as_ref(),
+                    clock,
+                    mint_x_account,
+                    mint_y_account,
+                )?;
+                (quote_params.amount, quote.amount_out, quote.fee)
+            }
+            SwapMode::ExactOut => {
+                let quote = quote_exact_out(
+                    self.key,
+                    &self.lb_pair,
+                    quote_params.amount,
+                    swap_for_y,
+                    QuoteOptions::default(),
+                    self.bin_arrays.clone(),
+                    self.bitmap_extension.as_ref(),
+                    clock,
+                    mint_x_account,
+                    mint_y_account,
+                )?;
+                (quote.amount_in, quote_params.amount, quote.fee)
+            }
+        };
+
+        let fee_mint = if swap_for_y == self.lb_pair.fee_on_input(swap_for_y) {
+            self.lb_pair.token_x_mint
+        } else {
+            self.lb_pair.token_y_mint
+        };
+
+        Ok(Quote {
+            in_amount,
+            out_amount,
+            fee_amount,
+            fee_mint,
+        })
+    }
+
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas> {
+        let swap_for_y =
+            self.get_swap_for_y(swap_params.source_mint, swap_params.destination_mint)?;
+
+        let mint_x_account = self
+            .mint_x_account
+            .as_ref()
+            .context("Pool is not updated")?;
+        let mint_y_account = self
+            .mint_y_account
+            .as_ref()
+            .context("Pool is not updated")?;
+
+        ensure_no_transfer_hook(mint_x_account, mint_y_account)?;
+
+        let mut account_metas = instructions::get_swap2_account_metas(
+            self.key,
+            &self.lb_pair,
+            self.bitmap_extension.is_some(),
+            swap_params.token_transfer_authority,
+            swap_params.source_token_account,
+            swap_params.destination_token_account,
+            None,
+        )?;
+
+        account_metas.extend(
+            self.get_bin_array_pubkeys(swap_for_y)?
+                .into_iter()
+                .map(|key| AccountMeta::new(key, false)),
+        );
+
+        let remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
+
+        let data = match swap_params.swap_mode {
+            SwapMode::ExactIn => dlmm::client::args::Swap2 {
+                amount_in: swap_params.in_amount,
+                min_amount_out: swap_params.out_amount,
+                remaining_accounts_info,
+            }
+            .data(),
+            SwapMode::ExactOut => dlmm::client::args::SwapExactOut2 {
+                max_in_amount: swap_params.in_amount,
+                out_amount: swap_params.out_amount,
+                remaining_accounts_info,
+            }
+            .data(),
+        };
+
+        Ok(SwapAndAccountMetas {
+            data,
+            account_metas,
+        })
+    }
+}
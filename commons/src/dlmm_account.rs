@@ -0,0 +1,63 @@
+use crate::*;
+use anchor_lang::{AccountDeserialize, Discriminator};
+
+macro_rules! dlmm_accounts {
+    (
+        zero_copy: [$($zero_copy:ident),* $(,)?],
+        borsh: [$($borsh:ident),* $(,)?] $(,)?
+    ) => {
+        /// Accounts owned by the DLMM program, decoded from their discriminator. Only the fixed
+        /// size part of the dynamic accounts is decoded, see `DynamicPosition`,
+        /// `ParsedLimitOrder` and `DynamicOracle` for their trailing data.
+        #[derive(Debug, Clone)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[allow(clippy::large_enum_variant)]
+        pub enum DlmmAccount {
+            $($zero_copy(dlmm::accounts::$zero_copy),)*
+            $($borsh(dlmm::accounts::$borsh),)*
+        }
+
+        impl DlmmAccount {
+            /// Decode an account from its data, discriminator included.
+            pub fn decode(account_data: &[u8]) -> Result<Self> {
+                $(
+                    if account_data.starts_with(dlmm::accounts::$zero_copy::DISCRIMINATOR) {
+                        return Ok(Self::$zero_copy(pod_read_unaligned_skip_disc(account_data)?));
+                    }
+                )*
+                $(
+                    if account_data.starts_with(dlmm::accounts::$borsh::DISCRIMINATOR) {
+                        let account =
+                            dlmm::accounts::$borsh::try_deserialize(&mut &account_data[..])?;
+                        return Ok(Self::$borsh(account));
+                    }
+                )*
+
+                bail!("Unknown DLMM account discriminator")
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$zero_copy(_) => stringify!($zero_copy),)*
+                    $(Self::$borsh(_) => stringify!($borsh),)*
+                }
+            }
+        }
+    };
+}
+
+dlmm_accounts!(
+    zero_copy: [
+        LbPair,
+        BinArray,
+        BinArrayBitmapExtension,
+        PositionV2,
+        LimitOrder,
+        Oracle,
+        PresetParameter2,
+        TokenBadge,
+        Operator,
+        ClaimFeeOperator,
+    ],
+    borsh: [PresetParameter],
+);
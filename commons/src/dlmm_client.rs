@@ -0,0 +1,741 @@
+use crate::*;
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id,
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, transaction::Transaction};
+use std::collections::HashMap;
+
+/// Bin arrays with liquidity loaded in each swap direction by [`DlmmClient::create`].
+pub const DEFAULT_BIN_ARRAY_COUNT: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinInfo {
+    pub bin_id: i32,
+    /// Price of the bin per lamport, in Q64.64.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub price: u128,
+    pub amount_x: u64,
+    pub amount_y: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub liquidity_supply: u128,
+}
+
+/// Position with its amounts, claimable fees and rewards.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LbPosition {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub pubkey: Pubkey,
+    pub state: PositionV2,
+    pub position_data: DynamicPosition,
+}
+
+/// Client of a pair, holding the pair state loaded from `account_provider`.
+///
+/// Transactions are returned unsigned, with the user as fee payer. The recent blockhash is set
+/// when signing them.
+pub struct DlmmClient<P: AccountProvider> {
+    pub account_provider: P,
+    pub pool: PoolSnapshot,
+    /// Bin arrays with liquidity loaded in each swap direction.
+    pub bin_array_count: u8,
+}
+
+impl<P: AccountProvider> DlmmClient<P> {
+    pub async fn create(account_provider: P, lb_pair: Pubkey) -> Result<Self> {
+        let pool = PoolSnapshot::load(&account_provider, lb_pair, DEFAULT_BIN_ARRAY_COUNT).await?;
+
+        Ok(Self {
+            account_provider,
+            pool,
+            bin_array_count: DEFAULT_BIN_ARRAY_COUNT,
+        })
+    }
+
+    /// Refetch the pair state and all its bin arrays, see [`PoolSnapshot::refresh`].
+    pub async fn refetch_states(&mut self) -> Result<()> {
+        // Nothing updates the bin arrays of the client between refetches, so all of them are
+        // fetched again
+        let bin_arrays = std::mem::take(&mut self.pool.bin_arrays);
+        let result = self
+            .pool
+            .refresh(&self.account_provider, self.bin_array_count)
+            .await;
+        if result.is_err() {
+            self.pool.bin_arrays = bin_arrays;
+        }
+
+        result
+    }
+
+    pub fn lb_pair(&self) -> Pubkey {
+        self.pool.lb_pair_pubkey
+    }
+
+    pub fn lb_pair_state(&self) -> &LbPair {
+        &self.pool.lb_pair
+    }
+
+    /// Bins from `min_bin_id` to `max_bin_id`, fetched from the account provider. Bins of bin
+    /// arrays not initialized are empty.
+    pub async fn get_bins_between(&self, min_bin_id: i32, max_bin_id: i32) -> Result<Vec<BinInfo>> {
+        ensure!(min_bin_id <= max_bin_id, "Invalid bin range");
+
+        let bin_arrays = self.fetch_bin_arrays(min_bin_id, max_bin_id).await?;
+
+        let mut bins = Vec::with_capacity((max_bin_id - min_bin_id + 1) as usize);
+        for bin_id in min_bin_id..=max_bin_id {
+            let bin_array_index = BinArray::bin_id_to_bin_array_index(bin_id)?;
+            let bin = bin_arrays
+                .get(&bin_array_index)
+                .map(|bin_array| bin_array.get_bin(bin_id))
+                .transpose()?;
+
+            bins.push(BinInfo {
+                bin_id,
+                price: get_price_from_id(bin_id, self.pool.lb_pair.bin_step)?,
+                amount_x: bin.map(|bin| bin.amount_x).unwrap_or_default(),
+                amount_y: bin.map(|bin| bin.amount_y).unwrap_or_default(),
+                liquidity_supply: bin.map(|bin| bin.liquidity_supply).unwrap_or_default(),
+            });
+        }
+
+        Ok(bins)
+    }
+
+    /// Initialized bin arrays covering the bins from `min_bin_id` to `max_bin_id`, by index.
+    async fn fetch_bin_arrays(
+        &self,
+        min_bin_id: i32,
+        max_bin_id: i32,
+    ) -> Result<HashMap<i32, BinArray>> {
+        let bin_array_indexes = BinArray::get_bin_array_indexes_coverage(min_bin_id, max_bin_id)?;
+        let bin_array_pubkeys = bin_array_indexes
+            .iter()
+            .map(|&index| derive_bin_array_pda(self.lb_pair(), index.into()).0)
+            .collect::<Vec<_>>();
+        let bin_array_accounts = self
+            .account_provider
+            .get_multiple_accounts(&bin_array_pubkeys)
+            .await?;
+
+        let mut bin_arrays = HashMap::new();
+        for (index, account) in bin_array_indexes.into_iter().zip(bin_array_accounts) {
+            if let Some(account) = account {
+                bin_arrays.insert(index, pod_read_unaligned_skip_disc(&account.data)?);
+            }
+        }
+
+        Ok(bin_arrays)
+    }
+
+    pub async fn get_active_bin(&self) -> Result<BinInfo> {
+        let active_id = self.pool.lb_pair.active_id;
+        let mut bins = self.get_bins_between(active_id, active_id).await?;
+        bins.pop().context("Active bin not found")
+    }
+
+    /// `left_count` bins on the left and `right_count` bins on the right of the active bin, and
+    /// the active bin.
+    pub async fn get_bins_around_active_bin(
+        &self,
+        left_count: i32,
+        right_count: i32,
+    ) -> Result<Vec<BinInfo>> {
+        let active_id = self.pool.lb_pair.active_id;
+        self.get_bins_between(active_id - left_count, active_id + right_count)
+            .await
+    }
+
+    /// Positions of `user` on the pair. Fees and rewards are computed at the clock of the last
+    /// refetch.
+    pub async fn get_positions_by_user(&self, user: Pubkey) -> Result<Vec<LbPosition>> {
+        let position_accounts = self
+            .account_provider
+            .get_program_accounts(
+                &dlmm::ID,
+                position_filter_by_wallet_and_pair(user, self.lb_pair()),
+            )
+            .await?
+            .into_iter()
+            .filter(|(_, account)| account.data.starts_with(PositionV2::DISCRIMINATOR))
+            .collect::<Vec<_>>();
+
+        let mut positions = Vec::with_capacity(position_accounts.len());
+        for (pubkey, account) in position_accounts {
+            positions.push(self.parse_position(pubkey, &account.data).await?);
+        }
+
+        Ok(positions)
+    }
+
+    pub async fn get_position(&self, position: Pubkey) -> Result<LbPosition> {
+        let account = self
+            .account_provider
+            .get_account(&position)
+            .await?
+            .with_context(|| format!("AccountNotFound: pubkey={}", position))?;
+        ensure!(
+            account.data.starts_with(PositionV2::DISCRIMINATOR),
+            "Not a valid position account"
+        );
+
+        self.parse_position(position, &account.data).await
+    }
+
+    async fn parse_position(&self, pubkey: Pubkey, account_data: &[u8]) -> Result<LbPosition> {
+        let state: PositionV2 = pod_read_unaligned_skip_disc(account_data)?;
+        ensure!(
+            state.lb_pair == self.lb_pair(),
+            "Position {} is not a position of the pair",
+            pubkey
+        );
+
+        let bin_arrays = self
+            .fetch_bin_arrays(state.lower_bin_id, state.upper_bin_id)
+            .await?;
+
+        let position_data = DynamicPosition::parse(
+            &state,
+            account_data,
+            &self.pool.lb_pair,
+            &bin_arrays,
+            self.pool.clock.unix_timestamp,
+        )?;
+
+        Ok(LbPosition {
+            pubkey,
+            state,
+            position_data,
+        })
+    }
+
+    /// See [`PoolSnapshot::quote_exact_in`].
+    pub fn swap_quote(
+        &self,
+        amount_in: u64,
+        swap_for_y: bool,
+        options: QuoteOptions,
+    ) -> Result<SwapExactInQuote> {
+        self.pool.quote_exact_in(amount_in, swap_for_y, options)
+    }
+
+    /// See [`PoolSnapshot::quote_exact_out`].
+    pub fn swap_quote_exact_out(
+        &self,
+        amount_out: u64,
+        swap_for_y: bool,
+        options: QuoteOptions,
+    ) -> Result<SwapExactOutQuote> {
+        self.pool.quote_exact_out(amount_out, swap_for_y, options)
+    }
+
+    /// `swap2` transaction from the associated token accounts of `user`. The output token
+    /// account is created if missing.
+    pub async fn swap(
+        &self,
+        user: Pubkey,
+        amount_in: u64,
+        min_amount_out: u64,
+        swap_for_y: bool,
+    ) -> Result<Transaction> {
+        let (user_token_in, user_token_out, create_user_token_out_ix) =
+            self.get_swap_user_token_accounts(user, swap_for_y)?;
+
+        let swap_ix = instructions::swap2(
+            self.lb_pair(),
+            &self.pool.lb_pair,
+            self.pool.bitmap_extension.is_some(),
+            user,
+            user_token_in,
+            user_token_out,
+            None,
+            &self.get_swap_bin_arrays(swap_for_y)?,
+            self.get_liquidity_transfer_hook_accounts().await?,
+            amount_in,
+            min_amount_out,
+        )?;
+
+        Ok(Transaction::new_with_payer(
+            &[create_user_token_out_ix, swap_ix],
+            Some(&user),
+        ))
+    }
+
+    /// `swap_exact_out2` transaction from the associated token accounts of `user`. The output
+    /// token account is created if missing.
+    pub async fn swap_exact_out(
+        &self,
+        user: Pubkey,
+        max_in_amount: u64,
+        out_amount: u64,
+        swap_for_y: bool,
+    ) -> Result<Transaction> {
+        let (user_token_in, user_token_out, create_user_token_out_ix) =
+            self.get_swap_user_token_accounts(user, swap_for_y)?;
+
+        let swap_ix = instructions::swap_exact_out2(
+            self.lb_pair(),
+            &self.pool.lb_pair,
+            self.pool.bitmap_extension.is_some(),
+            user,
+            user_token_in,
+            user_token_out,
+            None,
+            &self.get_swap_bin_arrays(swap_for_y)?,
+            self.get_liquidity_transfer_hook_accounts().await?,
+            max_in_amount,
+            out_amount,
+        )?;
+
+        Ok(Transaction::new_with_payer(
+            &[create_user_token_out_ix, swap_ix],
+            Some(&user),
+        ))
+    }
+
+    /// Initialize `position`, a new keypair account covering the bins of `deposit`, and deposit
+    /// into it. `position` signs the transaction along with `user`.
+    pub async fn initialize_position_and_add_liquidity_by_strategy(
+        &self,
+        position: Pubkey,
+        user: Pubkey,
+        deposit: &StrategyDeposit,
+        max_active_bin_slippage: i32,
+    ) -> Result<Transaction> {
+        let initialize_position_ix = instructions::initialize_position(
+            self.lb_pair(),
+            user,
+            position,
+            user,
+            deposit.min_bin_id,
+            deposit.max_bin_id - deposit.min_bin_id + 1,
+        )?;
+
+        let add_liquidity_ix = self
+            .get_add_liquidity_by_strategy_ix(position, user, deposit, max_active_bin_slippage)
+            .await?;
+
+        Ok(Transaction::new_with_payer(
+            &[initialize_position_ix, add_liquidity_ix],
+            Some(&user),
+        ))
+    }
+
+    pub async fn add_liquidity_by_strategy(
+        &self,
+        position: Pubkey,
+        user: Pubkey,
+        deposit: &StrategyDeposit,
+        max_active_bin_slippage: i32,
+    ) -> Result<Transaction> {
+        let add_liquidity_ix = self
+            .get_add_liquidity_by_strategy_ix(position, user, deposit, max_active_bin_slippage)
+            .await?;
+
+        Ok(Transaction::new_with_payer(
+            &[add_liquidity_ix],
+            Some(&user),
+        ))
+    }
+
+    async fn get_add_liquidity_by_strategy_ix(
+        &self,
+        position: Pubkey,
+        user: Pubkey,
+        deposit: &StrategyDeposit,
+        max_active_bin_slippage: i32,
+    ) -> Result<Instruction> {
+        let [user_token_x, user_token_y] = self.get_user_token_accounts(user)?;
+
+        instructions::add_liquidity_by_strategy2(
+            position,
+            self.lb_pair(),
+            &self.pool.lb_pair,
+            user,
+            user_token_x,
+            user_token_y,
+            self.get_liquidity_transfer_hook_accounts().await?,
+            deposit.get_liquidity_parameter_by_strategy(max_active_bin_slippage),
+        )
+    }
+
+    /// Withdraw `bps` of the liquidity of every bin of the position, one transaction per chunk of
+    /// bins with liquidity. With `should_claim_and_close`, the fees and rewards are claimed and the
+    /// position is closed in the last transaction.
+    pub async fn remove_liquidity(
+        &self,
+        user: Pubkey,
+        position: &LbPosition,
+        bps: u16,
+        should_claim_and_close: bool,
+    ) -> Result<Vec<Transaction>> {
+        let [user_token_x, user_token_y] = self.get_user_token_accounts(user)?;
+        let claim_accounts = self.get_claim_accounts(should_claim_and_close).await?;
+
+        let mut transactions = vec![];
+        for (min_bin_id, max_bin_id) in
+            position_bin_range_chunks(position.state.lower_bin_id, position.state.upper_bin_id)
+        {
+            let has_liquidity = get_chunk_bins(position, min_bin_id, max_bin_id)
+                .any(|bin| bin.position_liquidity > 0);
+            let claim_ixs = if should_claim_and_close {
+                self.get_claim_ixs(
+                    user,
+                    position,
+                    true,
+                    &claim_accounts,
+                    min_bin_id,
+                    max_bin_id,
+                )?
+            } else {
+                vec![]
+            };
+            if !has_liquidity && claim_ixs.is_empty() {
+                continue;
+            }
+
+            let mut ixs = vec![];
+            if has_liquidity || has_chunk_fee(position, min_bin_id, max_bin_id) {
+                ixs.extend(self.get_create_user_token_accounts_ixs(user)?);
+            }
+            if has_liquidity {
+                ixs.push(instructions::remove_liquidity_by_range2(
+                    position.pubkey,
+                    self.lb_pair(),
+                    &self.pool.lb_pair,
+                    user,
+                    user_token_x,
+                    user_token_y,
+                    claim_accounts.transfer_hook_accounts.clone(),
+                    min_bin_id,
+                    max_bin_id,
+                    bps,
+                )?);
+            }
+            ixs.extend(claim_ixs);
+
+            transactions.push(ixs);
+        }
+
+        if should_claim_and_close {
+            let close_position_ix =
+                instructions::close_position2(position.pubkey, &position.state, user, user)?;
+            match transactions.last_mut() {
+                Some(ixs) => ixs.push(close_position_ix),
+                None => transactions.push(vec![close_position_ix]),
+            }
+        }
+
+        Ok(transactions
+            .into_iter()
+            .map(|ixs| Transaction::new_with_payer(&ixs, Some(&user)))
+            .collect())
+    }
+
+    /// Claim the swap fees of the position, one transaction per chunk of bins with fees.
+    pub async fn claim_swap_fee(
+        &self,
+        owner: Pubkey,
+        position: &LbPosition,
+    ) -> Result<Vec<Transaction>> {
+        let claim_accounts = self.get_claim_accounts(false).await?;
+
+        self.get_claim_transactions(owner, position, true, &claim_accounts)
+    }
+
+    /// Claim the swap fees and the rewards of the positions, one transaction per position and
+    /// chunk of bins with fees or rewards.
+    pub async fn claim_all_rewards(
+        &self,
+        owner: Pubkey,
+        positions: &[LbPosition],
+    ) -> Result<Vec<Transaction>> {
+        let claim_accounts = self.get_claim_accounts(true).await?;
+
+        let mut transactions = vec![];
+        for position in positions {
+            transactions.extend(self.get_claim_transactions(
+                owner,
+                position,
+                true,
+                &claim_accounts,
+            )?);
+        }
+
+        Ok(transactions)
+    }
+
+    /// Close an empty position, the rent is returned to `owner`.
+    pub fn close_position(&self, owner: Pubkey, position: &LbPosition) -> Result<Transaction> {
+        let close_position_ix =
+            instructions::close_position2(position.pubkey, &position.state, owner, owner)?;
+
+        Ok(Transaction::new_with_payer(
+            &[close_position_ix],
+            Some(&owner),
+        ))
+    }
+
+    fn get_claim_transactions(
+        &self,
+        owner: Pubkey,
+        position: &LbPosition,
+        claim_fee: bool,
+        claim_accounts: &ClaimAccounts,
+    ) -> Result<Vec<Transaction>> {
+        let mut transactions = vec![];
+        for (min_bin_id, max_bin_id) in
+            position_bin_range_chunks(position.state.lower_bin_id, position.state.upper_bin_id)
+        {
+            let claim_ixs = self.get_claim_ixs(
+                owner,
+                position,
+                claim_fee,
+                claim_accounts,
+                min_bin_id,
+                max_bin_id,
+            )?;
+            if claim_ixs.is_empty() {
+                continue;
+            }
+
+            let mut ixs = vec![];
+            if claim_fee && has_chunk_fee(position, min_bin_id, max_bin_id) {
+                ixs.extend(self.get_create_user_token_accounts_ixs(owner)?);
+            }
+            ixs.extend(claim_ixs);
+
+            transactions.push(Transaction::new_with_payer(&ixs, Some(&owner)));
+        }
+
+        Ok(transactions)
+    }
+
+    /// Accounts of the claim instructions, resolved once for all the chunks and positions claimed.
+    /// The rewards are only resolved with `claim_reward`.
+    async fn get_claim_accounts(&self, claim_reward: bool) -> Result<ClaimAccounts> {
+        let mut rewards = vec![];
+        if claim_reward {
+            for (reward_index, reward_info) in self.pool.lb_pair.reward_infos.iter().enumerate() {
+                if reward_info.mint == Pubkey::default() {
+                    rewards.push(None);
+                    continue;
+                }
+
+                let token_program = self
+                    .account_provider
+                    .get_account(&reward_info.mint)
+                    .await?
+                    .context("Failed to fetch reward mint account")?
+                    .owner;
+                let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+                    &self.pool.lb_pair,
+                    &self.account_provider,
+                    ActionType::Reward(reward_index),
+                )
+                .await?;
+
+                rewards.push(Some(RewardClaimAccounts {
+                    mint: reward_info.mint,
+                    token_program,
+                    transfer_hook_accounts,
+                }));
+            }
+        }
+
+        Ok(ClaimAccounts {
+            transfer_hook_accounts: self.get_liquidity_transfer_hook_accounts().await?,
+            rewards,
+        })
+    }
+
+    /// `claim_fee2` and `claim_reward2` instructions of the position bins from `min_bin_id` to
+    /// `max_bin_id`, when there is anything to claim. The rewards claimed are the ones resolved in
+    /// `claim_accounts`. Missing reward token accounts are created, the user token accounts of the
+    /// pair are not, see [`Self::get_create_user_token_accounts_ixs`].
+    fn get_claim_ixs(
+        &self,
+        owner: Pubkey,
+        position: &LbPosition,
+        claim_fee: bool,
+        claim_accounts: &ClaimAccounts,
+        min_bin_id: i32,
+        max_bin_id: i32,
+    ) -> Result<Vec<Instruction>> {
+        let mut ixs = vec![];
+
+        if claim_fee && has_chunk_fee(position, min_bin_id, max_bin_id) {
+            let [user_token_x, user_token_y] = self.get_user_token_accounts(owner)?;
+
+            ixs.push(instructions::claim_fee2(
+                position.pubkey,
+                self.lb_pair(),
+                &self.pool.lb_pair,
+                owner,
+                user_token_x,
+                user_token_y,
+                claim_accounts.transfer_hook_accounts.clone(),
+                min_bin_id,
+                max_bin_id,
+            )?);
+        }
+
+        for (reward_index, reward) in claim_accounts.rewards.iter().enumerate() {
+            let Some(reward) = reward else {
+                continue;
+            };
+            let has_reward = get_chunk_bins(position, min_bin_id, max_bin_id)
+                .any(|bin| bin.position_reward_amounts[reward_index] > 0);
+            if !has_reward {
+                continue;
+            }
+
+            let user_token_account = get_associated_token_address_with_program_id(
+                &owner,
+                &reward.mint,
+                &reward.token_program,
+            );
+
+            ixs.push(create_associated_token_account_idempotent(
+                &owner,
+                &owner,
+                &reward.mint,
+                &reward.token_program,
+            ));
+            ixs.push(instructions::claim_reward2(
+                position.pubkey,
+                self.lb_pair(),
+                &self.pool.lb_pair,
+                reward.token_program,
+                owner,
+                user_token_account,
+                reward.transfer_hook_accounts.clone(),
+                reward_index as u64,
+                min_bin_id,
+                max_bin_id,
+            )?);
+        }
+
+        Ok(ixs)
+    }
+
+    async fn get_liquidity_transfer_hook_accounts(
+        &self,
+    ) -> Result<Option<instructions::TransferHookAccounts>> {
+        get_potential_token_2022_related_ix_data_and_accounts(
+            &self.pool.lb_pair,
+            &self.account_provider,
+            ActionType::Liquidity,
+        )
+        .await
+    }
+
+    fn get_swap_bin_arrays(&self, swap_for_y: bool) -> Result<Vec<Pubkey>> {
+        get_bin_array_pubkeys_for_swap(
+            self.lb_pair(),
+            &self.pool.lb_pair,
+            self.pool.bitmap_extension.as_ref(),
+            swap_for_y,
+            self.bin_array_count,
+        )
+    }
+
+    /// Associated token accounts of `user` for token X and Y.
+    pub fn get_user_token_accounts(&self, user: Pubkey) -> Result<[Pubkey; 2]> {
+        let [token_x_program, token_y_program] = self.pool.lb_pair.get_token_programs()?;
+
+        Ok([
+            get_associated_token_address_with_program_id(
+                &user,
+                &self.pool.lb_pair.token_x_mint,
+                &token_x_program,
+            ),
+            get_associated_token_address_with_program_id(
+                &user,
+                &self.pool.lb_pair.token_y_mint,
+                &token_y_program,
+            ),
+        ])
+    }
+
+    fn get_create_user_token_accounts_ixs(&self, user: Pubkey) -> Result<[Instruction; 2]> {
+        let [token_x_program, token_y_program] = self.pool.lb_pair.get_token_programs()?;
+
+        Ok([
+            create_associated_token_account_idempotent(
+                &user,
+                &user,
+                &self.pool.lb_pair.token_x_mint,
+                &token_x_program,
+            ),
+            create_associated_token_account_idempotent(
+                &user,
+                &user,
+                &self.pool.lb_pair.token_y_mint,
+                &token_y_program,
+            ),
+        ])
+    }
+
+    /// User token accounts in and out of a swap, and the instruction creating the token account
+    /// out.
+    fn get_swap_user_token_accounts(
+        &self,
+        user: Pubkey,
+        swap_for_y: bool,
+    ) -> Result<(Pubkey, Pubkey, Instruction)> {
+        let [user_token_x, user_token_y] = self.get_user_token_accounts(user)?;
+        let [create_user_token_x_ix, create_user_token_y_ix] =
+            self.get_create_user_token_accounts_ixs(user)?;
+
+        if swap_for_y {
+            Ok((user_token_x, user_token_y, create_user_token_y_ix))
+        } else {
+            Ok((user_token_y, user_token_x, create_user_token_x_ix))
+        }
+    }
+}
+
+fn get_chunk_bins(
+    position: &LbPosition,
+    min_bin_id: i32,
+    max_bin_id: i32,
+) -> impl Iterator<Item = &DynamicPositionBinData> {
+    position
+        .position_data
+        .bins
+        .iter()
+        .filter(move |bin| bin.bin_id >= min_bin_id && bin.bin_id <= max_bin_id)
+}
+
+fn has_chunk_fee(position: &LbPosition, min_bin_id: i32, max_bin_id: i32) -> bool {
+    get_chunk_bins(position, min_bin_id, max_bin_id)
+        .any(|bin| bin.position_fee_x_amount > 0 || bin.position_fee_y_amount > 0)
+}
+
+/// Accounts of the claim instructions shared by every chunk of bins claimed.
+struct ClaimAccounts {
+    /// Transfer hook accounts of the pair tokens, for `claim_fee2` and `remove_liquidity_by_range2`.
+    transfer_hook_accounts: Option<instructions::TransferHookAccounts>,
+    /// Initialized rewards by reward index, empty when the rewards are not claimed.
+    rewards: Vec<Option<RewardClaimAccounts>>,
+}
+
+struct RewardClaimAccounts {
+    mint: Pubkey,
+    token_program: Pubkey,
+    transfer_hook_accounts: Option<instructions::TransferHookAccounts>,
+}
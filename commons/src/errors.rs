@@ -0,0 +1,116 @@
+use crate::*;
+#[cfg(feature = "rpc")]
+use anchor_client::{
+    solana_client::{
+        client_error::{ClientError as SolanaClientError, ClientErrorKind},
+        rpc_request::{RpcError, RpcResponseErrorData},
+    },
+    ClientError,
+};
+#[cfg(feature = "rpc")]
+use solana_sdk::program_error::ProgramError;
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+include!(concat!(env!("OUT_DIR"), "/dlmm_errors.rs"));
+
+impl std::fmt::Display for DlmmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name(), self.message())
+    }
+}
+
+impl std::error::Error for DlmmError {}
+
+impl DlmmError {
+    pub fn code(&self) -> u32 {
+        *self as u32
+    }
+}
+
+/// Error of the DLMM program in the logs of a transaction, from its
+/// `Program <dlmm> failed: custom program error: 0x..` line. Failures of other programs are
+/// skipped.
+pub fn get_dlmm_error_from_logs<S: AsRef<str>>(logs: &[S]) -> Option<DlmmError> {
+    let failed_log_prefix = format!("Program {} failed: custom program error: 0x", dlmm::ID);
+
+    logs.iter().find_map(|log| {
+        let code = log.as_ref().strip_prefix(&failed_log_prefix)?;
+        DlmmError::from_code(u32::from_str_radix(code.trim(), 16).ok()?)
+    })
+}
+
+/// Error of a failed instruction. The failed instruction is assumed to be a DLMM instruction, as
+/// the transaction error doesn't tell which program failed.
+pub fn get_dlmm_error_from_transaction_error(error: &TransactionError) -> Option<DlmmError> {
+    match error {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            DlmmError::from_code(*code)
+        }
+        _ => None,
+    }
+}
+
+/// Error of a RPC client error. The preflight simulation logs are preferred over the transaction
+/// error when available.
+#[cfg(feature = "rpc")]
+pub fn get_dlmm_error_from_client_error(error: &SolanaClientError) -> Option<DlmmError> {
+    if let ClientErrorKind::RpcError(RpcError::RpcResponseError {
+        data: RpcResponseErrorData::SendTransactionPreflightFailure(simulation),
+        ..
+    }) = error.kind()
+    {
+        if let Some(logs) = &simulation.logs {
+            return get_dlmm_error_from_logs(logs);
+        }
+    }
+
+    error
+        .get_transaction_error()
+        .and_then(|error| get_dlmm_error_from_transaction_error(&error))
+}
+
+#[cfg(feature = "rpc")]
+fn get_dlmm_error_from_anchor_client_error(error: &ClientError) -> Option<DlmmError> {
+    match error {
+        ClientError::SolanaClientError(error) => get_dlmm_error_from_client_error(error),
+        ClientError::ProgramError(ProgramError::Custom(code)) => DlmmError::from_code(*code),
+        ClientError::AnchorError(anchor_lang::error::Error::AnchorError(error)) => {
+            DlmmError::from_code(error.error_code_number)
+        }
+        _ => None,
+    }
+}
+
+/// Find the DLMM program error in the chain of `error`.
+pub fn get_dlmm_error(error: &anyhow::Error) -> Option<DlmmError> {
+    error.chain().find_map(|error| {
+        if let Some(error) = error.downcast_ref::<DlmmError>() {
+            return Some(*error);
+        }
+        if let Some(error) = error.downcast_ref::<TransactionError>() {
+            return get_dlmm_error_from_transaction_error(error);
+        }
+
+        #[cfg(feature = "rpc")]
+        if let Some(error) = error.downcast_ref::<ClientError>() {
+            return get_dlmm_error_from_anchor_client_error(error);
+        }
+        #[cfg(feature = "rpc")]
+        if let Some(error) = error.downcast_ref::<SolanaClientError>() {
+            return get_dlmm_error_from_client_error(error);
+        }
+
+        None
+    })
+}
+
+/// Add the DLMM program error found in `error`, if any, as context so that it is displayed by
+/// name.
+pub fn add_dlmm_error_context(error: anyhow::Error) -> anyhow::Error {
+    match get_dlmm_error(&error) {
+        Some(dlmm_error) if error.downcast_ref::<DlmmError>().is_none() => {
+            error.context(dlmm_error)
+        }
+        _ => error,
+    }
+}
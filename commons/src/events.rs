@@ -0,0 +1,166 @@
+use crate::*;
+use anchor_lang::{event::EVENT_IX_TAG_LE, AnchorDeserialize, Discriminator};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
+#[cfg(feature = "rpc")]
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta, UiInstruction,
+};
+#[cfg(feature = "rpc")]
+use std::str::FromStr;
+
+const PROGRAM_DATA_LOG_PREFIX: &str = "Program data: ";
+
+macro_rules! dlmm_events {
+    ($($event:ident),* $(,)?) => {
+        /// Events emitted by the DLMM program.
+        #[derive(Debug, Clone)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum DlmmEvent {
+            $($event(dlmm::events::$event),)*
+        }
+
+        impl DlmmEvent {
+            /// Decode an event from its discriminator and borsh serialized data. `None` for
+            /// unknown discriminators.
+            pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+                $(
+                    if let Some(mut event_data) =
+                        data.strip_prefix(dlmm::events::$event::DISCRIMINATOR)
+                    {
+                        let event = dlmm::events::$event::deserialize(&mut event_data)
+                            .context(concat!("Failed to decode ", stringify!($event)))?;
+                        return Ok(Some(Self::$event(event)));
+                    }
+                )*
+
+                Ok(None)
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$event(_) => stringify!($event),)*
+                }
+            }
+        }
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/dlmm_events.rs"));
+
+/// Decode the data of a self CPI event instruction. `None` if the data is not an event.
+pub fn decode_event_cpi_instruction_data(ix_data: &[u8]) -> Result<Option<DlmmEvent>> {
+    match ix_data.strip_prefix(EVENT_IX_TAG_LE) {
+        Some(event_data) => DlmmEvent::decode(event_data),
+        None => Ok(None),
+    }
+}
+
+/// Decode the `Program data:` logs emitted by the DLMM program, in order. Logs of other programs
+/// are skipped.
+pub fn parse_events_from_logs<S: AsRef<str>>(logs: &[S]) -> Result<Vec<DlmmEvent>> {
+    let dlmm_program_id = dlmm::ID.to_string();
+    let mut invoked_programs: Vec<&str> = vec![];
+    let mut events = vec![];
+
+    for log in logs.iter().map(AsRef::as_ref) {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA_LOG_PREFIX) {
+            if invoked_programs.last() == Some(&dlmm_program_id.as_str()) {
+                let data = BASE64_STANDARD.decode(data)?;
+                events.extend(DlmmEvent::decode(&data)?);
+            }
+        } else if let Some(program_log) = log.strip_prefix("Program ") {
+            let mut words = program_log.split_whitespace();
+            if let (Some(program_id), Some(status)) = (words.next(), words.next()) {
+                match status {
+                    "invoke" => invoked_programs.push(program_id),
+                    "success" | "failed:" => {
+                        invoked_programs.pop();
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+/// Decode the self CPI events in `instructions`, usually the inner instructions of a
+/// transaction. `account_keys` are the account keys of the transaction, including the loaded
+/// addresses.
+pub fn parse_events_from_instructions<'a>(
+    account_keys: &[Pubkey],
+    instructions: impl IntoIterator<Item = &'a CompiledInstruction>,
+) -> Result<Vec<DlmmEvent>> {
+    let mut events = vec![];
+
+    for ix in instructions {
+        let program_id = account_keys
+            .get(usize::from(ix.program_id_index))
+            .context("Invalid program id index")?;
+        if *program_id == dlmm::ID {
+            events.extend(decode_event_cpi_instruction_data(&ix.data)?);
+        }
+    }
+
+    Ok(events)
+}
+
+/// Events of a transaction fetched with a binary encoding: the `Program data:` log events followed
+/// by the self CPI events.
+#[cfg(feature = "rpc")]
+pub fn parse_events_from_transaction(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<Vec<DlmmEvent>> {
+    let meta = tx
+        .transaction
+        .meta
+        .as_ref()
+        .context("Missing transaction meta")?;
+
+    let mut events = match &meta.log_messages {
+        OptionSerializer::Some(logs) => parse_events_from_logs(logs)?,
+        _ => vec![],
+    };
+
+    let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions else {
+        return Ok(events);
+    };
+
+    let versioned_tx = tx
+        .transaction
+        .transaction
+        .decode()
+        .context("Transaction must be fetched with a binary encoding")?;
+    let mut account_keys = versioned_tx.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded_addresses) = &meta.loaded_addresses {
+        for address in loaded_addresses
+            .writable
+            .iter()
+            .chain(loaded_addresses.readonly.iter())
+        {
+            account_keys.push(Pubkey::from_str(address)?);
+        }
+    }
+
+    let mut instructions = vec![];
+    for ix in inner_instructions
+        .iter()
+        .flat_map(|inner_ixs| inner_ixs.instructions.iter())
+    {
+        if let UiInstruction::Compiled(compiled_ix) = ix {
+            instructions.push(CompiledInstruction {
+                program_id_index: compiled_ix.program_id_index,
+                accounts: compiled_ix.accounts.clone(),
+                data: bs58::decode(&compiled_ix.data).into_vec()?,
+            });
+        }
+    }
+    events.extend(parse_events_from_instructions(
+        &account_keys,
+        &instructions,
+    )?);
+
+    Ok(events)
+}
@@ -13,12 +13,25 @@ pub const POSITION_BIN_DATA_SIZE: usize = std::mem::size_of::<PositionBinData>()
 
 /// Per-bin computed data for a position.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DynamicPositionBinData {
     pub bin_id: i32,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
     pub price: u128,
     pub bin_x_amount: u64,
     pub bin_y_amount: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
     pub bin_liquidity: u128,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
     pub position_liquidity: u128,
     pub position_x_amount: u64,
     pub position_y_amount: u64,
@@ -29,9 +42,22 @@ pub struct DynamicPositionBinData {
 
 /// Aggregated position data computed from on-chain state.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DynamicPosition {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
     pub lb_pair: Pubkey,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
     pub owner: Pubkey,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
     pub fee_owner: Pubkey,
     pub lower_bin_id: i32,
     pub upper_bin_id: i32,
@@ -1,8 +1,11 @@
 use crate::*;
+#[cfg(feature = "spl")]
 use anchor_spl::token::spl_token;
+#[cfg(feature = "spl")]
 use anchor_spl::token_2022::spl_token_2022;
 use ruint::aliases::U1024;
 use solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "spl")]
 use std::ops::Deref;
 use std::ops::Shl;
 use std::ops::Shr;
@@ -18,6 +21,7 @@ pub trait LbPairExtension {
     fn get_total_fee(&self) -> Result<u128>;
     fn get_base_fee(&self) -> Result<u128>;
     fn get_variable_fee(&self) -> Result<u128>;
+    #[cfg(feature = "spl")]
     fn get_token_programs(&self) -> Result<[Pubkey; 2]>;
     fn compute_variable_fee(&self, volatility_accumulator: u32) -> Result<u128>;
     fn compute_protocol_fee(&self, fee_amount: u64) -> Result<u64>;
@@ -38,6 +42,9 @@ pub trait LbPairExtension {
 
     /// Whether the fee is charged on the input token for the given swap direction.
     fn fee_on_input(&self, swap_for_y: bool) -> bool;
+
+    /// Point from which the pre-activation swap address is allowed to swap.
+    fn get_pre_activation_start_point(&self) -> u64;
 }
 
 impl LbPairExtension for LbPair {
@@ -45,6 +52,7 @@ impl LbPairExtension for LbPair {
         Ok(self.status.try_into()?)
     }
 
+    #[cfg(feature = "spl")]
     fn get_token_programs(&self) -> Result<[Pubkey; 2]> {
         let mut token_programs_id = [Pubkey::default(); 2];
 
@@ -262,8 +270,7 @@ impl LbPairExtension for LbPair {
     }
 
     fn is_support_limit_order(&self) -> bool {
-        let Some(function_type) = FunctionType::try_from(self.parameters.function_type).ok()
-        else {
+        let Some(function_type) = FunctionType::try_from(self.parameters.function_type).ok() else {
             return false;
         };
         match function_type {
@@ -286,6 +293,11 @@ impl LbPairExtension for LbPair {
         }
     }
 
+    fn get_pre_activation_start_point(&self) -> u64 {
+        self.activation_point
+            .saturating_sub(self.pre_activation_duration)
+    }
+
     fn next_bin_array_index_with_liquidity_internal(
         &self,
         swap_for_y: bool,
@@ -8,6 +8,7 @@ use solana_sdk::pubkey::Pubkey;
 use super::bin_array::BinArrayExtension;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LimitOrderStatus {
     NotFilled,
     PartialFilled,
@@ -26,6 +27,7 @@ impl std::fmt::Display for LimitOrderStatus {
 
 /// Per-bin result from processing a limit order bin against on-chain state.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LimitOrderBinResult {
     pub bin_id: i32,
     pub is_ask: bool,
@@ -40,6 +42,7 @@ pub struct LimitOrderBinResult {
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LimitOrderSummary {
     pub total_deposit_x: u64,
     pub total_deposit_y: u64,
@@ -68,12 +71,14 @@ impl LimitOrderSummary {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LimitOrderResult {
     pub bins: Vec<LimitOrderBinResult>,
     pub summary: LimitOrderSummary,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParsedLimitOrder {
     pub limit_order: LimitOrder,
     pub result: LimitOrderResult,
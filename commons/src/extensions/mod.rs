@@ -18,3 +18,6 @@ pub use limit_order::*;
 
 pub mod dynamic_position;
 pub use dynamic_position::*;
+
+pub mod oracle;
+pub use oracle::*;
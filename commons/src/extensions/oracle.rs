@@ -0,0 +1,234 @@
+use crate::*;
+use dlmm::accounts::Oracle;
+
+pub const ORACLE_METADATA_SIZE: usize = 8 + std::mem::size_of::<Oracle>();
+pub const OBSERVATION_SIZE: usize = 32;
+
+/// Sample of the oracle ring buffer. The program accumulates `active_id * elapsed seconds` into
+/// `cumulative_active_bin_id`, and starts a new sample once the latest one is older than
+/// `SAMPLE_LIFETIME`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Observation {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub cumulative_active_bin_id: i128,
+    pub created_at: i64,
+    pub last_updated_at: i64,
+}
+
+impl Observation {
+    pub fn is_initialized(&self) -> bool {
+        self.created_at != 0 && self.last_updated_at != 0
+    }
+
+    fn decode(data: &[u8]) -> Result<Self> {
+        ensure!(data.len() >= OBSERVATION_SIZE, "observation data too short");
+        Ok(Self {
+            cumulative_active_bin_id: i128::from_le_bytes(data[0..16].try_into()?),
+            created_at: i64::from_le_bytes(data[16..24].try_into()?),
+            last_updated_at: i64::from_le_bytes(data[24..32].try_into()?),
+        })
+    }
+}
+
+pub trait OracleExtension {
+    fn get_latest_index(&self) -> usize;
+    fn get_earliest_index(&self) -> usize;
+    /// Decode the observations stored after the oracle metadata in `account_data`, ordered from
+    /// the oldest to the latest. Uninitialized observations are skipped.
+    fn get_observations(&self, account_data: &[u8]) -> Result<Vec<Observation>>;
+}
+
+impl OracleExtension for Oracle {
+    fn get_latest_index(&self) -> usize {
+        self.idx as usize
+    }
+
+    fn get_earliest_index(&self) -> usize {
+        let next_index = self.idx + 1;
+        if next_index >= self.active_size {
+            0
+        } else {
+            next_index as usize
+        }
+    }
+
+    fn get_observations(&self, account_data: &[u8]) -> Result<Vec<Observation>> {
+        // Oracles are created with the default length, and can only grow
+        ensure!(
+            self.length >= DEFAULT_OBSERVATION_LENGTH,
+            "invalid oracle length {}",
+            self.length
+        );
+        ensure!(
+            self.active_size <= self.length
+                && (self.active_size == 0 || self.idx < self.active_size),
+            "invalid oracle index {} for active size {}",
+            self.idx,
+            self.active_size
+        );
+
+        let observations_data = account_data
+            .get(ORACLE_METADATA_SIZE..)
+            .context("account data too short for oracle")?;
+        ensure!(
+            observations_data.len() >= self.length as usize * OBSERVATION_SIZE,
+            "account data too short: expected {} observations",
+            self.length
+        );
+
+        let active_size = self.active_size as usize;
+        let earliest_index = self.get_earliest_index();
+
+        let mut observations = Vec::with_capacity(active_size);
+        for i in 0..active_size {
+            let offset = ((earliest_index + i) % active_size) * OBSERVATION_SIZE;
+            let observation =
+                Observation::decode(&observations_data[offset..offset + OBSERVATION_SIZE])?;
+            if observation.is_initialized() {
+                observations.push(observation);
+            }
+        }
+
+        Ok(observations)
+    }
+}
+
+/// Time weighted average active bin, and its price, over a time window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Twap {
+    pub active_id: i32,
+    /// Price of `active_id` in Q64.64
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub price: u128,
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+}
+
+/// Oracle of a pair, with its observations ordered from the oldest to the latest.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DynamicOracle {
+    pub oracle: Oracle,
+    pub observations: Vec<Observation>,
+}
+
+impl DynamicOracle {
+    pub fn parse(account_data: &[u8]) -> Result<Self> {
+        let oracle: Oracle = pod_read_unaligned_skip_disc(account_data)?;
+        let observations = oracle.get_observations(account_data)?;
+        Ok(Self {
+            oracle,
+            observations,
+        })
+    }
+
+    pub fn get_earliest_timestamp(&self) -> Option<i64> {
+        self.observations
+            .first()
+            .map(|observation| observation.last_updated_at)
+    }
+
+    /// Longest window ending at `current_timestamp` covered by the observations.
+    pub fn get_max_duration(&self, current_timestamp: i64) -> u64 {
+        self.get_earliest_timestamp()
+            .map(|earliest_timestamp| {
+                current_timestamp.saturating_sub(earliest_timestamp).max(0) as u64
+            })
+            .unwrap_or(0)
+    }
+
+    /// Cumulative active bin id at `timestamp`, interpolated between the observations. After the
+    /// latest observation, `active_id`, the current active bin of the pair, is accumulated. None
+    /// if `timestamp` is before the oldest observation.
+    pub fn get_cumulative_active_bin_id(&self, active_id: i32, timestamp: i64) -> Option<i128> {
+        let latest = self.observations.last()?;
+
+        if timestamp >= latest.last_updated_at {
+            let elapsed = i128::from(timestamp - latest.last_updated_at);
+            return latest
+                .cumulative_active_bin_id
+                .checked_add(i128::from(active_id).checked_mul(elapsed)?);
+        }
+
+        let next_index = self
+            .observations
+            .partition_point(|observation| observation.last_updated_at <= timestamp);
+        if next_index == 0 {
+            return None;
+        }
+
+        let previous = &self.observations[next_index - 1];
+        let next = &self.observations[next_index];
+
+        let total_weight = i128::from(next.last_updated_at - previous.last_updated_at);
+        let previous_weight = i128::from(next.last_updated_at - timestamp);
+        let next_weight = i128::from(timestamp - previous.last_updated_at);
+
+        previous
+            .cumulative_active_bin_id
+            .checked_mul(previous_weight)?
+            .checked_add(next.cumulative_active_bin_id.checked_mul(next_weight)?)?
+            .checked_div(total_weight)
+    }
+
+    /// TWAP of the active bin of `lb_pair` between `start_timestamp` and `end_timestamp`. The
+    /// window must be at least `SAMPLE_LIFETIME` long, as shorter windows may fall within the
+    /// latest observation, which is still being updated by every swap.
+    pub fn get_twap(
+        &self,
+        lb_pair: &LbPair,
+        start_timestamp: i64,
+        end_timestamp: i64,
+    ) -> Result<Twap> {
+        ensure!(end_timestamp > start_timestamp, "Invalid TWAP window");
+        let duration = end_timestamp - start_timestamp;
+        ensure!(
+            duration >= SAMPLE_LIFETIME as i64,
+            "TWAP window shorter than the oracle sample lifetime"
+        );
+
+        let start_cumulative_active_bin_id = self
+            .get_cumulative_active_bin_id(lb_pair.active_id, start_timestamp)
+            .context("Oracle observations do not cover the TWAP window")?;
+        let end_cumulative_active_bin_id = self
+            .get_cumulative_active_bin_id(lb_pair.active_id, end_timestamp)
+            .context("Oracle observations do not cover the TWAP window")?;
+
+        let active_id: i32 = end_cumulative_active_bin_id
+            .checked_sub(start_cumulative_active_bin_id)
+            .context("MathOverflow")?
+            .checked_div(duration.into())
+            .context("MathOverflow")?
+            .try_into()
+            .context("MathOverflow")?;
+
+        Ok(Twap {
+            active_id,
+            price: get_price_from_id(active_id, lb_pair.bin_step)?,
+            start_timestamp,
+            end_timestamp,
+        })
+    }
+
+    /// TWAP of the active bin of `lb_pair` over the `window` seconds ending at `current_timestamp`.
+    pub fn get_twap_over_window(
+        &self,
+        lb_pair: &LbPair,
+        current_timestamp: i64,
+        window: u64,
+    ) -> Result<Twap> {
+        let window: i64 = window.try_into().context("MathOverflow")?;
+        let start_timestamp = current_timestamp
+            .checked_sub(window)
+            .context("MathOverflow")?;
+        self.get_twap(lb_pair, start_timestamp, current_timestamp)
+    }
+}
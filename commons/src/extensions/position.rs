@@ -1,4 +1,5 @@
 use crate::*;
+use num_integer::Integer;
 use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey};
 
 pub trait PositionExtension {
@@ -98,3 +99,25 @@ impl PositionExtension for PositionV2 {
         true
     }
 }
+
+/// Split a position bin range into chunks of at most `DEFAULT_BIN_PER_POSITION` bins, the
+/// maximum bin range of the instructions acting on a position.
+pub fn position_bin_range_chunks(lower_bin_id: i32, upper_bin_id: i32) -> Vec<(i32, i32)> {
+    let mut chunked_bin_range = vec![];
+    let bin_range = upper_bin_id - lower_bin_id + 1;
+
+    let (quotient, remainder) = bin_range.div_rem(&(DEFAULT_BIN_PER_POSITION as i32));
+    let chunk = quotient + (remainder != 0) as i32;
+
+    for i in 0..chunk {
+        let min_bin_id = lower_bin_id + DEFAULT_BIN_PER_POSITION as i32 * i;
+        let max_bin_id = std::cmp::min(
+            min_bin_id + DEFAULT_BIN_PER_POSITION as i32 - 1,
+            upper_bin_id,
+        );
+
+        chunked_bin_range.push((min_bin_id, max_bin_id));
+    }
+
+    chunked_bin_range
+}
@@ -0,0 +1,177 @@
+use super::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+
+pub fn create_operator_account(
+    whitelisted_signer: Pubkey,
+    signer: Pubkey,
+    payer: Pubkey,
+    permission: u128,
+) -> Result<Instruction> {
+    let (operator, _bump) = derive_operator_pda(whitelisted_signer);
+
+    let accounts = dlmm::client::accounts::CreateOperatorAccount {
+        operator,
+        whitelisted_signer,
+        signer,
+        payer,
+        system_program: solana_sdk::system_program::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::CreateOperatorAccount { permission }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn close_operator_account(
+    whitelisted_signer: Pubkey,
+    signer: Pubkey,
+    rent_receiver: Pubkey,
+) -> Result<Instruction> {
+    let (operator, _bump) = derive_operator_pda(whitelisted_signer);
+
+    let accounts = dlmm::client::accounts::CloseOperatorAccount {
+        operator,
+        signer,
+        rent_receiver,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::CloseOperatorAccount {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `close_claim_fee_operator_account` instruction, closing the claim fee operator of `operator`.
+pub fn close_claim_fee_operator_account(
+    operator: Pubkey,
+    signer: Pubkey,
+    rent_receiver: Pubkey,
+) -> Result<Instruction> {
+    let (claim_fee_operator, _bump) = derive_claim_protocol_fee_operator_pda(operator);
+
+    let accounts = dlmm::client::accounts::CloseClaimFeeOperatorAccount {
+        claim_fee_operator,
+        rent_receiver,
+        signer,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::CloseClaimFeeOperatorAccount {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `initialize_preset_parameter` instruction. The preset parameter is derived from `ix.index`, see
+/// `derive_preset_parameter_pda_v2`. `signer` is the whitelisted signer of the operator.
+pub fn initialize_preset_parameter(
+    signer: Pubkey,
+    payer: Pubkey,
+    ix: InitPresetParametersIx,
+) -> Result<Instruction> {
+    let (preset_parameter, _bump) = derive_preset_parameter_pda_v2(ix.index);
+    let (operator, _bump) = derive_operator_pda(signer);
+
+    let accounts = dlmm::client::accounts::InitializePresetParameter {
+        preset_parameter,
+        operator,
+        signer,
+        payer,
+        system_program: solana_sdk::system_program::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializePresetParameter { ix }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `close_preset_parameter` instruction, closing a `PresetParameter`. `signer` is the whitelisted
+/// signer of the operator.
+pub fn close_preset_parameter(
+    preset_parameter: Pubkey,
+    signer: Pubkey,
+    rent_receiver: Pubkey,
+) -> Result<Instruction> {
+    let (operator, _bump) = derive_operator_pda(signer);
+
+    let accounts = dlmm::client::accounts::ClosePresetParameter {
+        preset_parameter,
+        operator,
+        signer,
+        rent_receiver,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::ClosePresetParameter {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `close_preset_parameter2` instruction, closing a `PresetParameter2`. `signer` is the
+/// whitelisted signer of the operator.
+pub fn close_preset_parameter2(
+    preset_parameter: Pubkey,
+    signer: Pubkey,
+    rent_receiver: Pubkey,
+) -> Result<Instruction> {
+    let (operator, _bump) = derive_operator_pda(signer);
+
+    let accounts = dlmm::client::accounts::ClosePresetParameter2 {
+        preset_parameter,
+        operator,
+        signer,
+        rent_receiver,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::ClosePresetParameter2 {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `initialize_token_badge` instruction. `signer` is the whitelisted signer of the operator.
+pub fn initialize_token_badge(
+    token_mint: Pubkey,
+    signer: Pubkey,
+    payer: Pubkey,
+) -> Result<Instruction> {
+    let (token_badge, _bump) = derive_token_badge_pda(token_mint);
+    let (operator, _bump) = derive_operator_pda(signer);
+
+    let accounts = dlmm::client::accounts::InitializeTokenBadge {
+        token_mint,
+        token_badge,
+        operator,
+        signer,
+        payer,
+        system_program: solana_sdk::system_program::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializeTokenBadge {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `close_token_badge` instruction. `signer` is the whitelisted signer of the operator.
+pub fn close_token_badge(
+    token_mint: Pubkey,
+    signer: Pubkey,
+    rent_receiver: Pubkey,
+) -> Result<Instruction> {
+    let (token_badge, _bump) = derive_token_badge_pda(token_mint);
+    let (operator, _bump) = derive_operator_pda(signer);
+
+    let accounts = dlmm::client::accounts::CloseTokenBadge {
+        token_badge,
+        rent_receiver,
+        operator,
+        signer,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::CloseTokenBadge {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
@@ -0,0 +1,166 @@
+use super::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use std::collections::BTreeSet;
+
+/// Bin arrays containing `bin_ids`, in ascending order.
+fn get_limit_order_bin_array_account_metas(
+    lb_pair: Pubkey,
+    bin_ids: impl Iterator<Item = i32>,
+) -> Result<Vec<AccountMeta>> {
+    let bin_array_indexes = bin_ids
+        .map(BinArray::bin_id_to_bin_array_index)
+        .collect::<Result<BTreeSet<_>>>()?;
+
+    Ok(bin_array_indexes
+        .into_iter()
+        .map(|index| AccountMeta::new(derive_bin_array_pda(lb_pair, index.into()).0, false))
+        .collect())
+}
+
+/// `place_limit_order` instruction, with the bin arrays of the bins of `params`. The bin ids of
+/// `params.bins` are taken as absolute bin ids to derive the bin arrays and the bitmap extension.
+#[allow(clippy::too_many_arguments)]
+pub fn place_limit_order(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    limit_order: Pubkey,
+    payer: Pubkey,
+    owner: Pubkey,
+    sender: Pubkey,
+    user_token: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    params: PlaceLimitOrderParams,
+) -> Result<Instruction> {
+    let (min_bin_id, max_bin_id) = get_bin_id_range(params.bins.iter().map(|bin| bin.id))?;
+
+    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
+    let (reserve, token_mint, token_program) = if params.is_ask_side {
+        (
+            lb_pair_state.reserve_x,
+            lb_pair_state.token_x_mint,
+            token_x_program,
+        )
+    } else {
+        (
+            lb_pair_state.reserve_y,
+            lb_pair_state.token_y_mint,
+            token_y_program,
+        )
+    };
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::PlaceLimitOrder {
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_bin_array_bitmap_extension_for_bin_range(
+            lb_pair,
+            lb_pair_state,
+            min_bin_id,
+            max_bin_id,
+        )?),
+        reserve,
+        token_mint,
+        limit_order,
+        payer,
+        owner,
+        user_token,
+        sender,
+        token_program,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(get_limit_order_bin_array_account_metas(
+        lb_pair,
+        params.bins.iter().map(|bin| bin.id),
+    )?);
+
+    let data = dlmm::client::args::PlaceLimitOrder {
+        params,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+/// `cancel_limit_order` instruction, with the bin arrays of `bins`.
+#[allow(clippy::too_many_arguments)]
+pub fn cancel_limit_order(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    limit_order: Pubkey,
+    owner: Pubkey,
+    owner_token_x: Pubkey,
+    owner_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    bins: Vec<i32>,
+) -> Result<Instruction> {
+    let (min_bin_id, max_bin_id) = get_bin_id_range(bins.iter().copied())?;
+
+    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::CancelLimitOrder {
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_bin_array_bitmap_extension_for_bin_range(
+            lb_pair,
+            lb_pair_state,
+            min_bin_id,
+            max_bin_id,
+        )?),
+        reserve_x: lb_pair_state.reserve_x,
+        reserve_y: lb_pair_state.reserve_y,
+        token_x_mint: lb_pair_state.token_x_mint,
+        token_y_mint: lb_pair_state.token_y_mint,
+        limit_order,
+        owner_token_x,
+        owner_token_y,
+        owner,
+        token_x_program,
+        token_y_program,
+        memo_program: spl_memo::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(get_limit_order_bin_array_account_metas(
+        lb_pair,
+        bins.iter().copied(),
+    )?);
+
+    let data = dlmm::client::args::CancelLimitOrder {
+        bins,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+pub fn close_limit_order_if_empty(
+    limit_order: Pubkey,
+    owner: Pubkey,
+    rent_receiver: Pubkey,
+) -> Result<Instruction> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::CloseLimitOrderIfEmpty {
+        limit_order,
+        owner,
+        rent_receiver,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::CloseLimitOrderIfEmpty {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
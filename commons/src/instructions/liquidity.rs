@@ -0,0 +1,844 @@
+use super::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+
+/// Accounts shared by `add_liquidity`, `add_liquidity_by_weight`, `add_liquidity_by_strategy`,
+/// `remove_liquidity`, `remove_all_liquidity` and `remove_liquidity_by_range`.
+fn get_liquidity_account_metas(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+) -> Result<Vec<AccountMeta>> {
+    let lb_pair = position_state.lb_pair;
+    let [bin_array_lower, bin_array_upper] = get_position_bin_arrays(position_state)?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    Ok(dlmm::client::accounts::AddLiquidity {
+        position,
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_bin_array_bitmap_extension_for_bin_range(
+            lb_pair,
+            lb_pair_state,
+            position_state.lower_bin_id,
+            position_state.upper_bin_id,
+        )?),
+        user_token_x,
+        user_token_y,
+        reserve_x: lb_pair_state.reserve_x,
+        reserve_y: lb_pair_state.reserve_y,
+        token_x_mint: lb_pair_state.token_x_mint,
+        token_y_mint: lb_pair_state.token_y_mint,
+        bin_array_lower,
+        bin_array_upper,
+        sender,
+        token_x_program: spl_token::ID,
+        token_y_program: spl_token::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None))
+}
+
+/// Accounts shared by `add_liquidity_one_side`, `add_liquidity_by_strategy_one_side` and
+/// `add_liquidity_one_side_precise`.
+fn get_liquidity_one_side_account_metas(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token: Pubkey,
+    is_token_x: bool,
+) -> Result<Vec<AccountMeta>> {
+    let lb_pair = position_state.lb_pair;
+    let [bin_array_lower, bin_array_upper] = get_position_bin_arrays(position_state)?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let (reserve, token_mint) = if is_token_x {
+        (lb_pair_state.reserve_x, lb_pair_state.token_x_mint)
+    } else {
+        (lb_pair_state.reserve_y, lb_pair_state.token_y_mint)
+    };
+
+    Ok(dlmm::client::accounts::AddLiquidityOneSide {
+        position,
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_bin_array_bitmap_extension_for_bin_range(
+            lb_pair,
+            lb_pair_state,
+            position_state.lower_bin_id,
+            position_state.upper_bin_id,
+        )?),
+        user_token,
+        reserve,
+        token_mint,
+        bin_array_lower,
+        bin_array_upper,
+        sender,
+        token_program: spl_token::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None))
+}
+
+/// Accounts shared by `add_liquidity2`, `add_liquidity_by_weight2` and
+/// `add_liquidity_by_strategy2`, and the remaining accounts: the transfer hook accounts and the
+/// bin arrays from `min_bin_id` to `max_bin_id`.
+#[allow(clippy::too_many_arguments)]
+fn get_liquidity2_account_metas(
+    position: Pubkey,
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    min_bin_id: i32,
+    max_bin_id: i32,
+) -> Result<(Vec<AccountMeta>, Vec<AccountMeta>, RemainingAccountsInfo)> {
+    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::AddLiquidity2 {
+        position,
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_bin_array_bitmap_extension_for_bin_range(
+            lb_pair,
+            lb_pair_state,
+            min_bin_id,
+            max_bin_id,
+        )?),
+        user_token_x,
+        user_token_y,
+        reserve_x: lb_pair_state.reserve_x,
+        reserve_y: lb_pair_state.reserve_y,
+        token_x_mint: lb_pair_state.token_x_mint,
+        token_y_mint: lb_pair_state.token_y_mint,
+        sender,
+        token_x_program,
+        token_y_program,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(BinArray::get_bin_array_account_metas_coverage(
+        min_bin_id, max_bin_id, lb_pair,
+    )?);
+
+    Ok((accounts, remaining_accounts, remaining_accounts_info))
+}
+
+/// Accounts shared by `remove_liquidity2` and `remove_liquidity_by_range2`, and the remaining
+/// accounts: the transfer hook accounts and the bin arrays from `min_bin_id` to `max_bin_id`.
+#[allow(clippy::too_many_arguments)]
+fn get_remove_liquidity2_account_metas(
+    position: Pubkey,
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    min_bin_id: i32,
+    max_bin_id: i32,
+) -> Result<(Vec<AccountMeta>, Vec<AccountMeta>, RemainingAccountsInfo)> {
+    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::RemoveLiquidity2 {
+        position,
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_bin_array_bitmap_extension_for_bin_range(
+            lb_pair,
+            lb_pair_state,
+            min_bin_id,
+            max_bin_id,
+        )?),
+        user_token_x,
+        user_token_y,
+        reserve_x: lb_pair_state.reserve_x,
+        reserve_y: lb_pair_state.reserve_y,
+        token_x_mint: lb_pair_state.token_x_mint,
+        token_y_mint: lb_pair_state.token_y_mint,
+        sender,
+        token_x_program,
+        token_y_program,
+        memo_program: spl_memo::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(BinArray::get_bin_array_account_metas_coverage(
+        min_bin_id, max_bin_id, lb_pair,
+    )?);
+
+    Ok((accounts, remaining_accounts, remaining_accounts_info))
+}
+
+pub fn add_liquidity(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    liquidity_parameter: LiquidityParameter,
+) -> Result<Instruction> {
+    let accounts = get_liquidity_account_metas(
+        position,
+        position_state,
+        lb_pair_state,
+        sender,
+        user_token_x,
+        user_token_y,
+    )?;
+
+    let data = dlmm::client::args::AddLiquidity {
+        liquidity_parameter,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `add_liquidity2` instruction, with the bin arrays of the bins of `liquidity_parameter`.
+#[allow(clippy::too_many_arguments)]
+pub fn add_liquidity2(
+    position: Pubkey,
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    liquidity_parameter: LiquidityParameter,
+) -> Result<Instruction> {
+    let (min_bin_id, max_bin_id) = get_bin_id_range(
+        liquidity_parameter
+            .bin_liquidity_dist
+            .iter()
+            .map(|dist| dist.bin_id),
+    )?;
+
+    let (accounts, remaining_accounts, remaining_accounts_info) = get_liquidity2_account_metas(
+        position,
+        lb_pair,
+        lb_pair_state,
+        sender,
+        user_token_x,
+        user_token_y,
+        transfer_hook_accounts,
+        min_bin_id,
+        max_bin_id,
+    )?;
+
+    let data = dlmm::client::args::AddLiquidity2 {
+        liquidity_parameter,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+pub fn add_liquidity_by_weight(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    liquidity_parameter: LiquidityParameterByWeight,
+) -> Result<Instruction> {
+    let accounts = get_liquidity_account_metas(
+        position,
+        position_state,
+        lb_pair_state,
+        sender,
+        user_token_x,
+        user_token_y,
+    )?;
+
+    let data = dlmm::client::args::AddLiquidityByWeight {
+        liquidity_parameter,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `add_liquidity_by_weight2` instruction, with the bin arrays of the bins of
+/// `liquidity_parameter`.
+#[allow(clippy::too_many_arguments)]
+pub fn add_liquidity_by_weight2(
+    position: Pubkey,
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    liquidity_parameter: LiquidityParameterByWeight,
+) -> Result<Instruction> {
+    let (min_bin_id, max_bin_id) = get_bin_id_range(
+        liquidity_parameter
+            .bin_liquidity_dist
+            .iter()
+            .map(|dist| dist.bin_id),
+    )?;
+
+    let (accounts, remaining_accounts, remaining_accounts_info) = get_liquidity2_account_metas(
+        position,
+        lb_pair,
+        lb_pair_state,
+        sender,
+        user_token_x,
+        user_token_y,
+        transfer_hook_accounts,
+        min_bin_id,
+        max_bin_id,
+    )?;
+
+    let data = dlmm::client::args::AddLiquidityByWeight2 {
+        liquidity_parameter,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+pub fn add_liquidity_by_strategy(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    liquidity_parameter: LiquidityParameterByStrategy,
+) -> Result<Instruction> {
+    let accounts = get_liquidity_account_metas(
+        position,
+        position_state,
+        lb_pair_state,
+        sender,
+        user_token_x,
+        user_token_y,
+    )?;
+
+    let data = dlmm::client::args::AddLiquidityByStrategy {
+        liquidity_parameter,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `add_liquidity_by_strategy2` instruction, with the bin arrays of the strategy bin range.
+#[allow(clippy::too_many_arguments)]
+pub fn add_liquidity_by_strategy2(
+    position: Pubkey,
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    liquidity_parameter: LiquidityParameterByStrategy,
+) -> Result<Instruction> {
+    let (accounts, remaining_accounts, remaining_accounts_info) = get_liquidity2_account_metas(
+        position,
+        lb_pair,
+        lb_pair_state,
+        sender,
+        user_token_x,
+        user_token_y,
+        transfer_hook_accounts,
+        liquidity_parameter.strategy_parameters.min_bin_id,
+        liquidity_parameter.strategy_parameters.max_bin_id,
+    )?;
+
+    let data = dlmm::client::args::AddLiquidityByStrategy2 {
+        liquidity_parameter,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+/// `add_liquidity_one_side` instruction, depositing token X if `is_token_x`, else token Y.
+pub fn add_liquidity_one_side(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token: Pubkey,
+    is_token_x: bool,
+    liquidity_parameter: LiquidityOneSideParameter,
+) -> Result<Instruction> {
+    let accounts = get_liquidity_one_side_account_metas(
+        position,
+        position_state,
+        lb_pair_state,
+        sender,
+        user_token,
+        is_token_x,
+    )?;
+
+    let data = dlmm::client::args::AddLiquidityOneSide {
+        liquidity_parameter,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `add_liquidity_by_strategy_one_side` instruction, depositing token X if `is_token_x`, else
+/// token Y.
+pub fn add_liquidity_by_strategy_one_side(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token: Pubkey,
+    is_token_x: bool,
+    liquidity_parameter: LiquidityParameterByStrategyOneSide,
+) -> Result<Instruction> {
+    let accounts = get_liquidity_one_side_account_metas(
+        position,
+        position_state,
+        lb_pair_state,
+        sender,
+        user_token,
+        is_token_x,
+    )?;
+
+    let data = dlmm::client::args::AddLiquidityByStrategyOneSide {
+        liquidity_parameter,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `add_liquidity_one_side_precise` instruction, depositing token X if `is_token_x`, else token
+/// Y.
+pub fn add_liquidity_one_side_precise(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token: Pubkey,
+    is_token_x: bool,
+    parameter: AddLiquiditySingleSidePreciseParameter,
+) -> Result<Instruction> {
+    let accounts = get_liquidity_one_side_account_metas(
+        position,
+        position_state,
+        lb_pair_state,
+        sender,
+        user_token,
+        is_token_x,
+    )?;
+
+    let data = dlmm::client::args::AddLiquidityOneSidePrecise { parameter }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `add_liquidity_one_side_precise2` instruction, depositing token X if `is_token_x`, else token
+/// Y, with the bin arrays of the bins of `liquidity_parameter`.
+#[allow(clippy::too_many_arguments)]
+pub fn add_liquidity_one_side_precise2(
+    position: Pubkey,
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token: Pubkey,
+    is_token_x: bool,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    liquidity_parameter: AddLiquiditySingleSidePreciseParameter2,
+) -> Result<Instruction> {
+    let (min_bin_id, max_bin_id) =
+        get_bin_id_range(liquidity_parameter.bins.iter().map(|bin| bin.bin_id))?;
+
+    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
+    let (reserve, token_mint, token_program) = if is_token_x {
+        (
+            lb_pair_state.reserve_x,
+            lb_pair_state.token_x_mint,
+            token_x_program,
+        )
+    } else {
+        (
+            lb_pair_state.reserve_y,
+            lb_pair_state.token_y_mint,
+            token_y_program,
+        )
+    };
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::AddLiquidityOneSidePrecise2 {
+        position,
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_bin_array_bitmap_extension_for_bin_range(
+            lb_pair,
+            lb_pair_state,
+            min_bin_id,
+            max_bin_id,
+        )?),
+        user_token,
+        reserve,
+        token_mint,
+        sender,
+        token_program,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(BinArray::get_bin_array_account_metas_coverage(
+        min_bin_id, max_bin_id, lb_pair,
+    )?);
+
+    let data = dlmm::client::args::AddLiquidityOneSidePrecise2 {
+        liquidity_parameter,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+pub fn remove_liquidity(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    bin_liquidity_removal: Vec<BinLiquidityReduction>,
+) -> Result<Instruction> {
+    let accounts = get_liquidity_account_metas(
+        position,
+        position_state,
+        lb_pair_state,
+        sender,
+        user_token_x,
+        user_token_y,
+    )?;
+
+    let data = dlmm::client::args::RemoveLiquidity {
+        bin_liquidity_removal,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `remove_liquidity2` instruction, with the bin arrays of the bins of `bin_liquidity_removal`.
+#[allow(clippy::too_many_arguments)]
+pub fn remove_liquidity2(
+    position: Pubkey,
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    bin_liquidity_removal: Vec<BinLiquidityReduction>,
+) -> Result<Instruction> {
+    let (min_bin_id, max_bin_id) = get_bin_id_range(
+        bin_liquidity_removal
+            .iter()
+            .map(|reduction| reduction.bin_id),
+    )?;
+
+    let (accounts, remaining_accounts, remaining_accounts_info) =
+        get_remove_liquidity2_account_metas(
+            position,
+            lb_pair,
+            lb_pair_state,
+            sender,
+            user_token_x,
+            user_token_y,
+            transfer_hook_accounts,
+            min_bin_id,
+            max_bin_id,
+        )?;
+
+    let data = dlmm::client::args::RemoveLiquidity2 {
+        bin_liquidity_removal,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+pub fn remove_all_liquidity(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+) -> Result<Instruction> {
+    let accounts = get_liquidity_account_metas(
+        position,
+        position_state,
+        lb_pair_state,
+        sender,
+        user_token_x,
+        user_token_y,
+    )?;
+
+    let data = dlmm::client::args::RemoveAllLiquidity {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn remove_liquidity_by_range(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    from_bin_id: i32,
+    to_bin_id: i32,
+    bps_to_remove: u16,
+) -> Result<Instruction> {
+    let accounts = get_liquidity_account_metas(
+        position,
+        position_state,
+        lb_pair_state,
+        sender,
+        user_token_x,
+        user_token_y,
+    )?;
+
+    let data = dlmm::client::args::RemoveLiquidityByRange {
+        from_bin_id,
+        to_bin_id,
+        bps_to_remove,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `remove_liquidity_by_range2` instruction, with the bin arrays from `from_bin_id` to
+/// `to_bin_id`.
+#[allow(clippy::too_many_arguments)]
+pub fn remove_liquidity_by_range2(
+    position: Pubkey,
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    from_bin_id: i32,
+    to_bin_id: i32,
+    bps_to_remove: u16,
+) -> Result<Instruction> {
+    let (accounts, remaining_accounts, remaining_accounts_info) =
+        get_remove_liquidity2_account_metas(
+            position,
+            lb_pair,
+            lb_pair_state,
+            sender,
+            user_token_x,
+            user_token_y,
+            transfer_hook_accounts,
+            from_bin_id,
+            to_bin_id,
+        )?;
+
+    let data = dlmm::client::args::RemoveLiquidityByRange2 {
+        from_bin_id,
+        to_bin_id,
+        bps_to_remove,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+/// `rebalance_liquidity` instruction, with the bin arrays of the position range and of the bins
+/// deposited to by `params.adds`.
+#[allow(clippy::too_many_arguments)]
+pub fn rebalance_liquidity(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    owner: Pubkey,
+    rent_payer: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    params: RebalanceLiquidityParams,
+) -> Result<Instruction> {
+    let lb_pair = position_state.lb_pair;
+
+    let mut bin_ids = vec![position_state.lower_bin_id, position_state.upper_bin_id];
+    for add in params.adds.iter() {
+        bin_ids.push(
+            params
+                .active_id
+                .checked_add(add.min_delta_id)
+                .context("MathOverflow")?,
+        );
+        bin_ids.push(
+            params
+                .active_id
+                .checked_add(add.max_delta_id)
+                .context("MathOverflow")?,
+        );
+    }
+    let (min_bin_id, max_bin_id) = get_bin_id_range(bin_ids.into_iter())?;
+
+    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::RebalanceLiquidity {
+        position,
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_bin_array_bitmap_extension_for_bin_range(
+            lb_pair,
+            lb_pair_state,
+            min_bin_id,
+            max_bin_id,
+        )?),
+        user_token_x,
+        user_token_y,
+        reserve_x: lb_pair_state.reserve_x,
+        reserve_y: lb_pair_state.reserve_y,
+        token_x_mint: lb_pair_state.token_x_mint,
+        token_y_mint: lb_pair_state.token_y_mint,
+        owner,
+        rent_payer,
+        token_x_program,
+        token_y_program,
+        memo_program: spl_memo::ID,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(BinArray::get_bin_array_account_metas_coverage(
+        min_bin_id, max_bin_id, lb_pair,
+    )?);
+
+    let data = dlmm::client::args::RebalanceLiquidity {
+        params,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+pub fn claim_fee(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+) -> Result<Instruction> {
+    let lb_pair = position_state.lb_pair;
+    let [bin_array_lower, bin_array_upper] = get_position_bin_arrays(position_state)?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::ClaimFee {
+        lb_pair,
+        position,
+        bin_array_lower,
+        bin_array_upper,
+        sender,
+        reserve_x: lb_pair_state.reserve_x,
+        reserve_y: lb_pair_state.reserve_y,
+        user_token_x,
+        user_token_y,
+        token_x_mint: lb_pair_state.token_x_mint,
+        token_y_mint: lb_pair_state.token_y_mint,
+        token_program: spl_token::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::ClaimFee {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `claim_fee2` instruction, claiming the fees of the position bins from `min_bin_id` to
+/// `max_bin_id`.
+#[allow(clippy::too_many_arguments)]
+pub fn claim_fee2(
+    position: Pubkey,
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    min_bin_id: i32,
+    max_bin_id: i32,
+) -> Result<Instruction> {
+    let [token_program_x, token_program_y] = lb_pair_state.get_token_programs()?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::ClaimFee2 {
+        lb_pair,
+        position,
+        sender,
+        reserve_x: lb_pair_state.reserve_x,
+        reserve_y: lb_pair_state.reserve_y,
+        user_token_x,
+        user_token_y,
+        token_x_mint: lb_pair_state.token_x_mint,
+        token_y_mint: lb_pair_state.token_y_mint,
+        token_program_x,
+        token_program_y,
+        memo_program: spl_memo::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(BinArray::get_bin_array_account_metas_coverage(
+        min_bin_id, max_bin_id, lb_pair,
+    )?);
+
+    let data = dlmm::client::args::ClaimFee2 {
+        min_bin_id,
+        max_bin_id,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
@@ -0,0 +1,98 @@
+//! Builders of the DLMM instructions. The PDAs, token programs and bin arrays are derived from
+//! the arguments, and the transfer hook accounts are attached as remaining accounts, so the
+//! returned instructions are ready to be sent. `for_idl_type_generation_do_not_call` is not
+//! covered, as it only exists to export types in the IDL.
+
+use crate::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+pub mod admin;
+pub use admin::*;
+
+pub mod limit_order;
+pub use limit_order::*;
+
+pub mod liquidity;
+pub use liquidity::*;
+
+pub mod pair;
+pub use pair::*;
+
+pub mod position;
+pub use position::*;
+
+pub mod reward;
+pub use reward::*;
+
+pub mod swap;
+pub use swap::*;
+
+/// Remaining accounts slices and accounts of the transfer hooks, as returned by
+/// `get_potential_token_2022_related_ix_data_and_accounts`.
+pub type TransferHookAccounts = (Vec<RemainingAccountsSlice>, Vec<AccountMeta>);
+
+/// Bitmap extension of `lb_pair`, if any bin array between `min_bin_id` and `max_bin_id` is out of
+/// the range of the default bitmap of the pair.
+pub fn get_bin_array_bitmap_extension_for_bin_range(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    min_bin_id: i32,
+    max_bin_id: i32,
+) -> Result<Option<Pubkey>> {
+    let is_overflow = BinArray::get_bin_array_indexes_coverage(min_bin_id, max_bin_id)?
+        .into_iter()
+        .any(|index| lb_pair_state.is_overflow_default_bin_array_bitmap(index));
+
+    Ok(is_overflow.then(|| derive_bin_array_bitmap_extension(lb_pair).0))
+}
+
+/// Optional account, replaced by the program id when absent.
+fn optional_account(account: Option<Pubkey>) -> Option<Pubkey> {
+    account.or(Some(dlmm::ID))
+}
+
+fn get_bin_array_bitmap_extension(lb_pair: Pubkey, has_bitmap_extension: bool) -> Option<Pubkey> {
+    optional_account(has_bitmap_extension.then(|| derive_bin_array_bitmap_extension(lb_pair).0))
+}
+
+/// `bin_array_lower` and `bin_array_upper` accounts of the instructions acting on a position.
+fn get_position_bin_arrays(position_state: &PositionV2) -> Result<[Pubkey; 2]> {
+    position_state
+        .get_bin_array_keys_coverage()?
+        .try_into()
+        .map_err(|_| anyhow!("Invalid position bin array coverage"))
+}
+
+fn get_bin_id_range(bin_ids: impl Iterator<Item = i32>) -> Result<(i32, i32)> {
+    bin_ids
+        .fold(None, |range, bin_id| match range {
+            Some((min_bin_id, max_bin_id)) => Some((
+                std::cmp::min(min_bin_id, bin_id),
+                std::cmp::max(max_bin_id, bin_id),
+            )),
+            None => Some((bin_id, bin_id)),
+        })
+        .context("No bin")
+}
+
+fn split_transfer_hook_accounts(
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+) -> (RemainingAccountsInfo, Vec<AccountMeta>) {
+    let (slices, accounts) = transfer_hook_accounts.unwrap_or_default();
+    (RemainingAccountsInfo { slices }, accounts)
+}
+
+fn build_instruction(
+    accounts: Vec<AccountMeta>,
+    remaining_accounts: Vec<AccountMeta>,
+    data: Vec<u8>,
+) -> Instruction {
+    Instruction {
+        program_id: dlmm::ID,
+        accounts: [accounts, remaining_accounts].concat(),
+        data,
+    }
+}
@@ -0,0 +1,598 @@
+use super::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+
+/// Bitmap extension to initialize with a new pair, if its active bin array is out of the range of
+/// the default bitmap.
+fn get_initial_bin_array_bitmap_extension(
+    lb_pair: Pubkey,
+    active_id: i32,
+) -> Result<Option<Pubkey>> {
+    let active_bin_array_index = BinArray::bin_id_to_bin_array_index(active_id)?;
+    let (min_bitmap_id, max_bitmap_id) = LbPair::bitmap_range();
+    let is_overflow =
+        active_bin_array_index < min_bitmap_id || active_bin_array_index > max_bitmap_id;
+
+    Ok(is_overflow.then(|| derive_bin_array_bitmap_extension(lb_pair).0))
+}
+
+fn get_token_badges(
+    token_mint_x: Pubkey,
+    token_mint_y: Pubkey,
+    has_token_badges: [bool; 2],
+) -> [Option<Pubkey>; 2] {
+    let [has_token_badge_x, has_token_badge_y] = has_token_badges;
+    [
+        optional_account(has_token_badge_x.then(|| derive_token_badge_pda(token_mint_x).0)),
+        optional_account(has_token_badge_y.then(|| derive_token_badge_pda(token_mint_y).0)),
+    ]
+}
+
+/// `initialize_lb_pair` instruction. The pair is derived from the mints, `bin_step` and
+/// `base_factor` of `preset_parameter`, see `derive_lb_pair_pda2`.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_lb_pair(
+    token_mint_x: Pubkey,
+    token_mint_y: Pubkey,
+    preset_parameter: Pubkey,
+    base_factor: u16,
+    funder: Pubkey,
+    active_id: i32,
+    bin_step: u16,
+) -> Result<Instruction> {
+    let (lb_pair, _bump) = derive_lb_pair_pda2(token_mint_x, token_mint_y, bin_step, base_factor);
+    let (reserve_x, _bump) = derive_reserve_pda(token_mint_x, lb_pair);
+    let (reserve_y, _bump) = derive_reserve_pda(token_mint_y, lb_pair);
+    let (oracle, _bump) = derive_oracle_pda(lb_pair);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::InitializeLbPair {
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_initial_bin_array_bitmap_extension(
+            lb_pair, active_id,
+        )?),
+        token_mint_x,
+        token_mint_y,
+        reserve_x,
+        reserve_y,
+        oracle,
+        preset_parameter,
+        funder,
+        token_program: spl_token::ID,
+        system_program: solana_sdk::system_program::ID,
+        rent: solana_sdk::sysvar::rent::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializeLbPair {
+        active_id,
+        bin_step,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `initialize_lb_pair2` instruction. The pair is derived from the mints and `preset_parameter`,
+/// see `derive_lb_pair_with_preset_parameter_key`. The token badges of the mints are included if
+/// `has_token_badges`.
+pub fn initialize_lb_pair2(
+    token_mint_x: Pubkey,
+    token_mint_y: Pubkey,
+    token_programs: [Pubkey; 2],
+    has_token_badges: [bool; 2],
+    preset_parameter: Pubkey,
+    funder: Pubkey,
+    params: InitializeLbPair2Params,
+) -> Result<Instruction> {
+    let (lb_pair, _bump) =
+        derive_lb_pair_with_preset_parameter_key(preset_parameter, token_mint_x, token_mint_y);
+    let (reserve_x, _bump) = derive_reserve_pda(token_mint_x, lb_pair);
+    let (reserve_y, _bump) = derive_reserve_pda(token_mint_y, lb_pair);
+    let (oracle, _bump) = derive_oracle_pda(lb_pair);
+    let (event_authority, _bump) = derive_event_authority_pda();
+    let [token_program_x, token_program_y] = token_programs;
+    let [token_badge_x, token_badge_y] =
+        get_token_badges(token_mint_x, token_mint_y, has_token_badges);
+
+    let accounts = dlmm::client::accounts::InitializeLbPair2 {
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_initial_bin_array_bitmap_extension(
+            lb_pair,
+            params.active_id,
+        )?),
+        token_mint_x,
+        token_mint_y,
+        reserve_x,
+        reserve_y,
+        oracle,
+        preset_parameter,
+        funder,
+        token_badge_x,
+        token_badge_y,
+        token_program_x,
+        token_program_y,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializeLbPair2 { params }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `initialize_customizable_permissionless_lb_pair` instruction. The pair is derived from the
+/// mints, see `derive_customizable_permissionless_lb_pair`.
+pub fn initialize_customizable_permissionless_lb_pair(
+    token_mint_x: Pubkey,
+    token_mint_y: Pubkey,
+    funder: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    params: CustomizableParams,
+) -> Result<Instruction> {
+    let (lb_pair, _bump) = derive_customizable_permissionless_lb_pair(token_mint_x, token_mint_y);
+    let (reserve_x, _bump) = derive_reserve_pda(token_mint_x, lb_pair);
+    let (reserve_y, _bump) = derive_reserve_pda(token_mint_y, lb_pair);
+    let (oracle, _bump) = derive_oracle_pda(lb_pair);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::InitializeCustomizablePermissionlessLbPair {
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_initial_bin_array_bitmap_extension(
+            lb_pair,
+            params.active_id,
+        )?),
+        token_mint_x,
+        token_mint_y,
+        reserve_x,
+        reserve_y,
+        oracle,
+        user_token_x,
+        funder,
+        token_program: spl_token::ID,
+        system_program: solana_sdk::system_program::ID,
+        user_token_y,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializeCustomizablePermissionlessLbPair { params }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `initialize_customizable_permissionless_lb_pair2` instruction. The pair is derived from the
+/// mints, see `derive_customizable_permissionless_lb_pair`. The token badges of the mints are
+/// included if `has_token_badges`.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_customizable_permissionless_lb_pair2(
+    token_mint_x: Pubkey,
+    token_mint_y: Pubkey,
+    token_programs: [Pubkey; 2],
+    has_token_badges: [bool; 2],
+    funder: Pubkey,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    params: CustomizableParams,
+) -> Result<Instruction> {
+    let (lb_pair, _bump) = derive_customizable_permissionless_lb_pair(token_mint_x, token_mint_y);
+    let (reserve_x, _bump) = derive_reserve_pda(token_mint_x, lb_pair);
+    let (reserve_y, _bump) = derive_reserve_pda(token_mint_y, lb_pair);
+    let (oracle, _bump) = derive_oracle_pda(lb_pair);
+    let (event_authority, _bump) = derive_event_authority_pda();
+    let [token_program_x, token_program_y] = token_programs;
+    let [token_badge_x, token_badge_y] =
+        get_token_badges(token_mint_x, token_mint_y, has_token_badges);
+
+    let accounts = dlmm::client::accounts::InitializeCustomizablePermissionlessLbPair2 {
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_initial_bin_array_bitmap_extension(
+            lb_pair,
+            params.active_id,
+        )?),
+        token_mint_x,
+        token_mint_y,
+        reserve_x,
+        reserve_y,
+        oracle,
+        user_token_x,
+        funder,
+        token_badge_x,
+        token_badge_y,
+        token_program_x,
+        token_program_y,
+        system_program: solana_sdk::system_program::ID,
+        user_token_y,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializeCustomizablePermissionlessLbPair2 { params }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `initialize_permission_lb_pair` instruction. The pair is derived from `base`, see
+/// `derive_permission_lb_pair_pda`. `signer` is the whitelisted signer of the operator, and the
+/// token badges of the mints are included if `has_token_badges`.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_permission_lb_pair(
+    base: Pubkey,
+    token_mint_x: Pubkey,
+    token_mint_y: Pubkey,
+    token_programs: [Pubkey; 2],
+    has_token_badges: [bool; 2],
+    payer: Pubkey,
+    signer: Pubkey,
+    ix_data: InitPermissionPairIx,
+) -> Result<Instruction> {
+    let (lb_pair, _bump) =
+        derive_permission_lb_pair_pda(base, token_mint_x, token_mint_y, ix_data.bin_step);
+    let (reserve_x, _bump) = derive_reserve_pda(token_mint_x, lb_pair);
+    let (reserve_y, _bump) = derive_reserve_pda(token_mint_y, lb_pair);
+    let (oracle, _bump) = derive_oracle_pda(lb_pair);
+    let (operator, _bump) = derive_operator_pda(signer);
+    let (event_authority, _bump) = derive_event_authority_pda();
+    let [token_program_x, token_program_y] = token_programs;
+    let [token_badge_x, token_badge_y] =
+        get_token_badges(token_mint_x, token_mint_y, has_token_badges);
+
+    let accounts = dlmm::client::accounts::InitializePermissionLbPair {
+        base,
+        lb_pair,
+        bin_array_bitmap_extension: optional_account(get_initial_bin_array_bitmap_extension(
+            lb_pair,
+            ix_data.active_id,
+        )?),
+        token_mint_x,
+        token_mint_y,
+        reserve_x,
+        reserve_y,
+        oracle,
+        payer,
+        operator,
+        signer,
+        token_badge_x,
+        token_badge_y,
+        token_program_x,
+        token_program_y,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializePermissionLbPair { ix_data }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn initialize_bin_array(lb_pair: Pubkey, funder: Pubkey, index: i64) -> Result<Instruction> {
+    let (bin_array, _bump) = derive_bin_array_pda(lb_pair, index);
+
+    let accounts = dlmm::client::accounts::InitializeBinArray {
+        lb_pair,
+        bin_array,
+        funder,
+        system_program: solana_sdk::system_program::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializeBinArray { index }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn initialize_bin_array_bitmap_extension(
+    lb_pair: Pubkey,
+    funder: Pubkey,
+) -> Result<Instruction> {
+    let (bin_array_bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
+
+    let accounts = dlmm::client::accounts::InitializeBinArrayBitmapExtension {
+        lb_pair,
+        bin_array_bitmap_extension,
+        funder,
+        system_program: solana_sdk::system_program::ID,
+        rent: solana_sdk::sysvar::rent::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializeBinArrayBitmapExtension {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn close_bin_array(
+    lb_pair: Pubkey,
+    index: i64,
+    signer: Pubkey,
+    rent_receiver: Pubkey,
+) -> Result<Instruction> {
+    let (bin_array, _bump) = derive_bin_array_pda(lb_pair, index);
+
+    let accounts = dlmm::client::accounts::CloseBinArray {
+        lb_pair,
+        bin_array,
+        rent_receiver,
+        signer,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::CloseBinArray {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `go_to_a_bin` instruction. The bin arrays of the active bin and of `bin_id` are included if
+/// they exist, as given by `has_from_bin_array` and `has_to_bin_array`.
+pub fn go_to_a_bin(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    has_bitmap_extension: bool,
+    has_from_bin_array: bool,
+    has_to_bin_array: bool,
+    bin_id: i32,
+) -> Result<Instruction> {
+    let from_bin_array = BinArray::bin_id_to_bin_array_key(lb_pair, lb_pair_state.active_id)?;
+    let to_bin_array = BinArray::bin_id_to_bin_array_key(lb_pair, bin_id)?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::GoToABin {
+        lb_pair,
+        bin_array_bitmap_extension: get_bin_array_bitmap_extension(lb_pair, has_bitmap_extension),
+        from_bin_array: optional_account(has_from_bin_array.then_some(from_bin_array)),
+        to_bin_array: optional_account(has_to_bin_array.then_some(to_bin_array)),
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::GoToABin { bin_id }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn increase_oracle_length(
+    lb_pair: Pubkey,
+    funder: Pubkey,
+    length_to_add: u64,
+) -> Result<Instruction> {
+    let (oracle, _bump) = derive_oracle_pda(lb_pair);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::IncreaseOracleLength {
+        oracle,
+        funder,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::IncreaseOracleLength { length_to_add }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn set_activation_point(
+    lb_pair: Pubkey,
+    signer: Pubkey,
+    activation_point: u64,
+) -> Result<Instruction> {
+    let accounts =
+        dlmm::client::accounts::SetActivationPoint { lb_pair, signer }.to_account_metas(None);
+
+    let data = dlmm::client::args::SetActivationPoint { activation_point }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `set_pair_status` instruction. `signer` is the whitelisted signer of the operator.
+pub fn set_pair_status(lb_pair: Pubkey, signer: Pubkey, status: u8) -> Result<Instruction> {
+    let (operator, _bump) = derive_operator_pda(signer);
+
+    let accounts = dlmm::client::accounts::SetPairStatus {
+        lb_pair,
+        operator,
+        signer,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::SetPairStatus { status }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn set_pair_status_permissionless(
+    lb_pair: Pubkey,
+    signer: Pubkey,
+    status: u8,
+) -> Result<Instruction> {
+    let accounts = dlmm::client::accounts::SetPairStatusPermissionless { lb_pair, signer }
+        .to_account_metas(None);
+
+    let data = dlmm::client::args::SetPairStatusPermissionless { status }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn set_pre_activation_duration(
+    lb_pair: Pubkey,
+    signer: Pubkey,
+    pre_activation_duration: u64,
+) -> Result<Instruction> {
+    let accounts =
+        dlmm::client::accounts::SetPreActivationDuration { lb_pair, signer }.to_account_metas(None);
+
+    let data = dlmm::client::args::SetPreActivationDuration {
+        pre_activation_duration,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn set_pre_activation_swap_address(
+    lb_pair: Pubkey,
+    signer: Pubkey,
+    pre_activation_swap_address: Pubkey,
+) -> Result<Instruction> {
+    let accounts = dlmm::client::accounts::SetPreActivationSwapAddress { lb_pair, signer }
+        .to_account_metas(None);
+
+    let data = dlmm::client::args::SetPreActivationSwapAddress {
+        pre_activation_swap_address,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `update_base_fee_parameters` instruction. `signer` is the whitelisted signer of the operator.
+pub fn update_base_fee_parameters(
+    lb_pair: Pubkey,
+    signer: Pubkey,
+    fee_parameter: BaseFeeParameter,
+) -> Result<Instruction> {
+    let (operator, _bump) = derive_operator_pda(signer);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::UpdateBaseFeeParameters {
+        lb_pair,
+        operator,
+        signer,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::UpdateBaseFeeParameters { fee_parameter }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `update_dynamic_fee_parameters` instruction. `signer` is the whitelisted signer of the
+/// operator.
+pub fn update_dynamic_fee_parameters(
+    lb_pair: Pubkey,
+    signer: Pubkey,
+    fee_parameter: DynamicFeeParameter,
+) -> Result<Instruction> {
+    let (operator, _bump) = derive_operator_pda(signer);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::UpdateDynamicFeeParameters {
+        lb_pair,
+        operator,
+        signer,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::UpdateDynamicFeeParameters { fee_parameter }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `withdraw_protocol_fee` instruction. `signer` is the whitelisted signer of the operator.
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_protocol_fee(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    signer: Pubkey,
+    receiver_token_x: Pubkey,
+    receiver_token_y: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    max_amount_x: u64,
+    max_amount_y: u64,
+) -> Result<Instruction> {
+    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
+    let (operator, _bump) = derive_operator_pda(signer);
+
+    let accounts = dlmm::client::accounts::WithdrawProtocolFee {
+        lb_pair,
+        reserve_x: lb_pair_state.reserve_x,
+        reserve_y: lb_pair_state.reserve_y,
+        token_x_mint: lb_pair_state.token_x_mint,
+        token_y_mint: lb_pair_state.token_y_mint,
+        receiver_token_x,
+        receiver_token_y,
+        operator,
+        signer,
+        token_x_program,
+        token_y_program,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+
+    let data = dlmm::client::args::WithdrawProtocolFee {
+        max_amount_x,
+        max_amount_y,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+/// `zap_protocol_fee` instruction, withdrawing the protocol fee of token X if `is_token_x`, else
+/// token Y. `signer` is the whitelisted signer of the operator.
+pub fn zap_protocol_fee(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    signer: Pubkey,
+    receiver_token: Pubkey,
+    is_token_x: bool,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    max_amount: u64,
+) -> Result<Instruction> {
+    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
+    let (reserve, token_mint, token_program) = if is_token_x {
+        (
+            lb_pair_state.reserve_x,
+            lb_pair_state.token_x_mint,
+            token_x_program,
+        )
+    } else {
+        (
+            lb_pair_state.reserve_y,
+            lb_pair_state.token_y_mint,
+            token_y_program,
+        )
+    };
+    let (operator, _bump) = derive_operator_pda(signer);
+
+    let accounts = dlmm::client::accounts::ZapProtocolFee {
+        lb_pair,
+        reserve,
+        token_mint,
+        receiver_token,
+        operator,
+        signer,
+        token_program,
+        sysvar_instructions: solana_sdk::sysvar::instructions::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+
+    let data = dlmm::client::args::ZapProtocolFee {
+        max_amount,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
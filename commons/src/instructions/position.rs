@@ -0,0 +1,396 @@
+use super::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+
+/// `initialize_position` instruction. `position` is a new keypair account.
+pub fn initialize_position(
+    lb_pair: Pubkey,
+    payer: Pubkey,
+    position: Pubkey,
+    owner: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+) -> Result<Instruction> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::InitializePosition {
+        payer,
+        position,
+        lb_pair,
+        owner,
+        system_program: solana_sdk::system_program::ID,
+        rent: solana_sdk::sysvar::rent::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializePosition {
+        lower_bin_id,
+        width,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `initialize_position2` instruction. `position` is a new keypair account.
+pub fn initialize_position2(
+    lb_pair: Pubkey,
+    payer: Pubkey,
+    position: Pubkey,
+    owner: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+) -> Result<Instruction> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::InitializePosition2 {
+        payer,
+        position,
+        lb_pair,
+        owner,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializePosition2 {
+        lower_bin_id,
+        width,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `initialize_position_pda` instruction. The position is derived from `base`, see
+/// `derive_position_pda`.
+pub fn initialize_position_pda(
+    lb_pair: Pubkey,
+    payer: Pubkey,
+    base: Pubkey,
+    owner: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+) -> Result<Instruction> {
+    let (position, _bump) = derive_position_pda(lb_pair, base, lower_bin_id, width);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::InitializePositionPda {
+        payer,
+        base,
+        position,
+        lb_pair,
+        owner,
+        system_program: solana_sdk::system_program::ID,
+        rent: solana_sdk::sysvar::rent::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializePositionPda {
+        lower_bin_id,
+        width,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `initialize_position_by_operator` instruction. The position is derived from `base`, see
+/// `derive_position_pda`.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_position_by_operator(
+    lb_pair: Pubkey,
+    payer: Pubkey,
+    base: Pubkey,
+    owner: Pubkey,
+    operator: Pubkey,
+    operator_token_x: Pubkey,
+    owner_token_x: Pubkey,
+    lower_bin_id: i32,
+    width: i32,
+    fee_owner: Pubkey,
+    lock_release_point: u64,
+) -> Result<Instruction> {
+    let (position, _bump) = derive_position_pda(lb_pair, base, lower_bin_id, width);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::InitializePositionByOperator {
+        payer,
+        base,
+        position,
+        lb_pair,
+        owner,
+        operator,
+        operator_token_x,
+        owner_token_x,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializePositionByOperator {
+        lower_bin_id,
+        width,
+        fee_owner,
+        lock_release_point,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn close_position(
+    position: Pubkey,
+    position_state: &PositionV2,
+    sender: Pubkey,
+    rent_receiver: Pubkey,
+) -> Result<Instruction> {
+    let [bin_array_lower, bin_array_upper] = get_position_bin_arrays(position_state)?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::ClosePosition {
+        position,
+        lb_pair: position_state.lb_pair,
+        bin_array_lower,
+        bin_array_upper,
+        sender,
+        rent_receiver,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::ClosePosition {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `close_position2` instruction, with the bin arrays of the position range.
+pub fn close_position2(
+    position: Pubkey,
+    position_state: &PositionV2,
+    sender: Pubkey,
+    rent_receiver: Pubkey,
+) -> Result<Instruction> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::ClosePosition2 {
+        position,
+        sender,
+        rent_receiver,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let bin_arrays = BinArray::get_bin_array_account_metas_coverage(
+        position_state.lower_bin_id,
+        position_state.upper_bin_id,
+        position_state.lb_pair,
+    )?;
+
+    let data = dlmm::client::args::ClosePosition2 {}.data();
+
+    Ok(build_instruction(accounts, bin_arrays, data))
+}
+
+pub fn close_position_if_empty(
+    position: Pubkey,
+    sender: Pubkey,
+    rent_receiver: Pubkey,
+) -> Result<Instruction> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::ClosePositionIfEmpty {
+        position,
+        sender,
+        rent_receiver,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::ClosePositionIfEmpty {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn increase_position_length(
+    lb_pair: Pubkey,
+    position: Pubkey,
+    funder: Pubkey,
+    owner: Pubkey,
+    length_to_add: u16,
+    side: u8,
+) -> Result<Instruction> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::IncreasePositionLength {
+        funder,
+        lb_pair,
+        position,
+        owner,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::IncreasePositionLength {
+        length_to_add,
+        side,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn increase_position_length2(
+    lb_pair: Pubkey,
+    position: Pubkey,
+    funder: Pubkey,
+    owner: Pubkey,
+    minimum_upper_bin_id: i32,
+) -> Result<Instruction> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::IncreasePositionLength2 {
+        funder,
+        lb_pair,
+        position,
+        owner,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::IncreasePositionLength2 {
+        minimum_upper_bin_id,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn decrease_position_length(
+    position: Pubkey,
+    owner: Pubkey,
+    rent_receiver: Pubkey,
+    length_to_remove: u16,
+    side: u8,
+) -> Result<Instruction> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::DecreasePositionLength {
+        rent_receiver,
+        position,
+        owner,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::DecreasePositionLength {
+        length_to_remove,
+        side,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn set_permissionless_operation_bits(
+    position: Pubkey,
+    owner: Pubkey,
+    bits: u8,
+) -> Result<Instruction> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::SetPermissionlessOperationBits {
+        position,
+        owner,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::SetPermissionlessOperationBits { bits }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn update_position_operator(
+    position: Pubkey,
+    owner: Pubkey,
+    operator: Pubkey,
+) -> Result<Instruction> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::UpdatePositionOperator {
+        position,
+        owner,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::UpdatePositionOperator { operator }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+pub fn update_fees_and_rewards(
+    position: Pubkey,
+    position_state: &PositionV2,
+    owner: Pubkey,
+) -> Result<Instruction> {
+    let [bin_array_lower, bin_array_upper] = get_position_bin_arrays(position_state)?;
+
+    let accounts = dlmm::client::accounts::UpdateFeesAndRewards {
+        position,
+        lb_pair: position_state.lb_pair,
+        bin_array_lower,
+        bin_array_upper,
+        owner,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::UpdateFeesAndRewards {}.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `update_fees_and_reward2` instruction, updating the position bins from `min_bin_id` to
+/// `max_bin_id`.
+pub fn update_fees_and_reward2(
+    lb_pair: Pubkey,
+    position: Pubkey,
+    owner: Pubkey,
+    min_bin_id: i32,
+    max_bin_id: i32,
+) -> Result<Instruction> {
+    let accounts = dlmm::client::accounts::UpdateFeesAndReward2 {
+        position,
+        lb_pair,
+        owner,
+    }
+    .to_account_metas(None);
+
+    let bin_arrays =
+        BinArray::get_bin_array_account_metas_coverage(min_bin_id, max_bin_id, lb_pair)?;
+
+    let data = dlmm::client::args::UpdateFeesAndReward2 {
+        min_bin_id,
+        max_bin_id,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, bin_arrays, data))
+}
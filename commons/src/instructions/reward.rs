@@ -0,0 +1,283 @@
+use super::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+
+fn get_reward_mint(lb_pair_state: &LbPair, reward_index: u64) -> Result<Pubkey> {
+    let reward_info = lb_pair_state
+        .reward_infos
+        .get(reward_index as usize)
+        .context("Invalid reward index")?;
+    Ok(reward_info.mint)
+}
+
+fn get_active_bin_array(lb_pair: Pubkey, lb_pair_state: &LbPair) -> Result<Pubkey> {
+    BinArray::bin_id_to_bin_array_key(lb_pair, lb_pair_state.active_id)
+}
+
+/// `initialize_reward` instruction. `signer` is the whitelisted signer of the operator, and the
+/// token badge of `reward_mint` is included if `has_token_badge`.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_reward(
+    lb_pair: Pubkey,
+    reward_mint: Pubkey,
+    reward_token_program: Pubkey,
+    has_token_badge: bool,
+    signer: Pubkey,
+    payer: Pubkey,
+    reward_index: u64,
+    reward_duration: u64,
+    funder: Pubkey,
+) -> Result<Instruction> {
+    let (reward_vault, _bump) = derive_reward_vault_pda(lb_pair, reward_index);
+    let (operator, _bump) = derive_operator_pda(signer);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::InitializeReward {
+        lb_pair,
+        reward_vault,
+        reward_mint,
+        token_badge: optional_account(
+            has_token_badge.then(|| derive_token_badge_pda(reward_mint).0),
+        ),
+        operator,
+        signer,
+        payer,
+        token_program: reward_token_program,
+        system_program: solana_sdk::system_program::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::InitializeReward {
+        reward_index,
+        reward_duration,
+        funder,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `fund_reward` instruction, with the bin array of the active bin of the pair.
+#[allow(clippy::too_many_arguments)]
+pub fn fund_reward(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    reward_token_program: Pubkey,
+    funder: Pubkey,
+    funder_token_account: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    reward_index: u64,
+    amount: u64,
+    carry_forward: bool,
+) -> Result<Instruction> {
+    let (reward_vault, _bump) = derive_reward_vault_pda(lb_pair, reward_index);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::FundReward {
+        lb_pair,
+        reward_vault,
+        reward_mint: get_reward_mint(lb_pair_state, reward_index)?,
+        funder_token_account,
+        funder,
+        bin_array: get_active_bin_array(lb_pair, lb_pair_state)?,
+        token_program: reward_token_program,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+
+    let data = dlmm::client::args::FundReward {
+        reward_index,
+        amount,
+        carry_forward,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+pub fn claim_reward(
+    position: Pubkey,
+    position_state: &PositionV2,
+    lb_pair_state: &LbPair,
+    sender: Pubkey,
+    user_token_account: Pubkey,
+    reward_index: u64,
+) -> Result<Instruction> {
+    let lb_pair = position_state.lb_pair;
+    let [bin_array_lower, bin_array_upper] = get_position_bin_arrays(position_state)?;
+    let (reward_vault, _bump) = derive_reward_vault_pda(lb_pair, reward_index);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::ClaimReward {
+        lb_pair,
+        position,
+        bin_array_lower,
+        bin_array_upper,
+        sender,
+        reward_vault,
+        reward_mint: get_reward_mint(lb_pair_state, reward_index)?,
+        user_token_account,
+        token_program: spl_token::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::ClaimReward { reward_index }.data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `claim_reward2` instruction, claiming the rewards of the position bins from `min_bin_id` to
+/// `max_bin_id`.
+#[allow(clippy::too_many_arguments)]
+pub fn claim_reward2(
+    position: Pubkey,
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    reward_token_program: Pubkey,
+    sender: Pubkey,
+    user_token_account: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    reward_index: u64,
+    min_bin_id: i32,
+    max_bin_id: i32,
+) -> Result<Instruction> {
+    let (reward_vault, _bump) = derive_reward_vault_pda(lb_pair, reward_index);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::ClaimReward2 {
+        lb_pair,
+        position,
+        sender,
+        reward_vault,
+        reward_mint: get_reward_mint(lb_pair_state, reward_index)?,
+        user_token_account,
+        token_program: reward_token_program,
+        memo_program: spl_memo::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(BinArray::get_bin_array_account_metas_coverage(
+        min_bin_id, max_bin_id, lb_pair,
+    )?);
+
+    let data = dlmm::client::args::ClaimReward2 {
+        reward_index,
+        min_bin_id,
+        max_bin_id,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+/// `update_reward_duration` instruction, with the bin array of the active bin of the pair.
+/// `signer` is the whitelisted signer of the operator.
+pub fn update_reward_duration(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    signer: Pubkey,
+    reward_index: u64,
+    new_duration: u64,
+) -> Result<Instruction> {
+    let (operator, _bump) = derive_operator_pda(signer);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::UpdateRewardDuration {
+        lb_pair,
+        operator,
+        signer,
+        bin_array: get_active_bin_array(lb_pair, lb_pair_state)?,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::UpdateRewardDuration {
+        reward_index,
+        new_duration,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `update_reward_funder` instruction. `signer` is the whitelisted signer of the operator.
+pub fn update_reward_funder(
+    lb_pair: Pubkey,
+    signer: Pubkey,
+    reward_index: u64,
+    new_funder: Pubkey,
+) -> Result<Instruction> {
+    let (operator, _bump) = derive_operator_pda(signer);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::UpdateRewardFunder {
+        lb_pair,
+        operator,
+        signer,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let data = dlmm::client::args::UpdateRewardFunder {
+        reward_index,
+        new_funder,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, vec![], data))
+}
+
+/// `withdraw_ineligible_reward` instruction, with the bin array of the active bin of the pair.
+pub fn withdraw_ineligible_reward(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    reward_token_program: Pubkey,
+    funder: Pubkey,
+    funder_token_account: Pubkey,
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    reward_index: u64,
+) -> Result<Instruction> {
+    let (reward_vault, _bump) = derive_reward_vault_pda(lb_pair, reward_index);
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let accounts = dlmm::client::accounts::WithdrawIneligibleReward {
+        lb_pair,
+        reward_vault,
+        reward_mint: get_reward_mint(lb_pair_state, reward_index)?,
+        funder_token_account,
+        funder,
+        bin_array: get_active_bin_array(lb_pair, lb_pair_state)?,
+        token_program: reward_token_program,
+        memo_program: spl_memo::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None);
+
+    let (remaining_accounts_info, remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+
+    let data = dlmm::client::args::WithdrawIneligibleReward {
+        reward_index,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
@@ -0,0 +1,314 @@
+use super::*;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+
+/// Accounts of `swap2`, `swap_exact_out2` and `swap_with_price_impact2`, without the remaining
+/// accounts.
+#[allow(clippy::too_many_arguments)]
+pub fn get_swap2_account_metas(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    has_bitmap_extension: bool,
+    user: Pubkey,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    host_fee_in: Option<Pubkey>,
+) -> Result<Vec<AccountMeta>> {
+    let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    Ok(dlmm::client::accounts::Swap2 {
+        lb_pair,
+        bin_array_bitmap_extension: get_bin_array_bitmap_extension(lb_pair, has_bitmap_extension),
+        reserve_x: lb_pair_state.reserve_x,
+        reserve_y: lb_pair_state.reserve_y,
+        user_token_in,
+        user_token_out,
+        token_x_mint: lb_pair_state.token_x_mint,
+        token_y_mint: lb_pair_state.token_y_mint,
+        oracle: lb_pair_state.oracle,
+        host_fee_in: optional_account(host_fee_in),
+        user,
+        token_x_program,
+        token_y_program,
+        memo_program: spl_memo::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn get_swap_account_metas(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    has_bitmap_extension: bool,
+    user: Pubkey,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    host_fee_in: Option<Pubkey>,
+) -> Vec<AccountMeta> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    dlmm::client::accounts::Swap {
+        lb_pair,
+        bin_array_bitmap_extension: get_bin_array_bitmap_extension(lb_pair, has_bitmap_extension),
+        reserve_x: lb_pair_state.reserve_x,
+        reserve_y: lb_pair_state.reserve_y,
+        user_token_in,
+        user_token_out,
+        token_x_mint: lb_pair_state.token_x_mint,
+        token_y_mint: lb_pair_state.token_y_mint,
+        oracle: lb_pair_state.oracle,
+        host_fee_in: optional_account(host_fee_in),
+        user,
+        token_x_program: spl_token::ID,
+        token_y_program: spl_token::ID,
+        event_authority,
+        program: dlmm::ID,
+    }
+    .to_account_metas(None)
+}
+
+fn get_bin_array_account_metas(bin_arrays: &[Pubkey]) -> Vec<AccountMeta> {
+    bin_arrays
+        .iter()
+        .map(|&bin_array| AccountMeta::new(bin_array, false))
+        .collect()
+}
+
+/// `swap` instruction. `bin_arrays` are the bin arrays traversed by the swap, in traversal order.
+#[allow(clippy::too_many_arguments)]
+pub fn swap(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    has_bitmap_extension: bool,
+    user: Pubkey,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    host_fee_in: Option<Pubkey>,
+    bin_arrays: &[Pubkey],
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<Instruction> {
+    let accounts = get_swap_account_metas(
+        lb_pair,
+        lb_pair_state,
+        has_bitmap_extension,
+        user,
+        user_token_in,
+        user_token_out,
+        host_fee_in,
+    );
+
+    let data = dlmm::client::args::Swap {
+        amount_in,
+        min_amount_out,
+    }
+    .data();
+
+    Ok(build_instruction(
+        accounts,
+        get_bin_array_account_metas(bin_arrays),
+        data,
+    ))
+}
+
+/// `swap2` instruction. `bin_arrays` are the bin arrays traversed by the swap, in traversal order.
+#[allow(clippy::too_many_arguments)]
+pub fn swap2(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    has_bitmap_extension: bool,
+    user: Pubkey,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    host_fee_in: Option<Pubkey>,
+    bin_arrays: &[Pubkey],
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<Instruction> {
+    let accounts = get_swap2_account_metas(
+        lb_pair,
+        lb_pair_state,
+        has_bitmap_extension,
+        user,
+        user_token_in,
+        user_token_out,
+        host_fee_in,
+    )?;
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(get_bin_array_account_metas(bin_arrays));
+
+    let data = dlmm::client::args::Swap2 {
+        amount_in,
+        min_amount_out,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+/// `swap_exact_out` instruction. `bin_arrays` are the bin arrays traversed by the swap, in
+/// traversal order.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_exact_out(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    has_bitmap_extension: bool,
+    user: Pubkey,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    host_fee_in: Option<Pubkey>,
+    bin_arrays: &[Pubkey],
+    max_in_amount: u64,
+    out_amount: u64,
+) -> Result<Instruction> {
+    let accounts = get_swap_account_metas(
+        lb_pair,
+        lb_pair_state,
+        has_bitmap_extension,
+        user,
+        user_token_in,
+        user_token_out,
+        host_fee_in,
+    );
+
+    let data = dlmm::client::args::SwapExactOut {
+        max_in_amount,
+        out_amount,
+    }
+    .data();
+
+    Ok(build_instruction(
+        accounts,
+        get_bin_array_account_metas(bin_arrays),
+        data,
+    ))
+}
+
+/// `swap_exact_out2` instruction. `bin_arrays` are the bin arrays traversed by the swap, in
+/// traversal order.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_exact_out2(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    has_bitmap_extension: bool,
+    user: Pubkey,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    host_fee_in: Option<Pubkey>,
+    bin_arrays: &[Pubkey],
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    max_in_amount: u64,
+    out_amount: u64,
+) -> Result<Instruction> {
+    let accounts = get_swap2_account_metas(
+        lb_pair,
+        lb_pair_state,
+        has_bitmap_extension,
+        user,
+        user_token_in,
+        user_token_out,
+        host_fee_in,
+    )?;
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(get_bin_array_account_metas(bin_arrays));
+
+    let data = dlmm::client::args::SwapExactOut2 {
+        max_in_amount,
+        out_amount,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
+
+/// `swap_with_price_impact` instruction. `bin_arrays` are the bin arrays traversed by the swap,
+/// in traversal order.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_with_price_impact(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    has_bitmap_extension: bool,
+    user: Pubkey,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    host_fee_in: Option<Pubkey>,
+    bin_arrays: &[Pubkey],
+    amount_in: u64,
+    active_id: Option<i32>,
+    max_price_impact_bps: u16,
+) -> Result<Instruction> {
+    let accounts = get_swap_account_metas(
+        lb_pair,
+        lb_pair_state,
+        has_bitmap_extension,
+        user,
+        user_token_in,
+        user_token_out,
+        host_fee_in,
+    );
+
+    let data = dlmm::client::args::SwapWithPriceImpact {
+        amount_in,
+        active_id,
+        max_price_impact_bps,
+    }
+    .data();
+
+    Ok(build_instruction(
+        accounts,
+        get_bin_array_account_metas(bin_arrays),
+        data,
+    ))
+}
+
+/// `swap_with_price_impact2` instruction. `bin_arrays` are the bin arrays traversed by the swap,
+/// in traversal order.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_with_price_impact2(
+    lb_pair: Pubkey,
+    lb_pair_state: &LbPair,
+    has_bitmap_extension: bool,
+    user: Pubkey,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    host_fee_in: Option<Pubkey>,
+    bin_arrays: &[Pubkey],
+    transfer_hook_accounts: Option<TransferHookAccounts>,
+    amount_in: u64,
+    active_id: Option<i32>,
+    max_price_impact_bps: u16,
+) -> Result<Instruction> {
+    let accounts = get_swap2_account_metas(
+        lb_pair,
+        lb_pair_state,
+        has_bitmap_extension,
+        user,
+        user_token_in,
+        user_token_out,
+        host_fee_in,
+    )?;
+
+    let (remaining_accounts_info, mut remaining_accounts) =
+        split_transfer_hook_accounts(transfer_hook_accounts);
+    remaining_accounts.extend(get_bin_array_account_metas(bin_arrays));
+
+    let data = dlmm::client::args::SwapWithPriceImpact2 {
+        amount_in,
+        active_id,
+        max_price_impact_bps,
+        remaining_accounts_info,
+    }
+    .data();
+
+    Ok(build_instruction(accounts, remaining_accounts, data))
+}
@@ -35,6 +35,38 @@ pub use pda::*;
 pub mod quote;
 pub use quote::*;
 
+pub mod pool_snapshot;
+pub use pool_snapshot::*;
+
+pub mod routing;
+pub use routing::*;
+
+#[cfg(feature = "rpc")]
+pub mod dlmm_client;
+#[cfg(feature = "rpc")]
+pub use dlmm_client::*;
+
+pub mod events;
+pub use events::*;
+
+pub mod errors;
+pub use errors::*;
+
+#[cfg(feature = "serde")]
+mod serialization;
+
+pub mod dlmm_account;
+pub use dlmm_account::*;
+
+#[cfg(feature = "spl")]
+pub mod amm;
+
+#[cfg(feature = "spl")]
+pub mod instructions;
+
+pub mod liquidity_strategy;
+pub use liquidity_strategy::*;
+
 pub mod seeds;
 pub use seeds::*;
 
@@ -44,10 +76,21 @@ pub use math::*;
 pub mod typedefs;
 pub use typedefs::*;
 
+#[cfg(feature = "rpc")]
 pub mod rpc_client_extension;
 
+#[cfg(feature = "rpc")]
+pub mod account_provider;
+#[cfg(feature = "rpc")]
+pub use account_provider::*;
+
+#[cfg(feature = "rpc")]
 pub mod account_filters;
+#[cfg(feature = "rpc")]
 pub use account_filters::*;
 
 pub mod token_2022;
 pub use token_2022::*;
+
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
@@ -0,0 +1,731 @@
+use crate::*;
+
+/// Shape of the liquidity deposited to a bin range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LiquidityStrategy {
+    /// Same amount in every bin
+    Spot,
+    /// Most liquidity around the active bin, decreasing towards the range ends
+    Curve,
+    /// Least liquidity around the active bin, increasing towards the range ends
+    BidAsk,
+}
+
+impl LiquidityStrategy {
+    /// Strategy type of `add_liquidity_by_strategy`. The imbalanced variants deposit the given
+    /// amounts of both tokens as is.
+    pub fn to_strategy_type(&self) -> StrategyType {
+        match self {
+            LiquidityStrategy::Spot => StrategyType::SpotImBalanced,
+            LiquidityStrategy::Curve => StrategyType::CurveImBalanced,
+            LiquidityStrategy::BidAsk => StrategyType::BidAskImBalanced,
+        }
+    }
+}
+
+/// `rebalance_liquidity` deposit of the bins `d` bins away from the active bin. Bid side bins
+/// receive `y0 + delta_y * d` of token Y. Ask side bins receive the amount of token X worth
+/// `x0 + delta_x * d` of token Y at the bin price.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiquidityStrategyParameters {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub x0: i128,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub y0: i128,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub delta_x: i128,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub delta_y: i128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinAmount {
+    pub bin_id: i32,
+    pub amount_x: u64,
+    pub amount_y: u64,
+}
+
+fn get_price_base_factor(bin_step: u16) -> Result<u128> {
+    let bps = (u128::from(bin_step) << SCALE_OFFSET) / u128::from(BASIS_POINT_MAX as u32);
+    ONE.checked_add(bps).context("MathOverflow")
+}
+
+fn to_amount(value: i128) -> Result<u64> {
+    ensure!(value >= 0, "Invalid liquidity strategy parameters");
+    u64::try_from(value).context("MathOverflow")
+}
+
+fn get_amounts_into_bins_bid_side(
+    active_id: i32,
+    min_delta_id: i32,
+    max_delta_id: i32,
+    y0: i128,
+    delta_y: i128,
+) -> Result<Vec<BinAmount>> {
+    let mut bin_amounts = vec![];
+
+    for bin_id in active_id + min_delta_id..=active_id + max_delta_id {
+        let delta_bin = i128::from(active_id - bin_id);
+        let amount_y = delta_y
+            .checked_mul(delta_bin)
+            .and_then(|total_delta_y| y0.checked_add(total_delta_y))
+            .context("MathOverflow")?;
+
+        bin_amounts.push(BinAmount {
+            bin_id,
+            amount_x: 0,
+            amount_y: to_amount(amount_y)?,
+        });
+    }
+
+    Ok(bin_amounts)
+}
+
+fn get_amounts_into_bins_ask_side(
+    active_id: i32,
+    bin_step: u16,
+    min_delta_id: i32,
+    max_delta_id: i32,
+    x0: i128,
+    delta_x: i128,
+) -> Result<Vec<BinAmount>> {
+    let min_bin_id = active_id + min_delta_id;
+    let max_bin_id = active_id + max_delta_id;
+
+    let base = get_price_base_factor(bin_step)?;
+    let mut inverse_price = pow(base, -max_bin_id).context("MathOverflow")?;
+
+    let mut bin_amounts = vec![];
+
+    for bin_id in (min_bin_id..=max_bin_id).rev() {
+        let delta_bin = i128::from(bin_id - active_id);
+        let amount_in_quote = delta_x
+            .checked_mul(delta_bin)
+            .and_then(|total_delta_x| x0.checked_add(total_delta_x))
+            .context("MathOverflow")?;
+        let amount_in_quote: u128 = to_amount(amount_in_quote)?.into();
+
+        let amount_x = mul_shr(amount_in_quote, inverse_price, SCALE_OFFSET, Rounding::Down)
+            .context("MathOverflow")?;
+
+        bin_amounts.push(BinAmount {
+            bin_id,
+            amount_x: amount_x.try_into().context("MathOverflow")?,
+            amount_y: 0,
+        });
+
+        inverse_price =
+            mul_shr(inverse_price, base, SCALE_OFFSET, Rounding::Down).context("MathOverflow")?;
+    }
+
+    bin_amounts.reverse();
+
+    Ok(bin_amounts)
+}
+
+fn sum_amount_x(bin_amounts: &[BinAmount]) -> u128 {
+    bin_amounts
+        .iter()
+        .map(|bin_amount| u128::from(bin_amount.amount_x))
+        .sum()
+}
+
+fn sum_amount_y(bin_amounts: &[BinAmount]) -> u128 {
+    bin_amounts
+        .iter()
+        .map(|bin_amount| u128::from(bin_amount.amount_y))
+        .sum()
+}
+
+/// Sum of the inverse prices, in Q64.64, of the bins
+/// `active_id + min_delta_id..=active_id + max_delta_id`, each weighted by `weight_fn(delta_id)`.
+fn sum_weighted_inverse_prices(
+    active_id: i32,
+    bin_step: u16,
+    min_delta_id: i32,
+    max_delta_id: i32,
+    weight_fn: impl Fn(i32) -> i128,
+) -> Result<i128> {
+    let base = get_price_base_factor(bin_step)?;
+    let mut inverse_price = pow(base, -(active_id + max_delta_id)).context("MathOverflow")?;
+    let mut total_weight = 0i128;
+
+    for delta_id in (min_delta_id..=max_delta_id).rev() {
+        let weight = i128::try_from(inverse_price)
+            .ok()
+            .and_then(|inverse_price| inverse_price.checked_mul(weight_fn(delta_id)))
+            .context("MathOverflow")?;
+        total_weight = total_weight.checked_add(weight).context("MathOverflow")?;
+
+        inverse_price =
+            mul_shr(inverse_price, base, SCALE_OFFSET, Rounding::Down).context("MathOverflow")?;
+    }
+
+    Ok(total_weight)
+}
+
+/// `(amount << SCALE_OFFSET) / weight`
+fn shl_div_amount(amount: u64, weight: i128) -> Result<i128> {
+    ensure!(weight > 0, "Invalid liquidity strategy parameters");
+    Ok((i128::from(amount) << SCALE_OFFSET) / weight)
+}
+
+/// First value from `start` towards `end`, both included, for which `is_within` holds. Probes at
+/// exponentially growing distances from `start`, then bisects, so a monotone `is_within` is
+/// evaluated a logarithmic number of times.
+fn find_first_within(
+    start: i128,
+    end: i128,
+    mut is_within: impl FnMut(i128) -> Result<bool>,
+) -> Result<i128> {
+    if is_within(start)? {
+        return Ok(start);
+    }
+
+    let step = if end < start { -1 } else { 1 };
+    let mut outside = start;
+    let mut distance = 1i128;
+
+    let mut within = loop {
+        let probe = if end.abs_diff(start) <= distance.unsigned_abs() {
+            end
+        } else {
+            start + step * distance
+        };
+
+        if is_within(probe)? {
+            break probe;
+        }
+
+        ensure!(probe != end, "Unable to find liquidity strategy parameters");
+
+        outside = probe;
+        distance = distance.saturating_mul(2);
+    };
+
+    while within.abs_diff(outside) > 1 {
+        let middle = outside + (within - outside) / 2;
+
+        if is_within(middle)? {
+            within = middle;
+        } else {
+            outside = middle;
+        }
+    }
+
+    Ok(within)
+}
+
+/// (base, delta) of the bid side bins `min_delta_id..=max_delta_id`, which receive `amount_y`.
+fn find_y_parameters(
+    strategy: LiquidityStrategy,
+    amount_y: u64,
+    min_delta_id: i32,
+    max_delta_id: i32,
+    active_id: i32,
+) -> Result<(i128, i128)> {
+    if min_delta_id > max_delta_id || amount_y == 0 {
+        return Ok((0, 0));
+    }
+
+    let amount_y_u128 = u128::from(amount_y);
+    let amount_y = i128::from(amount_y);
+
+    // min_delta_id = -m1, max_delta_id = -m2
+    let m1 = -i128::from(min_delta_id);
+    let m2 = -i128::from(max_delta_id);
+
+    let is_within_amount_y = |y0: i128, delta_y: i128| -> Result<bool> {
+        let bin_amounts =
+            get_amounts_into_bins_bid_side(active_id, min_delta_id, max_delta_id, y0, delta_y)?;
+        Ok(sum_amount_y(&bin_amounts) <= amount_y_u128)
+    };
+
+    match strategy {
+        LiquidityStrategy::Spot => Ok((amount_y / (m1 - m2 + 1), 0)),
+        LiquidityStrategy::Curve => {
+            // delta_y = -y0 / (m1 + 1), decreasing to the range end
+            let y0 = if min_delta_id == max_delta_id {
+                amount_y
+            } else {
+                let b = m1 - m2 + 1;
+                let c = m1 * (m1 + 1) / 2;
+                let d = m2 * (m2 - 1) / 2;
+                amount_y / (b - (c - d) / (m1 + 1))
+            };
+
+            // Nothing is deposited with y0 = 0
+            let y0 = find_first_within(y0, 0, |y0| is_within_amount_y(y0, -y0 / (m1 + 1)))?;
+
+            Ok((y0, -y0 / (m1 + 1)))
+        }
+        LiquidityStrategy::BidAsk => {
+            // y0 = -delta_y * (m2 - 1), so that the bin closest to the active bin is not empty
+            let delta_y = if min_delta_id == max_delta_id {
+                amount_y
+            } else {
+                let b = (-m2 + 1) * (m1 - m2 + 1);
+                let c = m1 * (m1 + 1) / 2;
+                let d = m2 * (m2 - 1) / 2;
+                amount_y / (b + (c - d))
+            };
+            let y0 = -delta_y * (m2 - 1);
+
+            let delta_y = find_first_within(delta_y, 0, |delta_y| is_within_amount_y(y0, delta_y))?;
+
+            Ok((y0, delta_y))
+        }
+    }
+}
+
+/// (base, delta) of the ask side bins `min_delta_id..=max_delta_id`, which receive `amount_x`.
+fn find_x_parameters(
+    strategy: LiquidityStrategy,
+    amount_x: u64,
+    min_delta_id: i32,
+    max_delta_id: i32,
+    bin_step: u16,
+    active_id: i32,
+) -> Result<(i128, i128)> {
+    if min_delta_id > max_delta_id || amount_x == 0 {
+        return Ok((0, 0));
+    }
+
+    let get_total_amount_x = |x0: i128, delta_x: i128| -> Result<u128> {
+        let bin_amounts = get_amounts_into_bins_ask_side(
+            active_id,
+            bin_step,
+            min_delta_id,
+            max_delta_id,
+            x0,
+            delta_x,
+        )?;
+        Ok(sum_amount_x(&bin_amounts))
+    };
+
+    let m1 = i128::from(min_delta_id);
+    let m2 = i128::from(max_delta_id);
+
+    match strategy {
+        LiquidityStrategy::Spot => {
+            let total_weight = sum_weighted_inverse_prices(
+                active_id,
+                bin_step,
+                min_delta_id,
+                max_delta_id,
+                |_| 1,
+            )?;
+            let x0 = shl_div_amount(amount_x, total_weight)?;
+
+            // Last x0 before the bins receive the whole amount
+            let x0 = find_first_within(x0, u64::MAX.into(), |x0| {
+                Ok(get_total_amount_x(x0, 0)? >= u128::from(amount_x))
+            })?;
+
+            Ok((x0 - 1, 0))
+        }
+        LiquidityStrategy::Curve => {
+            // delta_x = -x0 / m2, decreasing to the range end
+            let base = get_price_base_factor(bin_step)?;
+            let mut b = 0i128;
+            let mut c = 0i128;
+
+            for delta_id in min_delta_id..=max_delta_id {
+                let inverse_price: i128 = pow(base, -(active_id + delta_id))
+                    .and_then(|inverse_price| inverse_price.try_into().ok())
+                    .context("MathOverflow")?;
+                b = b.checked_add(inverse_price).context("MathOverflow")?;
+
+                if m2 != 0 {
+                    let c_delta = inverse_price
+                        .checked_mul(delta_id.into())
+                        .context("MathOverflow")?
+                        / m2;
+                    c = c.checked_add(c_delta).context("MathOverflow")?;
+                }
+            }
+
+            let x0 = shl_div_amount(amount_x, b - c)?;
+            let delta_x = if m2 == 0 { 0 } else { -x0 / m2 };
+
+            // The range end bin is empty with x0 = -delta_x * m2
+            let x0 = find_first_within(x0, -delta_x * m2, |x0| {
+                Ok(get_total_amount_x(x0, delta_x)? <= u128::from(amount_x))
+            })?;
+
+            Ok((x0, delta_x))
+        }
+        LiquidityStrategy::BidAsk => {
+            // x0 = (-m1 + 1) * delta_x, so that the bin closest to the active bin is not empty
+            let weight = sum_weighted_inverse_prices(
+                active_id,
+                bin_step,
+                min_delta_id,
+                max_delta_id,
+                |delta_id| i128::from(delta_id) - m1 + 1,
+            )?;
+            let delta_x = shl_div_amount(amount_x, weight)?;
+
+            // Nothing is deposited with delta_x = 0
+            let delta_x = find_first_within(delta_x, 0, |delta_x| {
+                Ok(get_total_amount_x((-m1 + 1) * delta_x, delta_x)? <= u128::from(amount_x))
+            })?;
+
+            Ok(((-m1 + 1) * delta_x, delta_x))
+        }
+    }
+}
+
+/// `rebalance_liquidity` parameters that deposit at most `amount_x` and `amount_y` to the bins
+/// `active_id + min_delta_id..=active_id + max_delta_id`. The active bin receives token X when
+/// `favor_x_in_active_id`, and token Y otherwise.
+#[allow(clippy::too_many_arguments)]
+pub fn get_liquidity_strategy_parameters(
+    strategy: LiquidityStrategy,
+    amount_x: u64,
+    amount_y: u64,
+    min_delta_id: i32,
+    max_delta_id: i32,
+    bin_step: u16,
+    favor_x_in_active_id: bool,
+    active_id: i32,
+) -> Result<LiquidityStrategyParameters> {
+    if min_delta_id > max_delta_id {
+        return Ok(LiquidityStrategyParameters::default());
+    }
+
+    let deposit_only_y = max_delta_id < 0 || (max_delta_id == 0 && !favor_x_in_active_id);
+    let deposit_only_x = min_delta_id > 0 || (min_delta_id == 0 && favor_x_in_active_id);
+
+    if deposit_only_y {
+        let (y0, delta_y) =
+            find_y_parameters(strategy, amount_y, min_delta_id, max_delta_id, active_id)?;
+        return Ok(LiquidityStrategyParameters {
+            y0,
+            delta_y,
+            ..Default::default()
+        });
+    }
+
+    if deposit_only_x {
+        let (x0, delta_x) = find_x_parameters(
+            strategy,
+            amount_x,
+            min_delta_id,
+            max_delta_id,
+            bin_step,
+            active_id,
+        )?;
+        return Ok(LiquidityStrategyParameters {
+            x0,
+            delta_x,
+            ..Default::default()
+        });
+    }
+
+    let (max_delta_id_bid_side, min_delta_id_ask_side) = if favor_x_in_active_id {
+        (-1, 0)
+    } else {
+        (0, 1)
+    };
+
+    let (y0, delta_y) = find_y_parameters(
+        strategy,
+        amount_y,
+        min_delta_id,
+        max_delta_id_bid_side,
+        active_id,
+    )?;
+    let (x0, delta_x) = find_x_parameters(
+        strategy,
+        amount_x,
+        min_delta_id_ask_side,
+        max_delta_id,
+        bin_step,
+        active_id,
+    )?;
+
+    Ok(LiquidityStrategyParameters {
+        x0,
+        y0,
+        delta_x,
+        delta_y,
+    })
+}
+
+impl LiquidityStrategyParameters {
+    /// Amounts deposited to each bin of `active_id + min_delta_id..=active_id + max_delta_id`.
+    pub fn get_amounts_into_bins(
+        &self,
+        active_id: i32,
+        min_delta_id: i32,
+        max_delta_id: i32,
+        bin_step: u16,
+        favor_x_in_active_id: bool,
+    ) -> Result<Vec<BinAmount>> {
+        let (max_delta_id_bid_side, min_delta_id_ask_side) = if favor_x_in_active_id {
+            (-1, 0)
+        } else {
+            (0, 1)
+        };
+
+        let bid_side = get_amounts_into_bins_bid_side(
+            active_id,
+            min_delta_id,
+            max_delta_id.min(max_delta_id_bid_side),
+            self.y0,
+            self.delta_y,
+        )?;
+        let ask_side = get_amounts_into_bins_ask_side(
+            active_id,
+            bin_step,
+            min_delta_id.max(min_delta_id_ask_side),
+            max_delta_id,
+            self.x0,
+            self.delta_x,
+        )?;
+
+        Ok([bid_side, ask_side].concat())
+    }
+
+    /// Deposit parameters of `rebalance_liquidity`. Negative parameters are encoded in the bit
+    /// flag.
+    pub fn to_add_liquidity_params(
+        &self,
+        min_delta_id: i32,
+        max_delta_id: i32,
+        favor_x_in_active_id: bool,
+    ) -> Result<AddLiquidityParams> {
+        let mut bit_flag = 0;
+        let mut to_unsigned = |value: i128, flag: u8| -> Result<u64> {
+            if value < 0 {
+                bit_flag |= flag;
+            }
+            u64::try_from(value.unsigned_abs()).context("MathOverflow")
+        };
+
+        let x0 = to_unsigned(self.x0, 0b1)?;
+        let y0 = to_unsigned(self.y0, 0b10)?;
+        let delta_x = to_unsigned(self.delta_x, 0b100)?;
+        let delta_y = to_unsigned(self.delta_y, 0b1000)?;
+
+        Ok(AddLiquidityParams {
+            min_delta_id,
+            max_delta_id,
+            x0,
+            y0,
+            delta_x,
+            delta_y,
+            bit_flag,
+            favor_x_in_active_id,
+            padding: [0; 16],
+        })
+    }
+}
+
+/// Weight of `bin_id` in the bid side (`min_bin_id..=max_bin_id` holding token Y) or the ask side
+/// (holding token X) of an `add_liquidity_by_strategy` deposit.
+fn get_strategy_bin_weight(
+    strategy: LiquidityStrategy,
+    bin_id: i32,
+    min_bin_id: i32,
+    max_bin_id: i32,
+    is_bid_side: bool,
+) -> u128 {
+    let ascending = (bin_id - min_bin_id + 1) as u128;
+    let descending = (max_bin_id - bin_id + 1) as u128;
+
+    match (strategy, is_bid_side) {
+        (LiquidityStrategy::Spot, _) => 1,
+        (LiquidityStrategy::Curve, true) | (LiquidityStrategy::BidAsk, false) => ascending,
+        (LiquidityStrategy::Curve, false) | (LiquidityStrategy::BidAsk, true) => descending,
+    }
+}
+
+/// Amounts `add_liquidity_by_strategy` deposits to each bin of `min_bin_id..=max_bin_id`. Each side
+/// splits its amount by the bin weights, the ask side weights being scaled by the inverse bin
+/// price.
+#[allow(clippy::too_many_arguments)]
+fn get_strategy_amounts_into_bins(
+    strategy: LiquidityStrategy,
+    active_id: i32,
+    bin_step: u16,
+    min_bin_id: i32,
+    max_bin_id: i32,
+    amount_x: u64,
+    amount_y: u64,
+    favor_x_in_active_id: bool,
+) -> Result<Vec<BinAmount>> {
+    let (max_bin_id_bid_side, min_bin_id_ask_side) = if favor_x_in_active_id {
+        (active_id - 1, active_id)
+    } else {
+        (active_id, active_id + 1)
+    };
+    let max_bin_id_bid_side = max_bin_id.min(max_bin_id_bid_side);
+    let min_bin_id_ask_side = min_bin_id.max(min_bin_id_ask_side);
+
+    let mut bin_amounts = vec![];
+
+    let bid_side_weights = (min_bin_id..=max_bin_id_bid_side)
+        .map(|bin_id| {
+            let weight =
+                get_strategy_bin_weight(strategy, bin_id, min_bin_id, max_bin_id_bid_side, true);
+            (bin_id, weight)
+        })
+        .collect::<Vec<_>>();
+    let total_weight: u128 = bid_side_weights.iter().map(|(_, weight)| weight).sum();
+
+    for (bin_id, weight) in bid_side_weights {
+        let amount_y = mul_div(amount_y.into(), weight, total_weight, Rounding::Down)
+            .context("MathOverflow")?;
+        bin_amounts.push(BinAmount {
+            bin_id,
+            amount_x: 0,
+            amount_y: amount_y.try_into().context("MathOverflow")?,
+        });
+    }
+
+    let base = get_price_base_factor(bin_step)?;
+    let mut ask_side_weights = vec![];
+    for bin_id in min_bin_id_ask_side..=max_bin_id {
+        let weight =
+            get_strategy_bin_weight(strategy, bin_id, min_bin_id_ask_side, max_bin_id, false);
+        let inverse_price = pow(base, -bin_id).context("MathOverflow")?;
+        let weight = inverse_price.checked_mul(weight).context("MathOverflow")?;
+        ask_side_weights.push((bin_id, weight));
+    }
+    let total_weight = ask_side_weights
+        .iter()
+        .try_fold(0u128, |total, (_, weight)| total.checked_add(*weight))
+        .context("MathOverflow")?;
+
+    for (bin_id, weight) in ask_side_weights {
+        let amount_x = mul_div(amount_x.into(), weight, total_weight, Rounding::Down)
+            .context("MathOverflow")?;
+        bin_amounts.push(BinAmount {
+            bin_id,
+            amount_x: amount_x.try_into().context("MathOverflow")?,
+            amount_y: 0,
+        });
+    }
+
+    Ok(bin_amounts)
+}
+
+/// `add_liquidity_by_strategy` deposit of `amount_x` and `amount_y` to the bins
+/// `min_bin_id..=max_bin_id`, shaped by `strategy`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrategyDeposit {
+    pub strategy: LiquidityStrategy,
+    pub active_id: i32,
+    pub min_bin_id: i32,
+    pub max_bin_id: i32,
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub favor_x_in_active_id: bool,
+    /// Expected amounts deposited to each bin, ordered by bin id
+    pub bin_amounts: Vec<BinAmount>,
+}
+
+impl StrategyDeposit {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        strategy: LiquidityStrategy,
+        active_id: i32,
+        bin_step: u16,
+        min_bin_id: i32,
+        max_bin_id: i32,
+        amount_x: u64,
+        amount_y: u64,
+        favor_x_in_active_id: bool,
+    ) -> Result<Self> {
+        ensure!(min_bin_id <= max_bin_id, "Invalid bin range");
+
+        let bin_amounts = get_strategy_amounts_into_bins(
+            strategy,
+            active_id,
+            bin_step,
+            min_bin_id,
+            max_bin_id,
+            amount_x,
+            amount_y,
+            favor_x_in_active_id,
+        )?;
+
+        Ok(Self {
+            strategy,
+            active_id,
+            min_bin_id,
+            max_bin_id,
+            amount_x,
+            amount_y,
+            favor_x_in_active_id,
+            bin_amounts,
+        })
+    }
+
+    pub fn get_total_amounts(&self) -> (u64, u64) {
+        let amount_x = self.bin_amounts.iter().map(|bin| bin.amount_x).sum();
+        let amount_y = self.bin_amounts.iter().map(|bin| bin.amount_y).sum();
+        (amount_x, amount_y)
+    }
+
+    /// Share, in bps, of the deposit amounts going to each bin, for `add_liquidity`. Rounded down.
+    pub fn get_bin_liquidity_distribution(&self) -> Vec<BinLiquidityDistribution> {
+        let (total_amount_x, total_amount_y) = self.get_total_amounts();
+        let to_bps = |amount: u64, total_amount: u64| -> u16 {
+            if total_amount == 0 {
+                0
+            } else {
+                (u128::from(amount) * BASIS_POINT_MAX as u128 / u128::from(total_amount)) as u16
+            }
+        };
+
+        self.bin_amounts
+            .iter()
+            .map(|bin| BinLiquidityDistribution {
+                bin_id: bin.bin_id,
+                distribution_x: to_bps(bin.amount_x, total_amount_x),
+                distribution_y: to_bps(bin.amount_y, total_amount_y),
+            })
+            .collect()
+    }
+
+    /// Parameters of `add_liquidity_by_strategy`.
+    pub fn get_liquidity_parameter_by_strategy(
+        &self,
+        max_active_bin_slippage: i32,
+    ) -> LiquidityParameterByStrategy {
+        let mut parameteres = [0u8; 64];
+        parameteres[0] = self.favor_x_in_active_id.into();
+
+        LiquidityParameterByStrategy {
+            amount_x: self.amount_x,
+            amount_y: self.amount_y,
+            active_id: self.active_id,
+            max_active_bin_slippage,
+            strategy_parameters: StrategyParameters {
+                min_bin_id: self.min_bin_id,
+                max_bin_id: self.max_bin_id,
+                strategy_type: self.strategy.to_strategy_type(),
+                parameteres,
+            },
+        }
+    }
+}
@@ -0,0 +1,400 @@
+use crate::*;
+use core::result::Result::Ok;
+use solana_sdk::{account::Account, clock::Clock, pubkey::Pubkey, sysvar};
+use std::collections::HashMap;
+
+/// State of a pair, loaded at the same point in time, needed to quote swaps against it.
+#[derive(Debug, Clone)]
+pub struct PoolSnapshot {
+    pub lb_pair_pubkey: Pubkey,
+    pub lb_pair: LbPair,
+    pub bin_arrays: HashMap<Pubkey, BinArray>,
+    pub bitmap_extension: Option<BinArrayBitmapExtension>,
+    pub clock: Clock,
+    pub mint_x_account: Account,
+    pub mint_y_account: Account,
+}
+
+/// Fetch the accounts that change with every swap, except the bin arrays.
+#[cfg(feature = "rpc")]
+async fn fetch_pair_state_accounts<P: AccountProvider + ?Sized>(
+    account_provider: &P,
+    lb_pair_pubkey: Pubkey,
+) -> Result<(Account, Option<Account>, Account)> {
+    let (bitmap_extension_key, _bump) = derive_bin_array_bitmap_extension(lb_pair_pubkey);
+
+    let accounts = account_provider
+        .get_multiple_accounts(&[
+            lb_pair_pubkey,
+            bitmap_extension_key,
+            solana_sdk::sysvar::clock::ID,
+        ])
+        .await?;
+
+    let [lb_pair_account, bitmap_extension_account, clock_account] =
+        <[Option<Account>; 3]>::try_from(accounts)
+            .ok()
+            .context("Failed to fetch pair accounts")?;
+
+    Ok((
+        lb_pair_account.context("Failed to fetch lb pair account")?,
+        bitmap_extension_account,
+        clock_account.context("Failed to fetch clock account")?,
+    ))
+}
+
+/// Bin arrays with liquidity that swaps may traverse, up to `bin_array_count` in each direction.
+#[cfg(feature = "rpc")]
+fn get_bin_array_pubkeys_for_snapshot(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    bin_array_count: u8,
+) -> Result<Vec<Pubkey>> {
+    let mut bin_array_pubkeys = vec![];
+
+    for swap_for_y in [true, false] {
+        for bin_array_pubkey in get_bin_array_pubkeys_for_swap(
+            lb_pair_pubkey,
+            lb_pair,
+            bitmap_extension,
+            swap_for_y,
+            bin_array_count,
+        )? {
+            // Active bin array is traversed in both directions
+            if !bin_array_pubkeys.contains(&bin_array_pubkey) {
+                bin_array_pubkeys.push(bin_array_pubkey);
+            }
+        }
+    }
+
+    Ok(bin_array_pubkeys)
+}
+
+#[cfg(feature = "rpc")]
+fn decode_bin_arrays(
+    bin_array_pubkeys: &[Pubkey],
+    bin_array_accounts: Vec<Option<Account>>,
+) -> Result<HashMap<Pubkey, BinArray>> {
+    let mut bin_arrays = HashMap::new();
+
+    for (&bin_array_pubkey, account) in bin_array_pubkeys.iter().zip(bin_array_accounts) {
+        if let Some(account) = account {
+            bin_arrays.insert(
+                bin_array_pubkey,
+                pod_read_unaligned_skip_disc(&account.data)?,
+            );
+        }
+    }
+
+    Ok(bin_arrays)
+}
+
+/// Bin arrays of the pair `lb_pair_pubkey` among accounts keyed by their address.
+pub fn get_bin_arrays_from_account_map(
+    accounts: &HashMap<Pubkey, Account>,
+    lb_pair_pubkey: Pubkey,
+) -> HashMap<Pubkey, BinArray> {
+    accounts
+        .iter()
+        .filter_map(|(key, account)| match DlmmAccount::decode(&account.data) {
+            Ok(DlmmAccount::BinArray(bin_array)) if bin_array.lb_pair == lb_pair_pubkey => {
+                Some((*key, bin_array))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Bin arrays of the pair `lb_pair_pubkey` among accounts keyed by their address, keyed by their
+/// index.
+pub fn get_bin_array_map_from_account_map(
+    accounts: &HashMap<Pubkey, Account>,
+    lb_pair_pubkey: Pubkey,
+) -> HashMap<i32, BinArray> {
+    get_bin_arrays_from_account_map(accounts, lb_pair_pubkey)
+        .into_values()
+        .map(|bin_array| (bin_array.index as i32, bin_array))
+        .collect()
+}
+
+impl PoolSnapshot {
+    /// Build a snapshot from raw accounts. `bin_array_accounts` are the bin arrays available to
+    /// the quotes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_accounts(
+        lb_pair_pubkey: Pubkey,
+        lb_pair_account: &Account,
+        bitmap_extension_account: Option<&Account>,
+        clock_account: &Account,
+        mint_x_account: Account,
+        mint_y_account: Account,
+        bin_array_accounts: &[(Pubkey, Account)],
+    ) -> Result<Self> {
+        let bin_arrays = bin_array_accounts
+            .iter()
+            .map(|(key, account)| Ok((*key, pod_read_unaligned_skip_disc(&account.data)?)))
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        Ok(Self {
+            lb_pair_pubkey,
+            lb_pair: pod_read_unaligned_skip_disc(&lb_pair_account.data)?,
+            bin_arrays,
+            bitmap_extension: bitmap_extension_account
+                .map(|account| pod_read_unaligned_skip_disc(&account.data))
+                .transpose()?,
+            clock: bincode::deserialize(clock_account.data.as_ref())?,
+            mint_x_account,
+            mint_y_account,
+        })
+    }
+
+    /// Build a snapshot from accounts keyed by their address: the pair, the clock sysvar, both
+    /// mints, the bin arrays of the pair and the bitmap extension when the pair has one.
+    pub fn from_account_map(
+        lb_pair_pubkey: Pubkey,
+        accounts: &HashMap<Pubkey, Account>,
+    ) -> Result<Self> {
+        let get_account = |key: &Pubkey, name: &str| {
+            accounts
+                .get(key)
+                .with_context(|| format!("Missing {name} account {key}"))
+        };
+
+        let lb_pair: LbPair =
+            pod_read_unaligned_skip_disc(&get_account(&lb_pair_pubkey, "lb pair")?.data)?;
+        let (bitmap_extension_key, _bump) = derive_bin_array_bitmap_extension(lb_pair_pubkey);
+
+        Ok(Self {
+            lb_pair_pubkey,
+            bin_arrays: get_bin_arrays_from_account_map(accounts, lb_pair_pubkey),
+            bitmap_extension: accounts
+                .get(&bitmap_extension_key)
+                .map(|account| pod_read_unaligned_skip_disc(&account.data))
+                .transpose()?,
+            clock: bincode::deserialize(get_account(&sysvar::clock::ID, "clock")?.data.as_ref())?,
+            mint_x_account: get_account(&lb_pair.token_x_mint, "token x mint")?.clone(),
+            mint_y_account: get_account(&lb_pair.token_y_mint, "token y mint")?.clone(),
+            lb_pair,
+        })
+    }
+
+    /// Load the snapshot of a pair, with up to `bin_array_count` bin arrays with liquidity in each
+    /// swap direction.
+    #[cfg(feature = "rpc")]
+    pub async fn load<P: AccountProvider + ?Sized>(
+        account_provider: &P,
+        lb_pair_pubkey: Pubkey,
+        bin_array_count: u8,
+    ) -> Result<Self> {
+        let (lb_pair_account, bitmap_extension_account, clock_account) =
+            fetch_pair_state_accounts(account_provider, lb_pair_pubkey).await?;
+
+        let lb_pair: LbPair = pod_read_unaligned_skip_disc(&lb_pair_account.data)?;
+        let bitmap_extension: Option<BinArrayBitmapExtension> = bitmap_extension_account
+            .as_ref()
+            .map(|account| pod_read_unaligned_skip_disc(&account.data))
+            .transpose()?;
+
+        let bin_array_pubkeys = get_bin_array_pubkeys_for_snapshot(
+            lb_pair_pubkey,
+            &lb_pair,
+            bitmap_extension.as_ref(),
+            bin_array_count,
+        )?;
+
+        let accounts_to_fetch = [
+            vec![lb_pair.token_x_mint, lb_pair.token_y_mint],
+            bin_array_pubkeys.clone(),
+        ]
+        .concat();
+
+        let mut accounts = account_provider
+            .get_multiple_accounts(&accounts_to_fetch)
+            .await?;
+        let bin_array_accounts = accounts.split_off(2);
+
+        let mint_y_account = accounts
+            .pop()
+            .flatten()
+            .context("Failed to fetch mint account")?;
+        let mint_x_account = accounts
+            .pop()
+            .flatten()
+            .context("Failed to fetch mint account")?;
+
+        Ok(Self {
+            lb_pair_pubkey,
+            lb_pair,
+            bin_arrays: decode_bin_arrays(&bin_array_pubkeys, bin_array_accounts)?,
+            bitmap_extension,
+            clock: bincode::deserialize(clock_account.data.as_ref())?,
+            mint_x_account,
+            mint_y_account,
+        })
+    }
+
+    /// Replace the accounts of the snapshot found in `accounts`, keyed by their address: the
+    /// pair, the bitmap extension, the clock, the mints and the bin arrays of the pair. Only the
+    /// accounts passed in are decoded and the others are kept, so account subscriptions can pass
+    /// the accounts that changed. The snapshot is left untouched on error.
+    pub fn apply_account_updates(&mut self, accounts: &HashMap<Pubkey, Account>) -> Result<()> {
+        let (bitmap_extension_key, _bump) = derive_bin_array_bitmap_extension(self.lb_pair_pubkey);
+
+        let lb_pair: Option<LbPair> = accounts
+            .get(&self.lb_pair_pubkey)
+            .map(|account| pod_read_unaligned_skip_disc(&account.data))
+            .transpose()?;
+        let bitmap_extension: Option<BinArrayBitmapExtension> = accounts
+            .get(&bitmap_extension_key)
+            .map(|account| pod_read_unaligned_skip_disc(&account.data))
+            .transpose()?;
+        let clock: Option<Clock> = accounts
+            .get(&sysvar::clock::ID)
+            .map(|account| bincode::deserialize(account.data.as_ref()))
+            .transpose()?;
+
+        if let Some(lb_pair) = lb_pair {
+            self.lb_pair = lb_pair;
+        }
+        if bitmap_extension.is_some() {
+            self.bitmap_extension = bitmap_extension;
+        }
+        if let Some(clock) = clock {
+            self.clock = clock;
+        }
+        if let Some(mint_x_account) = accounts.get(&self.lb_pair.token_x_mint) {
+            self.mint_x_account = mint_x_account.clone();
+        }
+        if let Some(mint_y_account) = accounts.get(&self.lb_pair.token_y_mint) {
+            self.mint_y_account = mint_y_account.clone();
+        }
+        self.bin_arrays.extend(get_bin_arrays_from_account_map(
+            accounts,
+            self.lb_pair_pubkey,
+        ));
+
+        Ok(())
+    }
+
+    /// Refetch the pair, the bitmap extension and the clock, and move the bin arrays to the new
+    /// active bin. Only the bin arrays not in the snapshot yet are fetched, the others are kept
+    /// as they are, see [`Self::apply_account_updates`] to update them. Bin arrays out of range
+    /// are dropped. The snapshot is left untouched on error.
+    #[cfg(feature = "rpc")]
+    pub async fn refresh<P: AccountProvider + ?Sized>(
+        &mut self,
+        account_provider: &P,
+        bin_array_count: u8,
+    ) -> Result<()> {
+        let (lb_pair_account, bitmap_extension_account, clock_account) =
+            fetch_pair_state_accounts(account_provider, self.lb_pair_pubkey).await?;
+
+        let lb_pair: LbPair = pod_read_unaligned_skip_disc(&lb_pair_account.data)?;
+        let bitmap_extension: Option<BinArrayBitmapExtension> = bitmap_extension_account
+            .as_ref()
+            .map(|account| pod_read_unaligned_skip_disc(&account.data))
+            .transpose()?;
+        let clock: Clock = bincode::deserialize(clock_account.data.as_ref())?;
+
+        let bin_array_pubkeys = get_bin_array_pubkeys_for_snapshot(
+            self.lb_pair_pubkey,
+            &lb_pair,
+            bitmap_extension.as_ref(),
+            bin_array_count,
+        )?;
+        let new_bin_array_pubkeys = bin_array_pubkeys
+            .iter()
+            .filter(|bin_array_pubkey| !self.bin_arrays.contains_key(bin_array_pubkey))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let mut bin_arrays = if new_bin_array_pubkeys.is_empty() {
+            HashMap::new()
+        } else {
+            let new_bin_array_accounts = account_provider
+                .get_multiple_accounts(&new_bin_array_pubkeys)
+                .await?;
+            decode_bin_arrays(&new_bin_array_pubkeys, new_bin_array_accounts)?
+        };
+        for bin_array_pubkey in bin_array_pubkeys {
+            if let Some(bin_array) = self.bin_arrays.get(&bin_array_pubkey) {
+                bin_arrays.insert(bin_array_pubkey, *bin_array);
+            }
+        }
+
+        self.bin_arrays = bin_arrays;
+        self.lb_pair = lb_pair;
+        self.bitmap_extension = bitmap_extension;
+        self.clock = clock;
+
+        Ok(())
+    }
+
+    /// See [`quote_exact_in`].
+    pub fn quote_exact_in(
+        &self,
+        amount_in: u64,
+        swap_for_y: bool,
+        options: QuoteOptions,
+    ) -> Result<SwapExactInQuote> {
+        quote_exact_in(
+            self.lb_pair_pubkey,
+            &self.lb_pair,
+            amount_in,
+            swap_for_y,
+            options,
+            self.bin_arrays.clone(),
+            self.bitmap_extension.as_ref(),
+            &self.clock,
+            &self.mint_x_account,
+            &self.mint_y_account,
+        )
+    }
+
+    /// See [`quote_exact_out`].
+    pub fn quote_exact_out(
+        &self,
+        amount_out: u64,
+        swap_for_y: bool,
+        options: QuoteOptions,
+    ) -> Result<SwapExactOutQuote> {
+        quote_exact_out(
+            self.lb_pair_pubkey,
+            &self.lb_pair,
+            amount_out,
+            swap_for_y,
+            options,
+            self.bin_arrays.clone(),
+            self.bitmap_extension.as_ref(),
+            &self.clock,
+            &self.mint_x_account,
+            &self.mint_y_account,
+        )
+    }
+
+    /// See [`quote_exact_in_with_price_impact`].
+    pub fn quote_exact_in_with_price_impact(
+        &self,
+        amount_in: u64,
+        swap_for_y: bool,
+        active_id: Option<i32>,
+        max_price_impact_bps: u16,
+        options: QuoteOptions,
+    ) -> Result<SwapWithPriceImpactQuote> {
+        quote_exact_in_with_price_impact(
+            self.lb_pair_pubkey,
+            &self.lb_pair,
+            amount_in,
+            swap_for_y,
+            active_id,
+            max_price_impact_bps,
+            options,
+            self.bin_arrays.clone(),
+            self.bitmap_extension.as_ref(),
+            &self.clock,
+            &self.mint_x_account,
+            &self.mint_y_account,
+        )
+    }
+}
@@ -1,23 +1,353 @@
 use crate::*;
-use anchor_client::solana_sdk::pubkey::Pubkey;
 use core::result::Result::Ok;
-use solana_sdk::{account::Account, clock::Clock};
+use solana_sdk::{account::Account, clock::Clock, pubkey::Pubkey};
 use std::collections::HashMap;
 
+/// Options shared by the swap quotes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuoteOptions {
+    /// Host share of the protocol fee. Pass `Some(HOST_FEE_BPS)` when the swap is sent with a
+    /// host fee account.
+    pub host_fee_bps: Option<u16>,
+    /// Swapping wallet. It lets the pair `pre_activation_swap_address` get quotes during the
+    /// pre-activation window of permission and customizable permissionless pairs.
+    pub user: Option<Pubkey>,
+}
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SwapExactInQuote {
     pub amount_out: u64,
     pub fee: u64,
+    /// Protocol fee, includes host fee
     pub protocol_fee: u64,
+    pub host_fee: u64,
+    pub protocol_fee_after_host_fee: u64,
+    pub prices: SwapQuotePrices,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SwapExactOutQuote {
     pub amount_in: u64,
     pub fee: u64,
+    /// Protocol fee, includes host fee
+    pub protocol_fee: u64,
+    pub host_fee: u64,
+    pub protocol_fee_after_host_fee: u64,
+    pub prices: SwapQuotePrices,
+}
+
+/// Price data of a swap quote. Prices are in Q64.64, expressed as token Y per token X.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapQuotePrices {
+    /// Active bin id before the swap
+    pub start_active_id: i32,
+    /// Price of the active bin before the swap
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub start_price: u128,
+    /// Active bin id after the swap
+    pub end_active_id: i32,
+    /// Price of the active bin after the swap
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub end_price: u128,
+    /// Volume weighted price of the liquidity consumed, excluding trading fee
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub execution_price: u128,
+    /// Price obtained by the swapper, including trading fee and excluding token 2022 transfer fee
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub effective_price: u128,
+    /// Deviation of the execution price from the start price, in bps
+    pub price_impact_bps: u64,
+}
+
+impl SwapQuotePrices {
+    fn new(
+        start_active_id: i32,
+        bin_step: u16,
+        swap_for_y: bool,
+        fee_on_input: bool,
+        bin_fills: &[BinFill],
+    ) -> Result<Self> {
+        let start_price = get_price_from_id(start_active_id, bin_step)?;
+
+        let Some(last_bin_fill) = bin_fills.last() else {
+            return Ok(Self {
+                start_active_id,
+                start_price,
+                end_active_id: start_active_id,
+                end_price: start_price,
+                execution_price: start_price,
+                effective_price: start_price,
+                price_impact_bps: 0,
+            });
+        };
+
+        let end_active_id = last_bin_fill.bin_id;
+        let end_price = get_price_from_id(end_active_id, bin_step)?;
+
+        let mut excluded_fee_amount_in: u128 = 0;
+        let mut included_fee_amount_out: u128 = 0;
+        let mut amount_in: u128 = 0;
+        let mut amount_out: u128 = 0;
+
+        for bin_fill in bin_fills {
+            let fee_in = if fee_on_input { bin_fill.fee } else { 0 };
+            excluded_fee_amount_in = excluded_fee_amount_in
+                .checked_add(
+                    bin_fill
+                        .amount_in
+                        .checked_sub(fee_in)
+                        .context("MathOverflow")?
+                        .into(),
+                )
+                .context("MathOverflow")?;
+            included_fee_amount_out = included_fee_amount_out
+                .checked_add(bin_fill.mm_amount_out.into())
+                .and_then(|amount| amount.checked_add(bin_fill.limit_order_amount_out().into()))
+                .context("MathOverflow")?;
+            amount_in = amount_in
+                .checked_add(bin_fill.amount_in.into())
+                .context("MathOverflow")?;
+            amount_out = amount_out
+                .checked_add(bin_fill.amount_out.into())
+                .context("MathOverflow")?;
+        }
+
+        let execution_price =
+            get_price_from_amounts(excluded_fee_amount_in, included_fee_amount_out, swap_for_y)?
+                .unwrap_or(start_price);
+
+        let effective_price =
+            get_price_from_amounts(amount_in, amount_out, swap_for_y)?.unwrap_or(start_price);
+
+        let price_impact_bps = mul_div(
+            execution_price.abs_diff(start_price),
+            BASIS_POINT_MAX as u128,
+            start_price,
+            Rounding::Up,
+        )
+        .context("MathOverflow")?
+        .try_into()
+        .context("MathOverflow")?;
+
+        Ok(Self {
+            start_active_id,
+            start_price,
+            end_active_id,
+            end_price,
+            execution_price,
+            effective_price,
+            price_impact_bps,
+        })
+    }
+}
+
+/// Q64.64 price (token Y per token X) implied by swapping `amount_in` for `amount_out`.
+/// Returns `None` when either amount is zero.
+fn get_price_from_amounts(
+    amount_in: u128,
+    amount_out: u128,
+    swap_for_y: bool,
+) -> Result<Option<u128>> {
+    if amount_in == 0 || amount_out == 0 {
+        return Ok(None);
+    }
+
+    let (amount_x, amount_y) = if swap_for_y {
+        (amount_in, amount_out)
+    } else {
+        (amount_out, amount_in)
+    };
+
+    Ok(Some(
+        shl_div(amount_y, amount_x, SCALE_OFFSET, Rounding::Down).context("MathOverflow")?,
+    ))
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapWithPriceImpactQuote {
+    /// Amount of input consumed, up to the bin where the price impact is reached
+    pub amount_in: u64,
+    /// Amount of input left unswapped because of the price impact
+    pub amount_left: u64,
+    pub amount_out: u64,
+    /// Minimum amount out enforced by the program for the consumed amount of input
+    pub min_amount_out: u64,
+    pub fee: u64,
+    /// Protocol fee, includes host fee
+    pub protocol_fee: u64,
+    pub host_fee: u64,
+    pub protocol_fee_after_host_fee: u64,
+    pub prices: SwapQuotePrices,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapExactInToBinQuote {
+    /// Swap direction required to move the active bin to the target bin
+    pub swap_for_y: bool,
+    /// Minimum amount in for the swap to end at the target bin. Includes trading fee and token 2022
+    /// transfer fee.
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    /// Protocol fee, includes host fee
+    pub protocol_fee: u64,
+    pub host_fee: u64,
+    pub protocol_fee_after_host_fee: u64,
+    pub prices: SwapQuotePrices,
+}
+
+/// A level of a depth curve. Amounts are cumulative, from the active bin up to and including
+/// `bin_id`, as if every bin along the way is drained.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepthLevel {
+    pub bin_id: i32,
+    /// Bin price in Q64.64
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub price: u128,
+    /// Amount in required to drain the bins. Includes trading fee and token 2022 transfer fee.
+    pub amount_in: u64,
+    /// Amount out received. Excludes trading fee and token 2022 transfer fee.
+    pub amount_out: u64,
+    pub fee: u64,
+    /// Protocol fee, includes host fee
+    pub protocol_fee: u64,
+    pub host_fee: u64,
+    pub protocol_fee_after_host_fee: u64,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapDepthQuote {
+    /// Depth of swapping X for Y, walking the bins to the left of the active bin
+    pub x_to_y: Vec<DepthLevel>,
+    /// Depth of swapping Y for X, walking the bins to the right of the active bin
+    pub y_to_x: Vec<DepthLevel>,
+}
+
+/// Fill breakdown of a single bin traversed by a swap quote.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinFill {
+    pub bin_id: i32,
+    /// Bin price in Q64.64
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub price: u128,
+    /// Amount of input consumed by the bin (includes trading fee when fee_on_input)
+    pub amount_in: u64,
+    /// Amount of output produced by the bin (excludes trading fee when fee_on_output)
+    pub amount_out: u64,
+    /// Amount of MM liquidity consumed
+    pub mm_amount_out: u64,
+    /// Amount of processed limit order liquidity consumed
+    pub processed_order_amount_out: u64,
+    /// Amount of open limit order liquidity consumed
+    pub open_order_amount_out: u64,
+    /// Trading fee charged in the bin, includes protocol fee
+    pub fee: u64,
+    /// Protocol portion of the trading fee, includes host fee
     pub protocol_fee: u64,
+    /// Host portion of the protocol fee
+    pub host_fee: u64,
+}
+
+impl BinFill {
+    fn new(bin_id: i32, price: u128, result: &BinQuoteResult) -> Self {
+        Self {
+            bin_id,
+            price,
+            amount_in: result.amount_in,
+            amount_out: result.amount_out,
+            mm_amount_out: result.mm_amount_out,
+            processed_order_amount_out: result.processed_order_amount_out,
+            open_order_amount_out: result.open_order_amount_out,
+            fee: result.fee,
+            protocol_fee: result.protocol_fee,
+            host_fee: result.host_fee,
+        }
+    }
+
+    /// Amount of limit order liquidity (processed + open) consumed
+    pub fn limit_order_amount_out(&self) -> u64 {
+        self.processed_order_amount_out
+            .saturating_add(self.open_order_amount_out)
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapExactInDetailedQuote {
+    pub quote: SwapExactInQuote,
+    /// Bins traversed by the swap, in traversal order. Amounts are before token 2022 transfer fee.
+    pub bin_fills: Vec<BinFill>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapExactOutDetailedQuote {
+    pub quote: SwapExactOutQuote,
+    /// Bins traversed by the swap, in traversal order. Amounts are before token 2022 transfer fee.
+    pub bin_fills: Vec<BinFill>,
+}
+
+/// Error returned by the quote functions when the swap reaches a bin array missing from
+/// `bin_arrays`. Downcast the returned `anyhow::Error` to get the bin arrays to fetch.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MissingBinArraysError {
+    /// Bin arrays with liquidity not found in `bin_arrays`, in traversal order. The first one is
+    /// where the quote stopped, the following ones are only needed if the swap goes past it.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub bin_array_pubkeys: Vec<Pubkey>,
+}
+
+impl std::fmt::Display for MissingBinArraysError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Bin arrays not found: {:?}", self.bin_array_pubkeys)
+    }
+}
+
+impl std::error::Error for MissingBinArraysError {}
+
+/// Error returned by the quote functions when the pool runs out of liquidity before the swap is
+/// filled.
+#[derive(Debug, Clone)]
+pub struct PoolOutOfLiquidityError;
+
+impl std::fmt::Display for PoolOutOfLiquidityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Pool out of liquidity")
+    }
 }
 
+impl std::error::Error for PoolOutOfLiquidityError {}
+
 /// Internal fill result for a single liquidity layer within a bin.
 struct FillResult {
     amount_in: u64,
@@ -31,6 +361,9 @@ struct ExactInFillResult {
     amount_left: u64,
     out_amount: u64,
     mm_amount_in: u64,
+    mm_out_amount: u64,
+    processed_order_out_amount: u64,
+    open_order_out_amount: u64,
 }
 
 /// Calculate how much of `amount` can be filled against `max_amount_out` of liquidity at `price`.
@@ -84,11 +417,16 @@ fn get_exact_in_fill_amount_result(
             amount_left: mm_fill.amount_left,
             out_amount: mm_fill.out_amount,
             mm_amount_in: mm_fill.amount_in,
+            mm_out_amount: mm_fill.out_amount,
+            processed_order_out_amount: 0,
+            open_order_out_amount: 0,
         });
     }
 
     let mut total_amount_in = mm_fill.amount_in;
     let mut total_amount_out = mm_fill.out_amount;
+    let mut processed_order_out_amount = 0;
+    let mut open_order_out_amount = 0;
     let amount_left_after_mm = mm_fill.amount_left;
 
     if amount_left_after_mm > 0 {
@@ -108,6 +446,7 @@ fn get_exact_in_fill_amount_result(
         total_amount_out = total_amount_out
             .checked_add(processed_fill.out_amount)
             .context("MathOverflow")?;
+        processed_order_out_amount = processed_fill.out_amount;
 
         // Fill open orders next
         if processed_fill.amount_left > 0 {
@@ -123,6 +462,7 @@ fn get_exact_in_fill_amount_result(
             total_amount_out = total_amount_out
                 .checked_add(open_fill.out_amount)
                 .context("MathOverflow")?;
+            open_order_out_amount = open_fill.out_amount;
         }
     }
 
@@ -133,18 +473,23 @@ fn get_exact_in_fill_amount_result(
             .context("MathOverflow")?,
         out_amount: total_amount_out,
         mm_amount_in: mm_fill.amount_in,
+        mm_out_amount: mm_fill.out_amount,
+        processed_order_out_amount,
+        open_order_out_amount,
     })
 }
 
-/// Split trading fee between user (LP) fee and protocol fee, accounting for limit order fee share.
+/// Split trading fee between user (LP) fee, protocol fee and host fee, accounting for limit order
+/// fee share. The protocol fee includes the host fee.
 fn split_fee(
     trading_fee: u64,
     protocol_share: u16,
     mm_amount_in: u64,
     total_amount_in: u64,
-) -> Result<(u64, u64)> {
+    host_fee_bps: Option<u16>,
+) -> Result<(u64, u64, u64)> {
     if total_amount_in == 0 || trading_fee == 0 {
-        return Ok((0, 0));
+        return Ok((0, 0, 0));
     }
 
     // mm_fee = ceil(trading_fee * mm_amount_in / total_amount_in)
@@ -191,7 +536,37 @@ fn split_fee(
         .checked_sub(total_protocol_fee)
         .context("MathOverflow")?;
 
-    Ok((total_user_fee, total_protocol_fee))
+    // Host fee is a cut of the protocol share of both the MM fee and the limit order fee. Like the
+    // program, the limit order part is taken from the protocol share of the whole limit order fee
+    // rather than from `lo_protocol_fee`, which matches the `host_fee` of the program `Swap` event
+    let host_fee = match host_fee_bps {
+        Some(host_fee_bps) => {
+            let lo_protocol_share_fee: u64 = u128::from(total_lo_fee)
+                .checked_mul(protocol_share.into())
+                .context("MathOverflow")?
+                .checked_div(BASIS_POINT_MAX as u128)
+                .context("MathOverflow")?
+                .try_into()
+                .context("MathOverflow")?;
+
+            get_host_fee(mm_protocol_fee, host_fee_bps)?
+                .checked_add(get_host_fee(lo_protocol_share_fee, host_fee_bps)?)
+                .context("MathOverflow")?
+        }
+        None => 0,
+    };
+
+    Ok((total_user_fee, total_protocol_fee, host_fee))
+}
+
+fn get_host_fee(protocol_fee: u64, host_fee_bps: u16) -> Result<u64> {
+    u128::from(protocol_fee)
+        .checked_mul(host_fee_bps.into())
+        .context("MathOverflow")?
+        .checked_div(BASIS_POINT_MAX as u128)
+        .context("MathOverflow")?
+        .try_into()
+        .context("MathOverflow")
 }
 
 /// Per-bin exact-in quote with limit order and fee mode support.
@@ -202,6 +577,7 @@ fn swap_exact_in_quote_at_bin(
     swap_for_y: bool,
     support_limit_order: bool,
     fee_on_input: bool,
+    host_fee_bps: Option<u16>,
 ) -> Result<BinQuoteResult> {
     let mut trading_fee: u64 = 0;
     let mut excluded_fee_amount_in = in_amount;
@@ -247,11 +623,12 @@ fn swap_exact_in_quote_at_bin(
         excluded_fee_amount_out = out_amount.checked_sub(fee).context("MathOverflow")?;
     }
 
-    let (_user_fee, protocol_fee) = split_fee(
+    let (_user_fee, protocol_fee, host_fee) = split_fee(
         trading_fee,
         lb_pair.parameters.protocol_share,
         fill_result.mm_amount_in,
         fill_result.amount_in,
+        host_fee_bps,
     )?;
 
     Ok(BinQuoteResult {
@@ -259,6 +636,11 @@ fn swap_exact_in_quote_at_bin(
         amount_out: excluded_fee_amount_out,
         fee: trading_fee,
         protocol_fee,
+        host_fee,
+        mm_amount_in: fill_result.mm_amount_in,
+        mm_amount_out: fill_result.mm_out_amount,
+        processed_order_amount_out: fill_result.processed_order_out_amount,
+        open_order_amount_out: fill_result.open_order_out_amount,
     })
 }
 
@@ -319,6 +701,7 @@ fn swap_exact_out_quote_at_bin(
     swap_for_y: bool,
     support_limit_order: bool,
     fee_on_input: bool,
+    host_fee_bps: Option<u16>,
 ) -> Result<BinQuoteResult> {
     let mut included_fee_amount_out = out_amount;
 
@@ -338,6 +721,7 @@ fn swap_exact_out_quote_at_bin(
             swap_for_y,
             support_limit_order,
             fee_on_input,
+            host_fee_bps,
         );
     }
 
@@ -361,6 +745,7 @@ fn swap_exact_out_quote_at_bin(
         swap_for_y,
         support_limit_order,
         fee_on_input,
+        host_fee_bps,
     )?;
 
     // Delta between quoted output and requested output goes to protocol (rounding)
@@ -384,14 +769,29 @@ fn swap_exact_out_quote_at_bin(
 
 fn validate_swap_activation(
     lb_pair: &LbPair,
+    user: Option<Pubkey>,
     current_timestamp: u64,
     current_slot: u64,
 ) -> Result<()> {
     ensure!(
-        lb_pair.status()?.eq(&PairStatus::Enabled),
+        is_swap_enabled(lb_pair, user, current_timestamp, current_slot)?,
         "Pair is disabled"
     );
 
+    Ok(())
+}
+
+/// Whether `user` can swap against the pair, given its status and activation point.
+pub(crate) fn is_swap_enabled(
+    lb_pair: &LbPair,
+    user: Option<Pubkey>,
+    current_timestamp: u64,
+    current_slot: u64,
+) -> Result<bool> {
+    if !lb_pair.status()?.eq(&PairStatus::Enabled) {
+        return Ok(false);
+    }
+
     let pair_type = lb_pair.pair_type()?;
     if pair_type.eq(&PairType::Permission) || pair_type.eq(&PairType::CustomizablePermissionless) {
         let activation_type = lb_pair.activation_type()?;
@@ -400,13 +800,17 @@ fn validate_swap_activation(
             ActivationType::Timestamp => current_timestamp,
         };
 
-        ensure!(
-            current_point >= lb_pair.activation_point,
-            "Pair is disabled"
-        );
+        // The pre-activation swap address can swap from the pre-activation start point
+        let activation_point = if user == Some(lb_pair.pre_activation_swap_address) {
+            lb_pair.get_pre_activation_start_point()
+        } else {
+            lb_pair.activation_point
+        };
+
+        return Ok(current_point >= activation_point);
     }
 
-    Ok(())
+    Ok(true)
 }
 
 fn shift_active_bin_if_empty_gap(
@@ -431,33 +835,152 @@ fn shift_active_bin_if_empty_gap(
     Ok(())
 }
 
+/// Get the bin array the swap continues in, with its pubkey. Fails with [`MissingBinArraysError`] when it is not
+/// in `bin_arrays`.
+fn get_active_bin_array(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    swap_for_y: bool,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+) -> Result<(Pubkey, BinArray)> {
+    let active_bin_array_pubkey =
+        get_bin_array_pubkeys_for_swap(lb_pair_pubkey, lb_pair, bitmap_extension, swap_for_y, 1)?
+            .pop()
+            .ok_or(PoolOutOfLiquidityError)?;
+
+    if let Some(active_bin_array) = bin_arrays.get(&active_bin_array_pubkey) {
+        return Ok((active_bin_array_pubkey, *active_bin_array));
+    }
+
+    Err(get_missing_bin_arrays_error(
+        lb_pair_pubkey,
+        lb_pair,
+        bitmap_extension,
+        swap_for_y,
+        bin_arrays,
+    )?
+    .into())
+}
+
+/// Get the bin arrays the swap goes through which are not in `bin_arrays`.
+fn get_missing_bin_arrays_error(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    swap_for_y: bool,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+) -> Result<MissingBinArraysError> {
+    let bin_array_pubkeys = get_bin_array_pubkeys_for_swap(
+        lb_pair_pubkey,
+        lb_pair,
+        bitmap_extension,
+        swap_for_y,
+        u8::MAX,
+    )?
+    .into_iter()
+    .filter(|pubkey| !bin_arrays.contains_key(pubkey))
+    .collect();
+
+    Ok(MissingBinArraysError { bin_array_pubkeys })
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn quote_exact_out(
     lb_pair_pubkey: Pubkey,
     lb_pair: &LbPair,
-    mut amount_out: u64,
+    amount_out: u64,
     swap_for_y: bool,
+    options: QuoteOptions,
     bin_arrays: HashMap<Pubkey, BinArray>,
     bitmap_extension: Option<&BinArrayBitmapExtension>,
     clock: &Clock,
     mint_x_account: &Account,
     mint_y_account: &Account,
 ) -> Result<SwapExactOutQuote> {
+    let detailed_quote = quote_exact_out_detailed(
+        lb_pair_pubkey,
+        lb_pair,
+        amount_out,
+        swap_for_y,
+        options,
+        bin_arrays,
+        bitmap_extension,
+        clock,
+        mint_x_account,
+        mint_y_account,
+    )?;
+
+    Ok(detailed_quote.quote)
+}
+
+/// Same as [`quote_exact_out`], but also returns the fill breakdown of every bin traversed.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_exact_out_detailed(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    amount_out: u64,
+    swap_for_y: bool,
+    options: QuoteOptions,
+    mut bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    clock: &Clock,
+    mint_x_account: &Account,
+    mint_y_account: &Account,
+) -> Result<SwapExactOutDetailedQuote> {
+    let mut lb_pair = *lb_pair;
+
+    simulate_swap_exact_out(
+        lb_pair_pubkey,
+        &mut lb_pair,
+        amount_out,
+        swap_for_y,
+        options,
+        &mut bin_arrays,
+        bitmap_extension,
+        clock,
+        mint_x_account,
+        mint_y_account,
+    )
+}
+
+/// Apply an exact out swap to `lb_pair` and `bin_arrays`, and return its quote.
+///
+/// On success, `lb_pair` and `bin_arrays` hold the post swap state, so swaps can be chained and
+/// variable fee compounds across them. They are left untouched on error. See
+/// [`simulate_swap_exact_in`] for the state that is simulated.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_swap_exact_out(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &mut LbPair,
+    mut amount_out: u64,
+    swap_for_y: bool,
+    options: QuoteOptions,
+    bin_arrays: &mut HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    clock: &Clock,
+    mint_x_account: &Account,
+    mint_y_account: &Account,
+) -> Result<SwapExactOutDetailedQuote> {
     let current_timestamp = clock.unix_timestamp as u64;
     let current_slot = clock.slot;
     let epoch = clock.epoch;
 
-    validate_swap_activation(lb_pair, current_timestamp, current_slot)?;
+    validate_swap_activation(lb_pair, options.user, current_timestamp, current_slot)?;
 
-    let mut lb_pair = *lb_pair;
-    lb_pair.update_references(current_timestamp as i64)?;
+    let start_active_id = lb_pair.active_id;
+    let mut swapped_lb_pair = *lb_pair;
+    swapped_lb_pair.update_references(current_timestamp as i64)?;
 
-    let support_limit_order = lb_pair.is_support_limit_order();
-    let fee_on_input = lb_pair.fee_on_input(swap_for_y);
+    let support_limit_order = swapped_lb_pair.is_support_limit_order();
+    let fee_on_input = swapped_lb_pair.fee_on_input(swap_for_y);
 
     let mut total_amount_in: u64 = 0;
     let mut total_fee: u64 = 0;
     let mut total_protocol_fee: u64 = 0;
+    let mut total_host_fee: u64 = 0;
+    let mut bin_fills = vec![];
+    let mut swapped_bin_arrays = HashMap::new();
 
     let (in_mint_account, out_mint_account) = if swap_for_y {
         (mint_x_account, mint_y_account)
@@ -469,44 +992,41 @@ pub fn quote_exact_out(
         calculate_transfer_fee_included_amount(out_mint_account, amount_out, epoch)?.amount;
 
     while amount_out > 0 {
-        let active_bin_array_pubkey = get_bin_array_pubkeys_for_swap(
+        let (active_bin_array_pubkey, mut active_bin_array) = get_active_bin_array(
             lb_pair_pubkey,
-            &lb_pair,
+            &swapped_lb_pair,
             bitmap_extension,
             swap_for_y,
-            1,
-        )?
-        .pop()
-        .context("Pool out of liquidity")?;
-
-        let mut active_bin_array = bin_arrays
-            .get(&active_bin_array_pubkey)
-            .cloned()
-            .context("Active bin array not found")?;
+            bin_arrays,
+        )?;
 
-        shift_active_bin_if_empty_gap(&mut lb_pair, &active_bin_array, swap_for_y)?;
+        shift_active_bin_if_empty_gap(&mut swapped_lb_pair, &active_bin_array, swap_for_y)?;
 
         loop {
-            if !active_bin_array.is_bin_id_within_range(lb_pair.active_id)? || amount_out == 0 {
+            if !active_bin_array.is_bin_id_within_range(swapped_lb_pair.active_id)?
+                || amount_out == 0
+            {
                 break;
             }
 
-            let active_bin = active_bin_array.get_bin_mut(lb_pair.active_id)?;
-            let _price = active_bin.get_or_store_bin_price(lb_pair.active_id, lb_pair.bin_step)?;
+            let active_bin = active_bin_array.get_bin_mut(swapped_lb_pair.active_id)?;
+            let price = active_bin
+                .get_or_store_bin_price(swapped_lb_pair.active_id, swapped_lb_pair.bin_step)?;
 
             let max_out =
                 active_bin.get_max_amount_out_with_limit_orders(swap_for_y, support_limit_order);
 
             if max_out > 0 {
-                lb_pair.update_volatility_accumulator()?;
+                swapped_lb_pair.update_volatility_accumulator()?;
 
                 let result = swap_exact_out_quote_at_bin(
                     active_bin,
-                    &lb_pair,
+                    &swapped_lb_pair,
                     amount_out,
                     swap_for_y,
                     support_limit_order,
                     fee_on_input,
+                    options.host_fee_bps,
                 )?;
 
                 if result.amount_out > 0 {
@@ -520,22 +1040,50 @@ pub fn quote_exact_out(
                     total_protocol_fee = total_protocol_fee
                         .checked_add(result.protocol_fee)
                         .context("MathOverflow")?;
+                    total_host_fee = total_host_fee
+                        .checked_add(result.host_fee)
+                        .context("MathOverflow")?;
+                    apply_bin_quote_result(active_bin, &result, swap_for_y)?;
+                    apply_protocol_fee(&mut swapped_lb_pair, &result, swap_for_y, fee_on_input)?;
+                    bin_fills.push(BinFill::new(swapped_lb_pair.active_id, price, &result));
                 }
             }
 
             if amount_out > 0 {
-                lb_pair.advance_active_bin(swap_for_y)?;
+                swapped_lb_pair.advance_active_bin(swap_for_y)?;
             }
         }
+
+        swapped_bin_arrays.insert(active_bin_array_pubkey, active_bin_array);
     }
 
     total_amount_in =
         calculate_transfer_fee_included_amount(in_mint_account, total_amount_in, epoch)?.amount;
 
-    Ok(SwapExactOutQuote {
-        amount_in: total_amount_in,
-        fee: total_fee,
-        protocol_fee: total_protocol_fee,
+    let prices = SwapQuotePrices::new(
+        start_active_id,
+        swapped_lb_pair.bin_step,
+        swap_for_y,
+        fee_on_input,
+        &bin_fills,
+    )?;
+
+    swapped_lb_pair.v_parameters.last_update_timestamp = current_timestamp as i64;
+    *lb_pair = swapped_lb_pair;
+    bin_arrays.extend(swapped_bin_arrays);
+
+    Ok(SwapExactOutDetailedQuote {
+        quote: SwapExactOutQuote {
+            amount_in: total_amount_in,
+            fee: total_fee,
+            protocol_fee: total_protocol_fee,
+            host_fee: total_host_fee,
+            protocol_fee_after_host_fee: total_protocol_fee
+                .checked_sub(total_host_fee)
+                .context("MathOverflow")?,
+            prices,
+        },
+        bin_fills,
     })
 }
 
@@ -545,27 +1093,91 @@ pub fn quote_exact_in(
     lb_pair: &LbPair,
     amount_in: u64,
     swap_for_y: bool,
+    options: QuoteOptions,
     bin_arrays: HashMap<Pubkey, BinArray>,
     bitmap_extension: Option<&BinArrayBitmapExtension>,
     clock: &Clock,
     mint_x_account: &Account,
     mint_y_account: &Account,
 ) -> Result<SwapExactInQuote> {
+    let detailed_quote = quote_exact_in_detailed(
+        lb_pair_pubkey,
+        lb_pair,
+        amount_in,
+        swap_for_y,
+        options,
+        bin_arrays,
+        bitmap_extension,
+        clock,
+        mint_x_account,
+        mint_y_account,
+    )?;
+
+    Ok(detailed_quote.quote)
+}
+
+/// Same as [`quote_exact_in`], but also returns the fill breakdown of every bin traversed.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_exact_in_detailed(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    amount_in: u64,
+    swap_for_y: bool,
+    options: QuoteOptions,
+    mut bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    clock: &Clock,
+    mint_x_account: &Account,
+    mint_y_account: &Account,
+) -> Result<SwapExactInDetailedQuote> {
+    let mut lb_pair = *lb_pair;
+
+    simulate_swap_exact_in(
+        lb_pair_pubkey,
+        &mut lb_pair,
+        amount_in,
+        swap_for_y,
+        options,
+        &mut bin_arrays,
+        bitmap_extension,
+        clock,
+        mint_x_account,
+        mint_y_account,
+    )
+}
+
+/// Apply an exact in swap to `lb_pair` and `bin_arrays`, and return its quote.
+///
+/// On success, `lb_pair` and `bin_arrays` hold the post swap state, so swaps can be chained and
+/// variable fee compounds across them. They are left untouched on error.
+///
+/// The simulated state covers the active bin, the volatility parameters and the protocol fee of
+/// the pair, and the MM and limit order liquidity of the bins. Fee and limit order bookkeeping of
+/// the bins (fee per token, fulfilled order amounts, order age) is not simulated.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_swap_exact_in(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &mut LbPair,
+    amount_in: u64,
+    swap_for_y: bool,
+    options: QuoteOptions,
+    bin_arrays: &mut HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    clock: &Clock,
+    mint_x_account: &Account,
+    mint_y_account: &Account,
+) -> Result<SwapExactInDetailedQuote> {
     let current_timestamp = clock.unix_timestamp as u64;
     let current_slot = clock.slot;
     let epoch = clock.epoch;
 
-    validate_swap_activation(lb_pair, current_timestamp, current_slot)?;
+    validate_swap_activation(lb_pair, options.user, current_timestamp, current_slot)?;
 
-    let mut lb_pair = *lb_pair;
-    lb_pair.update_references(current_timestamp as i64)?;
-
-    let support_limit_order = lb_pair.is_support_limit_order();
-    let fee_on_input = lb_pair.fee_on_input(swap_for_y);
+    let start_active_id = lb_pair.active_id;
+    let mut swapped_lb_pair = *lb_pair;
+    swapped_lb_pair.update_references(current_timestamp as i64)?;
 
-    let mut total_amount_out: u64 = 0;
-    let mut total_fee: u64 = 0;
-    let mut total_protocol_fee: u64 = 0;
+    let fee_on_input = swapped_lb_pair.fee_on_input(swap_for_y);
 
     let (in_mint_account, out_mint_account) = if swap_for_y {
         (mint_x_account, mint_y_account)
@@ -576,78 +1188,686 @@ pub fn quote_exact_in(
     let transfer_fee_excluded_amount_in =
         calculate_transfer_fee_excluded_amount(in_mint_account, amount_in, epoch)?.amount;
 
-    let mut amount_left = transfer_fee_excluded_amount_in;
+    let SwapExactInWalk {
+        lb_pair: mut swapped_lb_pair,
+        swapped_bin_arrays,
+        bin_fills,
+        liquidity_error,
+    } = walk_swap_exact_in(
+        lb_pair_pubkey,
+        swapped_lb_pair,
+        transfer_fee_excluded_amount_in,
+        swap_for_y,
+        options.host_fee_bps,
+        bin_arrays,
+        bitmap_extension,
+        |_, _| false,
+    )?;
+
+    if let Some(err) = liquidity_error {
+        return Err(err);
+    }
+
+    let totals = SwapTotals::from_bin_fills(&bin_fills)?;
+
+    let transfer_fee_excluded_amount_out =
+        calculate_transfer_fee_excluded_amount(out_mint_account, totals.amount_out, epoch)?.amount;
+
+    let prices = SwapQuotePrices::new(
+        start_active_id,
+        swapped_lb_pair.bin_step,
+        swap_for_y,
+        fee_on_input,
+        &bin_fills,
+    )?;
+
+    swapped_lb_pair.v_parameters.last_update_timestamp = current_timestamp as i64;
+    *lb_pair = swapped_lb_pair;
+    bin_arrays.extend(swapped_bin_arrays);
+
+    Ok(SwapExactInDetailedQuote {
+        quote: SwapExactInQuote {
+            amount_out: transfer_fee_excluded_amount_out,
+            fee: totals.fee,
+            protocol_fee: totals.protocol_fee,
+            host_fee: totals.host_fee,
+            protocol_fee_after_host_fee: totals.protocol_fee_after_host_fee()?,
+            prices,
+        },
+        bin_fills,
+    })
+}
+
+/// Exact in swap walked over the bins by [`walk_swap_exact_in`].
+struct SwapExactInWalk {
+    /// Pair state after the walk
+    lb_pair: LbPair,
+    /// Bin arrays swapped by the walk, in their post swap state
+    swapped_bin_arrays: HashMap<Pubkey, BinArray>,
+    bin_fills: Vec<BinFill>,
+    /// Set when the walk ends with input left because the pool is out of liquidity, or the next
+    /// bin array is not in the given bin arrays
+    liquidity_error: Option<Error>,
+}
+
+/// Walk the bins of an exact in swap of `amount_in`, excluding transfer fee, from the active bin
+/// of `lb_pair`. This is the bin walk behind every exact in quote.
+///
+/// `stop` is called with the active bin id and the bin fills so far before every bin is swapped,
+/// and ends the walk when it returns true.
+#[allow(clippy::too_many_arguments)]
+fn walk_swap_exact_in(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: LbPair,
+    amount_in: u64,
+    swap_for_y: bool,
+    host_fee_bps: Option<u16>,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    mut stop: impl FnMut(i32, &[BinFill]) -> bool,
+) -> Result<SwapExactInWalk> {
+    let support_limit_order = lb_pair.is_support_limit_order();
+    let fee_on_input = lb_pair.fee_on_input(swap_for_y);
+
+    let mut walk = SwapExactInWalk {
+        lb_pair,
+        swapped_bin_arrays: HashMap::new(),
+        bin_fills: vec![],
+        liquidity_error: None,
+    };
+    let mut amount_left = amount_in;
+    let mut stopped = false;
 
-    while amount_left > 0 {
-        let active_bin_array_pubkey = get_bin_array_pubkeys_for_swap(
+    while amount_left > 0 && !stopped {
+        let Some(active_bin_array_pubkey) = get_bin_array_pubkeys_for_swap(
             lb_pair_pubkey,
-            &lb_pair,
+            &walk.lb_pair,
             bitmap_extension,
             swap_for_y,
             1,
         )?
-        .pop()
-        .context("Pool out of liquidity")?;
+        .pop() else {
+            walk.liquidity_error = Some(PoolOutOfLiquidityError.into());
+            break;
+        };
 
-        let mut active_bin_array = bin_arrays
-            .get(&active_bin_array_pubkey)
-            .cloned()
-            .context("Active bin array not found")?;
+        let Some(mut active_bin_array) = bin_arrays.get(&active_bin_array_pubkey).copied() else {
+            walk.liquidity_error = Some(
+                get_missing_bin_arrays_error(
+                    lb_pair_pubkey,
+                    &walk.lb_pair,
+                    bitmap_extension,
+                    swap_for_y,
+                    bin_arrays,
+                )?
+                .into(),
+            );
+            break;
+        };
 
-        shift_active_bin_if_empty_gap(&mut lb_pair, &active_bin_array, swap_for_y)?;
+        shift_active_bin_if_empty_gap(&mut walk.lb_pair, &active_bin_array, swap_for_y)?;
 
         loop {
-            if !active_bin_array.is_bin_id_within_range(lb_pair.active_id)? || amount_left == 0 {
+            if !active_bin_array.is_bin_id_within_range(walk.lb_pair.active_id)? || amount_left == 0
+            {
                 break;
             }
 
-            let active_bin = active_bin_array.get_bin_mut(lb_pair.active_id)?;
+            if stop(walk.lb_pair.active_id, &walk.bin_fills) {
+                stopped = true;
+                break;
+            }
+
+            let active_bin = active_bin_array.get_bin_mut(walk.lb_pair.active_id)?;
 
             let max_out =
                 active_bin.get_max_amount_out_with_limit_orders(swap_for_y, support_limit_order);
 
             if max_out > 0 {
-                lb_pair.update_volatility_accumulator()?;
+                walk.lb_pair.update_volatility_accumulator()?;
 
                 let result = swap_exact_in_quote_at_bin(
                     active_bin,
-                    &lb_pair,
+                    &walk.lb_pair,
                     amount_left,
                     swap_for_y,
                     support_limit_order,
                     fee_on_input,
+                    host_fee_bps,
                 )?;
 
                 if result.amount_in > 0 {
                     amount_left = amount_left
                         .checked_sub(result.amount_in)
                         .context("MathOverflow")?;
-                    total_amount_out = total_amount_out
-                        .checked_add(result.amount_out)
-                        .context("MathOverflow")?;
-                    total_fee = total_fee.checked_add(result.fee).context("MathOverflow")?;
-                    total_protocol_fee = total_protocol_fee
-                        .checked_add(result.protocol_fee)
-                        .context("MathOverflow")?;
+                    apply_bin_quote_result(active_bin, &result, swap_for_y)?;
+                    apply_protocol_fee(&mut walk.lb_pair, &result, swap_for_y, fee_on_input)?;
+                    walk.bin_fills.push(BinFill::new(
+                        walk.lb_pair.active_id,
+                        active_bin.price,
+                        &result,
+                    ));
                 }
             }
 
             if amount_left > 0 {
-                lb_pair.advance_active_bin(swap_for_y)?;
+                walk.lb_pair.advance_active_bin(swap_for_y)?;
             }
         }
+
+        walk.swapped_bin_arrays
+            .insert(active_bin_array_pubkey, active_bin_array);
     }
 
-    let transfer_fee_excluded_amount_out =
-        calculate_transfer_fee_excluded_amount(out_mint_account, total_amount_out, epoch)?.amount;
+    Ok(walk)
+}
 
-    Ok(SwapExactInQuote {
-        amount_out: transfer_fee_excluded_amount_out,
-        fee: total_fee,
-        protocol_fee: total_protocol_fee,
+/// Running totals of the bin fills of a swap.
+#[derive(Default)]
+struct SwapTotals {
+    amount_in: u64,
+    amount_out: u64,
+    fee: u64,
+    protocol_fee: u64,
+    host_fee: u64,
+}
+
+impl SwapTotals {
+    fn from_bin_fills(bin_fills: &[BinFill]) -> Result<Self> {
+        let mut totals = Self::default();
+
+        for bin_fill in bin_fills {
+            totals.add(bin_fill)?;
+        }
+
+        Ok(totals)
+    }
+
+    fn add(&mut self, bin_fill: &BinFill) -> Result<()> {
+        self.amount_in = self
+            .amount_in
+            .checked_add(bin_fill.amount_in)
+            .context("MathOverflow")?;
+        self.amount_out = self
+            .amount_out
+            .checked_add(bin_fill.amount_out)
+            .context("MathOverflow")?;
+        self.fee = self.fee.checked_add(bin_fill.fee).context("MathOverflow")?;
+        self.protocol_fee = self
+            .protocol_fee
+            .checked_add(bin_fill.protocol_fee)
+            .context("MathOverflow")?;
+        self.host_fee = self
+            .host_fee
+            .checked_add(bin_fill.host_fee)
+            .context("MathOverflow")?;
+
+        Ok(())
+    }
+
+    fn protocol_fee_after_host_fee(&self) -> Result<u64> {
+        self.protocol_fee
+            .checked_sub(self.host_fee)
+            .context("MathOverflow")
+    }
+}
+
+/// Move the liquidity consumed by a swap in the bin.
+fn apply_bin_quote_result(bin: &mut Bin, result: &BinQuoteResult, swap_for_y: bool) -> Result<()> {
+    let (mm_amount_in, mm_amount_out) = if swap_for_y {
+        (&mut bin.amount_x, &mut bin.amount_y)
+    } else {
+        (&mut bin.amount_y, &mut bin.amount_x)
+    };
+
+    *mm_amount_in = mm_amount_in
+        .checked_add(result.mm_amount_in)
+        .context("MathOverflow")?;
+    *mm_amount_out = mm_amount_out
+        .checked_sub(result.mm_amount_out)
+        .context("MathOverflow")?;
+
+    bin.processed_order_remaining_amount = bin
+        .processed_order_remaining_amount
+        .checked_sub(result.processed_order_amount_out)
+        .context("MathOverflow")?;
+    bin.open_order_amount = bin
+        .open_order_amount
+        .checked_sub(result.open_order_amount_out)
+        .context("MathOverflow")?;
+
+    Ok(())
+}
+
+/// Accumulate the protocol fee of a swap, net of host fee, in the pair. The fee is accumulated in
+/// the token it is charged in.
+fn apply_protocol_fee(
+    lb_pair: &mut LbPair,
+    result: &BinQuoteResult,
+    swap_for_y: bool,
+    fee_on_input: bool,
+) -> Result<()> {
+    let protocol_fee = if swap_for_y == fee_on_input {
+        &mut lb_pair.protocol_fee.amount_x
+    } else {
+        &mut lb_pair.protocol_fee.amount_y
+    };
+
+    *protocol_fee = result
+        .protocol_fee
+        .checked_sub(result.host_fee)
+        .and_then(|amount| protocol_fee.checked_add(amount))
+        .context("MathOverflow")?;
+
+    Ok(())
+}
+
+/// Quote for `swap_with_price_impact` / `swap_with_price_impact2`.
+///
+/// The program rejects the swap when the amount out is lower than the amount in converted at the
+/// price of the reference active bin, discounted by `max_price_impact_bps`. `active_id` is the
+/// reference active bin passed to the instruction, and defaults to the pair active bin when it is
+/// `None`.
+///
+/// When swapping the whole `amount_in` exceeds the price impact, the quote is the partial fill of
+/// the bins swapped before the price impact is reached: `amount_in` is the input consumed by
+/// these bins, and `amount_left` is the input left unswapped. Send the quoted `amount_in` to get
+/// the partial fill, since the program rejects the whole amount. `amount_in` is 0 when the first
+/// bin already exceeds the price impact, for example when the trading fee alone exceeds
+/// `max_price_impact_bps`.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_exact_in_with_price_impact(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    amount_in: u64,
+    swap_for_y: bool,
+    active_id: Option<i32>,
+    max_price_impact_bps: u16,
+    options: QuoteOptions,
+    bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    clock: &Clock,
+    mint_x_account: &Account,
+    mint_y_account: &Account,
+) -> Result<SwapWithPriceImpactQuote> {
+    let current_timestamp = clock.unix_timestamp as u64;
+    let current_slot = clock.slot;
+    let epoch = clock.epoch;
+
+    validate_swap_activation(lb_pair, options.user, current_timestamp, current_slot)?;
+
+    let start_active_id = lb_pair.active_id;
+    let mut swapped_lb_pair = *lb_pair;
+    swapped_lb_pair.update_references(current_timestamp as i64)?;
+
+    let fee_on_input = swapped_lb_pair.fee_on_input(swap_for_y);
+
+    let (in_mint_account, out_mint_account) = if swap_for_y {
+        (mint_x_account, mint_y_account)
+    } else {
+        (mint_y_account, mint_x_account)
+    };
+
+    // Amounts sent to and received from the program for the bin fills, with the minimum amount
+    // out the program enforces for them
+    let get_amounts = |bin_fills: &[BinFill]| -> Result<(SwapTotals, u64, u64, u64)> {
+        let totals = SwapTotals::from_bin_fills(bin_fills)?;
+        let amount_in =
+            calculate_transfer_fee_included_amount(in_mint_account, totals.amount_in, epoch)?
+                .amount;
+        let amount_out =
+            calculate_transfer_fee_excluded_amount(out_mint_account, totals.amount_out, epoch)?
+                .amount;
+        let min_amount_out = get_min_amount_out_with_price_impact(
+            lb_pair,
+            amount_in,
+            swap_for_y,
+            active_id,
+            max_price_impact_bps,
+        )?;
+
+        Ok((totals, amount_in, amount_out, min_amount_out))
+    };
+
+    let transfer_fee_excluded_amount_in =
+        calculate_transfer_fee_excluded_amount(in_mint_account, amount_in, epoch)?.amount;
+
+    // Stop at the first bin that brings the cumulative amount out below the minimum amount out
+    let mut check_error = None;
+    let SwapExactInWalk {
+        mut bin_fills,
+        liquidity_error,
+        ..
+    } = walk_swap_exact_in(
+        lb_pair_pubkey,
+        swapped_lb_pair,
+        transfer_fee_excluded_amount_in,
+        swap_for_y,
+        options.host_fee_bps,
+        &bin_arrays,
+        bitmap_extension,
+        |_, bin_fills| match get_amounts(bin_fills) {
+            Ok((_, _, amount_out, min_amount_out)) => amount_out < min_amount_out,
+            Err(err) => {
+                check_error = Some(err);
+                true
+            }
+        },
+    )?;
+
+    if let Some(err) = check_error {
+        return Err(err);
+    }
+
+    let (totals, _, amount_out, _) = get_amounts(&bin_fills)?;
+    let min_amount_out = get_min_amount_out_with_price_impact(
+        lb_pair,
+        amount_in,
+        swap_for_y,
+        active_id,
+        max_price_impact_bps,
+    )?;
+
+    let (totals, filled_amount_in, amount_out, min_amount_out) = if amount_out < min_amount_out {
+        // The last bin swapped exceeds the price impact
+        bin_fills.pop();
+        get_amounts(&bin_fills)?
+    } else if let Some(err) = liquidity_error {
+        return Err(err);
+    } else {
+        (totals, amount_in, amount_out, min_amount_out)
+    };
+
+    let prices = SwapQuotePrices::new(
+        start_active_id,
+        swapped_lb_pair.bin_step,
+        swap_for_y,
+        fee_on_input,
+        &bin_fills,
+    )?;
+
+    Ok(SwapWithPriceImpactQuote {
+        amount_in: filled_amount_in,
+        amount_left: amount_in
+            .checked_sub(filled_amount_in)
+            .context("MathOverflow")?,
+        amount_out,
+        min_amount_out,
+        fee: totals.fee,
+        protocol_fee: totals.protocol_fee,
+        host_fee: totals.host_fee,
+        protocol_fee_after_host_fee: totals.protocol_fee_after_host_fee()?,
+        prices,
+    })
+}
+
+/// Minimum amount out enforced by `swap_with_price_impact` for the given price impact.
+pub fn get_min_amount_out_with_price_impact(
+    lb_pair: &LbPair,
+    amount_in: u64,
+    swap_for_y: bool,
+    active_id: Option<i32>,
+    max_price_impact_bps: u16,
+) -> Result<u64> {
+    ensure!(
+        i32::from(max_price_impact_bps) <= BASIS_POINT_MAX,
+        "Invalid basis point"
+    );
+
+    let active_id = active_id.unwrap_or(lb_pair.active_id);
+    let price = get_price_from_id(active_id, lb_pair.bin_step)?;
+
+    let amount_out: u128 = if swap_for_y {
+        mul_shr(price, amount_in.into(), SCALE_OFFSET, Rounding::Down)
+    } else {
+        shl_div(amount_in.into(), price, SCALE_OFFSET, Rounding::Down)
+    }
+    .context("MathOverflow")?;
+
+    let min_amount_out = amount_out
+        .checked_mul((BASIS_POINT_MAX as u128) - u128::from(max_price_impact_bps))
+        .context("MathOverflow")?
+        .checked_div(BASIS_POINT_MAX as u128)
+        .context("MathOverflow")?;
+
+    min_amount_out.try_into().context("MathOverflow")
+}
+
+/// Quote the minimum exact in swap that moves the active bin to `target_active_id`.
+///
+/// Every bin between the active bin and the target bin is drained, including limit order
+/// liquidity, and the swap ends with 1 unit of input consumed by the target bin. The target bin
+/// must hold liquidity to swap against, otherwise the swap would skip over it.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_exact_in_to_bin(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    target_active_id: i32,
+    options: QuoteOptions,
+    bin_arrays: HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    clock: &Clock,
+    mint_x_account: &Account,
+    mint_y_account: &Account,
+) -> Result<SwapExactInToBinQuote> {
+    let current_timestamp = clock.unix_timestamp as u64;
+    let current_slot = clock.slot;
+    let epoch = clock.epoch;
+
+    validate_swap_activation(lb_pair, options.user, current_timestamp, current_slot)?;
+
+    let start_active_id = lb_pair.active_id;
+    let swap_for_y = target_active_id < start_active_id;
+
+    let mut lb_pair = *lb_pair;
+    lb_pair.update_references(current_timestamp as i64)?;
+
+    let fee_on_input = lb_pair.fee_on_input(swap_for_y);
+
+    let (in_mint_account, out_mint_account) = if swap_for_y {
+        (mint_x_account, mint_y_account)
+    } else {
+        (mint_y_account, mint_x_account)
+    };
+
+    let mut bin_fills = vec![];
+
+    if target_active_id != start_active_id {
+        // Drain the bins before the target bin
+        let drained = walk_swap_exact_in(
+            lb_pair_pubkey,
+            lb_pair,
+            u64::MAX,
+            swap_for_y,
+            options.host_fee_bps,
+            &bin_arrays,
+            bitmap_extension,
+            |active_id, _| {
+                if swap_for_y {
+                    active_id <= target_active_id
+                } else {
+                    active_id >= target_active_id
+                }
+            },
+        )?;
+
+        if let Some(err) = drained.liquidity_error {
+            return Err(err);
+        }
+
+        ensure!(
+            drained.lb_pair.active_id == target_active_id,
+            "Target bin has no liquidity"
+        );
+
+        // The remaining unit of input moves the active bin to the target bin
+        let target_bin_fills = walk_swap_exact_in(
+            lb_pair_pubkey,
+            drained.lb_pair,
+            1,
+            swap_for_y,
+            options.host_fee_bps,
+            &bin_arrays,
+            bitmap_extension,
+            |_, _| false,
+        )?
+        .bin_fills;
+
+        ensure!(
+            target_bin_fills.first().map(|bin_fill| bin_fill.bin_id) == Some(target_active_id),
+            "Target bin has no liquidity"
+        );
+
+        bin_fills = drained.bin_fills;
+        bin_fills.extend(target_bin_fills);
+    }
+
+    let totals = SwapTotals::from_bin_fills(&bin_fills)?;
+
+    let transfer_fee_included_amount_in =
+        calculate_transfer_fee_included_amount(in_mint_account, totals.amount_in, epoch)?.amount;
+
+    let transfer_fee_excluded_amount_out =
+        calculate_transfer_fee_excluded_amount(out_mint_account, totals.amount_out, epoch)?.amount;
+
+    let prices = SwapQuotePrices::new(
+        start_active_id,
+        lb_pair.bin_step,
+        swap_for_y,
+        fee_on_input,
+        &bin_fills,
+    )?;
+
+    Ok(SwapExactInToBinQuote {
+        swap_for_y,
+        amount_in: transfer_fee_included_amount_in,
+        amount_out: transfer_fee_excluded_amount_out,
+        fee: totals.fee,
+        protocol_fee: totals.protocol_fee,
+        host_fee: totals.host_fee,
+        protocol_fee_after_host_fee: totals.protocol_fee_after_host_fee()?,
+        prices,
     })
 }
 
+/// Quote the cumulative depth curve of both swap directions in a single pass over the bins.
+///
+/// Each direction returns up to `bin_limit` levels, one per bin with liquidity. The walk stops
+/// early when the pool runs out of liquidity, or when the next bin array is not in `bin_arrays`.
+#[allow(clippy::too_many_arguments)]
+pub fn quote_depth(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: &LbPair,
+    bin_limit: u16,
+    options: QuoteOptions,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    clock: &Clock,
+    mint_x_account: &Account,
+    mint_y_account: &Account,
+) -> Result<SwapDepthQuote> {
+    let current_timestamp = clock.unix_timestamp as u64;
+    let current_slot = clock.slot;
+    let epoch = clock.epoch;
+
+    validate_swap_activation(lb_pair, options.user, current_timestamp, current_slot)?;
+
+    let mut lb_pair = *lb_pair;
+    lb_pair.update_references(current_timestamp as i64)?;
+
+    Ok(SwapDepthQuote {
+        x_to_y: get_depth_levels(
+            lb_pair_pubkey,
+            lb_pair,
+            true,
+            bin_limit,
+            options.host_fee_bps,
+            bin_arrays,
+            bitmap_extension,
+            epoch,
+            mint_x_account,
+            mint_y_account,
+        )?,
+        y_to_x: get_depth_levels(
+            lb_pair_pubkey,
+            lb_pair,
+            false,
+            bin_limit,
+            options.host_fee_bps,
+            bin_arrays,
+            bitmap_extension,
+            epoch,
+            mint_x_account,
+            mint_y_account,
+        )?,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn get_depth_levels(
+    lb_pair_pubkey: Pubkey,
+    lb_pair: LbPair,
+    swap_for_y: bool,
+    bin_limit: u16,
+    host_fee_bps: Option<u16>,
+    bin_arrays: &HashMap<Pubkey, BinArray>,
+    bitmap_extension: Option<&BinArrayBitmapExtension>,
+    epoch: u64,
+    mint_x_account: &Account,
+    mint_y_account: &Account,
+) -> Result<Vec<DepthLevel>> {
+    let (in_mint_account, out_mint_account) = if swap_for_y {
+        (mint_x_account, mint_y_account)
+    } else {
+        (mint_y_account, mint_x_account)
+    };
+
+    let bin_limit = usize::from(bin_limit);
+
+    // Swap as much as the pool holds, the walk ends after `bin_limit` bins, or when it runs out
+    // of liquidity or bin arrays
+    let walk = walk_swap_exact_in(
+        lb_pair_pubkey,
+        lb_pair,
+        u64::MAX,
+        swap_for_y,
+        host_fee_bps,
+        bin_arrays,
+        bitmap_extension,
+        |_, bin_fills| bin_fills.len() == bin_limit,
+    )?;
+
+    let mut totals = SwapTotals::default();
+
+    walk.bin_fills
+        .iter()
+        .map(|bin_fill| {
+            totals.add(bin_fill)?;
+
+            Ok(DepthLevel {
+                bin_id: bin_fill.bin_id,
+                price: bin_fill.price,
+                amount_in: calculate_transfer_fee_included_amount(
+                    in_mint_account,
+                    totals.amount_in,
+                    epoch,
+                )?
+                .amount,
+                amount_out: calculate_transfer_fee_excluded_amount(
+                    out_mint_account,
+                    totals.amount_out,
+                    epoch,
+                )?
+                .amount,
+                fee: totals.fee,
+                protocol_fee: totals.protocol_fee,
+                host_fee: totals.host_fee,
+                protocol_fee_after_host_fee: totals.protocol_fee_after_host_fee()?,
+            })
+        })
+        .collect()
+}
+
 pub fn get_bin_array_pubkeys_for_swap(
     lb_pair_pubkey: Pubkey,
     lb_pair: &LbPair,
@@ -705,259 +1925,153 @@ pub fn get_bin_array_pubkeys_for_swap(
     Ok(bin_array_pubkeys)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "rpc"))]
 mod tests {
     use super::*;
     use anchor_client::solana_client::rpc_response::RpcKeyedAccount;
     use anchor_client::solana_sdk::clock::Clock;
-    use anchor_client::{
-        solana_client::nonblocking::rpc_client::RpcClient, solana_sdk::pubkey::Pubkey, Cluster,
-    };
+    use anchor_client::solana_sdk::pubkey::Pubkey;
     use litesvm::LiteSVM;
 
     pub const DLMM_PROGRAM_FILE_PATH: &str = "../artifacts/lb_clmm.so";
 
-    /// Get on chain clock
-    async fn get_clock(rpc_client: RpcClient) -> Result<Clock> {
-        let clock_account = rpc_client
-            .get_account(&anchor_client::solana_sdk::sysvar::clock::ID)
-            .await?;
+    /// SOL/USDC like pair at 150 USDC per SOL, with 10 SOL in every bin above the active bin and
+    /// 1,500 USDC in every bin below it.
+    #[cfg(feature = "test-utils")]
+    fn sol_usdc_snapshot() -> PoolSnapshot {
+        use crate::test_utils::*;
 
-        let clock_state: Clock = bincode::deserialize(clock_account.data.as_ref())?;
+        // 1.001 ^ -1898 = 0.15 USDC lamport per SOL lamport
+        let active_id = -1898;
+        let mut fixture = PoolFixture::two_sided(10, active_id, 20, 10_000_000_000, 1_500_000_000);
+        fixture.token_x = MintFixture::new(Pubkey::new_unique(), 9);
+        fixture.token_y = MintFixture::new(Pubkey::new_unique(), 6);
 
-        Ok(clock_state)
+        fixture.build().unwrap().to_pool_snapshot().unwrap()
     }
 
-    #[tokio::test]
-    async fn test_swap_quote_exact_out() {
-        // RPC client. No gPA is required.
-        let rpc_client = RpcClient::new(Cluster::Mainnet.url().to_string());
-
-        let sol_usdc = Pubkey::from_str_const("HTvjzsfX3yU6BUodCjZ5vZkUrAxMDTrBs3CJaq43ashR");
-
-        let lb_pair_account = rpc_client.get_account(&sol_usdc).await.unwrap();
-
-        let lb_pair: LbPair = bytemuck::pod_read_unaligned(&lb_pair_account.data[8..]);
-
-        let mut mint_accounts = rpc_client
-            .get_multiple_accounts(&[lb_pair.token_x_mint, lb_pair.token_y_mint])
-            .await
-            .unwrap();
-
-        let mint_x_account = mint_accounts[0].take().unwrap();
-        let mint_y_account = mint_accounts[1].take().unwrap();
-
-        // 3 bin arrays to left, and right is enough to cover most of the swap, and stay under 1.4m CU constraint.
-        // Get 3 bin arrays to the left from the active bin
-        let left_bin_array_pubkeys =
-            get_bin_array_pubkeys_for_swap(sol_usdc, &lb_pair, None, true, 3).unwrap();
-
-        // Get 3 bin arrays to the right the from active bin
-        let right_bin_array_pubkeys =
-            get_bin_array_pubkeys_for_swap(sol_usdc, &lb_pair, None, false, 3).unwrap();
-
-        // Fetch bin arrays
-        let bin_array_pubkeys = left_bin_array_pubkeys
-            .into_iter()
-            .chain(right_bin_array_pubkeys.into_iter())
-            .collect::<Vec<Pubkey>>();
-
-        let accounts = rpc_client
-            .get_multiple_accounts(&bin_array_pubkeys)
-            .await
-            .unwrap();
-
-        let bin_arrays = accounts
-            .into_iter()
-            .zip(bin_array_pubkeys.into_iter())
-            .map(|(account, key)| {
-                (
-                    key,
-                    bytemuck::pod_read_unaligned(&account.unwrap().data[8..]),
-                )
-            })
-            .collect::<HashMap<_, _>>();
-
-        let usdc_token_multiplier = 1_000_000.0;
-        let sol_token_multiplier = 1_000_000_000.0;
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_swap_quote_exact_out() {
+        let snapshot = sol_usdc_snapshot();
 
+        // USDC -> exact 1 SOL
         let out_sol_amount = 1_000_000_000;
-        let clock = get_clock(rpc_client).await.unwrap();
-
-        let quote_result = quote_exact_out(
-            sol_usdc,
-            &lb_pair,
-            out_sol_amount,
-            false,
-            bin_arrays.clone(),
-            None,
-            &clock,
-            &mint_x_account,
-            &mint_y_account,
-        )
-        .unwrap();
-
+        let quote_result = snapshot
+            .quote_exact_out(out_sol_amount, false, QuoteOptions::default())
+            .unwrap();
         let in_amount = quote_result.amount_in + quote_result.fee;
-
-        println!(
-            "{} USDC -> exact 1 SOL",
-            in_amount as f64 / usdc_token_multiplier
-        );
-
-        let quote_result = quote_exact_in(
-            sol_usdc,
-            &lb_pair,
-            in_amount,
-            false,
-            bin_arrays.clone(),
-            None,
-            &clock,
-            &mint_x_account,
-            &mint_y_account,
-        )
-        .unwrap();
-
-        println!(
-            "{} USDC -> {} SOL",
-            in_amount as f64 / usdc_token_multiplier,
-            quote_result.amount_out as f64 / sol_token_multiplier
+        assert!(in_amount > 150_000_000 && in_amount < 151_000_000);
+        assert!(
+            snapshot
+                .quote_exact_in(in_amount, false, QuoteOptions::default())
+                .unwrap()
+                .amount_out
+                >= out_sol_amount
         );
 
+        // SOL -> exact 200 USDC
         let out_usdc_amount = 200_000_000;
-
-        let quote_result = quote_exact_out(
-            sol_usdc,
-            &lb_pair,
-            out_usdc_amount,
-            true,
-            bin_arrays.clone(),
-            None,
-            &clock,
-            &mint_x_account,
-            &mint_y_account,
-        )
-        .unwrap();
-
+        let quote_result = snapshot
+            .quote_exact_out(out_usdc_amount, true, QuoteOptions::default())
+            .unwrap();
         let in_amount = quote_result.amount_in + quote_result.fee;
-
-        println!(
-            "{} SOL -> exact 200 USDC",
-            in_amount as f64 / sol_token_multiplier
-        );
-
-        let quote_result = quote_exact_in(
-            sol_usdc,
-            &lb_pair,
-            in_amount,
-            true,
-            bin_arrays,
-            None,
-            &clock,
-            &mint_x_account,
-            &mint_y_account,
-        )
-        .unwrap();
-
-        println!(
-            "{} SOL -> {} USDC",
-            in_amount as f64 / sol_token_multiplier,
-            quote_result.amount_out as f64 / usdc_token_multiplier
+        assert!(in_amount > 1_333_000_000 && in_amount < 1_343_000_000);
+        assert!(
+            snapshot
+                .quote_exact_in(in_amount, true, QuoteOptions::default())
+                .unwrap()
+                .amount_out
+                >= out_usdc_amount
         );
     }
 
-    #[tokio::test]
-    async fn test_swap_quote_exact_in() {
-        // RPC client. No gPA is required.
-        let rpc_client = RpcClient::new(Cluster::Mainnet.url().to_string());
-
-        let sol_usdc = Pubkey::from_str_const("HTvjzsfX3yU6BUodCjZ5vZkUrAxMDTrBs3CJaq43ashR");
-
-        let lb_pair_account = rpc_client.get_account(&sol_usdc).await.unwrap();
-
-        let lb_pair: LbPair = bytemuck::pod_read_unaligned(&lb_pair_account.data[8..]);
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_swap_quote_exact_in() {
+        let snapshot = sol_usdc_snapshot();
 
-        let mut mint_accounts = rpc_client
-            .get_multiple_accounts(&[lb_pair.token_x_mint, lb_pair.token_y_mint])
-            .await
+        // 5 SOL -> USDC
+        let in_sol_amount = 5_000_000_000;
+        let quote_result = snapshot
+            .quote_exact_in(in_sol_amount, true, QuoteOptions::default())
             .unwrap();
+        assert!(quote_result.amount_out > 745_000_000 && quote_result.amount_out < 750_000_000);
+        assert!(
+            snapshot
+                .quote_exact_out(quote_result.amount_out, true, QuoteOptions::default())
+                .unwrap()
+                .amount_in
+                <= in_sol_amount
+        );
 
-        let mint_x_account = mint_accounts[0].take().unwrap();
-        let mint_y_account = mint_accounts[1].take().unwrap();
-
-        // 3 bin arrays to left, and right is enough to cover most of the swap, and stay under 1.4m CU constraint.
-        // Get 3 bin arrays to the left from the active bin
-        let left_bin_array_pubkeys =
-            get_bin_array_pubkeys_for_swap(sol_usdc, &lb_pair, None, true, 3).unwrap();
-
-        // Get 3 bin arrays to the right the from active bin
-        let right_bin_array_pubkeys =
-            get_bin_array_pubkeys_for_swap(sol_usdc, &lb_pair, None, false, 3).unwrap();
-
-        // Fetch bin arrays
-        let bin_array_pubkeys = left_bin_array_pubkeys
-            .into_iter()
-            .chain(right_bin_array_pubkeys.into_iter())
-            .collect::<Vec<Pubkey>>();
-
-        let accounts = rpc_client
-            .get_multiple_accounts(&bin_array_pubkeys)
-            .await
+        // 100 USDC -> SOL
+        let in_usdc_amount = 100_000_000;
+        let quote_result = snapshot
+            .quote_exact_in(in_usdc_amount, false, QuoteOptions::default())
             .unwrap();
+        assert!(quote_result.amount_out > 660_000_000 && quote_result.amount_out < 667_000_000);
+        assert!(
+            snapshot
+                .quote_exact_out(quote_result.amount_out, false, QuoteOptions::default())
+                .unwrap()
+                .amount_in
+                <= in_usdc_amount
+        );
+    }
 
-        let bin_arrays = accounts
-            .into_iter()
-            .zip(bin_array_pubkeys.into_iter())
-            .map(|(account, key)| {
-                (
-                    key,
-                    bytemuck::pod_read_unaligned(&account.unwrap().data[8..]),
-                )
-            })
-            .collect::<HashMap<_, _>>();
-
-        // 1 SOL -> USDC
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_swap_quote_exact_in_with_price_impact() {
+        let snapshot = sol_usdc_snapshot();
         let in_sol_amount = 5_000_000_000;
 
-        let clock = get_clock(rpc_client).await.unwrap();
-
-        let quote_result = quote_exact_in(
-            sol_usdc,
-            &lb_pair,
-            in_sol_amount,
-            true,
-            bin_arrays.clone(),
-            None,
-            &clock,
-            &mint_x_account,
-            &mint_y_account,
-        )
-        .unwrap();
-
-        println!(
-            "1 SOL -> {:?} USDC",
-            quote_result.amount_out as f64 / 1_000_000.0
+        // The 10 bps base fee alone exceeds a 5 bps price impact, no bin can be swapped
+        let quote_result = snapshot
+            .quote_exact_in_with_price_impact(in_sol_amount, true, None, 5, QuoteOptions::default())
+            .unwrap();
+        assert_eq!(quote_result.amount_in, 0);
+        assert_eq!(quote_result.amount_left, in_sol_amount);
+        assert_eq!(quote_result.amount_out, 0);
+        assert_eq!(quote_result.min_amount_out, 0);
+        assert_eq!(
+            quote_result.prices.end_active_id,
+            quote_result.prices.start_active_id
         );
 
-        // 100 USDC -> SOL
-        let in_usdc_amount = 100_000_000;
-
-        let quote_result = quote_exact_in(
-            sol_usdc,
-            &lb_pair,
-            in_usdc_amount,
-            false,
-            bin_arrays.clone(),
-            None,
-            &clock,
-            &mint_x_account,
-            &mint_y_account,
-        )
-        .unwrap();
-
-        println!(
-            "100 USDC -> {:?} SOL",
-            quote_result.amount_out as f64 / 1_000_000_000.0
+        // 5 SOL span 1 bin, which is within a 20 bps price impact
+        let quote_result = snapshot
+            .quote_exact_in_with_price_impact(
+                in_sol_amount,
+                true,
+                None,
+                20,
+                QuoteOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(quote_result.amount_in, in_sol_amount);
+        assert_eq!(quote_result.amount_left, 0);
+        assert_eq!(
+            quote_result.amount_out,
+            snapshot
+                .quote_exact_in(in_sol_amount, true, QuoteOptions::default())
+                .unwrap()
+                .amount_out
         );
+
+        // 50 SOL span 5 bins, the partial fill stops at the bin exceeding the price impact
+        let quote_result = snapshot
+            .quote_exact_in_with_price_impact(
+                10 * in_sol_amount,
+                true,
+                None,
+                20,
+                QuoteOptions::default(),
+            )
+            .unwrap();
+        assert!(quote_result.amount_in > 0);
+        assert!(quote_result.amount_left > 0);
+        assert!(quote_result.amount_out >= quote_result.min_amount_out);
     }
 
     #[test]
@@ -1012,6 +2126,7 @@ mod tests {
             &lb_pair,
             in_base_amount,
             true,
+            QuoteOptions::default(),
             bin_arrays.clone(),
             None,
             &clock,
@@ -1030,6 +2145,7 @@ mod tests {
             &lb_pair,
             in_quote_amount,
             false,
+            QuoteOptions::default(),
             bin_arrays.clone(),
             None,
             &clock,
@@ -1047,6 +2163,7 @@ mod tests {
             &lb_pair,
             out_quote_amount,
             true,
+            QuoteOptions::default(),
             bin_arrays.clone(),
             None,
             &clock,
@@ -1065,6 +2182,7 @@ mod tests {
             &lb_pair,
             out_base_amount,
             false,
+            QuoteOptions::default(),
             bin_arrays.clone(),
             None,
             &clock,
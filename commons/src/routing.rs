@@ -0,0 +1,563 @@
+use crate::*;
+#[cfg(feature = "spl")]
+use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id,
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+};
+#[cfg(feature = "spl")]
+use anchor_spl::token::spl_token;
+#[cfg(feature = "spl")]
+use anchor_spl::token_2022::spl_token_2022::extension::{transfer_hook, StateWithExtensions};
+#[cfg(feature = "spl")]
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use core::result::Result::Ok;
+use solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "spl")]
+use solana_sdk::{account::Account, instruction::Instruction};
+
+/// Quote of the swap against a single pair of a route.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouteLegQuote {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub lb_pair: Pubkey,
+    pub swap_for_y: bool,
+    pub amount_in: u64,
+    pub quote: SwapExactInQuote,
+    /// Bin arrays traversed by the swap, in traversal order
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub bin_array_pubkeys: Vec<Pubkey>,
+}
+
+/// Quote of a route hop. The hop amount is split across parallel pairs of the same tokens.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouteHopQuote {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub input_mint: Pubkey,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub legs: Vec<RouteLegQuote>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouteQuote {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub input_mint: Pubkey,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serialization::string_encoded")
+    )]
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Hops in swap order. The amount out of a hop is the amount in of the next one.
+    pub hops: Vec<RouteHopQuote>,
+}
+
+/// Error returned by the route quotes when a hop of the route can't be swapped.
+/// [`quote_best_route`] skips the routes failing with it.
+#[derive(Debug, Clone)]
+pub enum RouteHopError {
+    /// No pair swaps the hop mints
+    NoPair {
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+    },
+    /// The pairs of the hop run out of liquidity or bin arrays before the hop amount is swapped
+    InsufficientLiquidity {
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+    },
+}
+
+impl std::fmt::Display for RouteHopError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoPair {
+                input_mint,
+                output_mint,
+            } => write!(f, "No pair found from {} to {}", input_mint, output_mint),
+            Self::InsufficientLiquidity {
+                input_mint,
+                output_mint,
+            } => write!(
+                f,
+                "Pool out of liquidity from {} to {}",
+                input_mint, output_mint
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RouteHopError {}
+
+/// Find and quote the route from `input_mint` to `output_mint` with the most amount out.
+///
+/// Routes are either direct, or go through one intermediate mint. See [`quote_route`] for how the
+/// amount of a hop is split across its pairs.
+pub fn quote_best_route(
+    pools: &[PoolSnapshot],
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    amount_in: u64,
+    split_count: u8,
+    user: Option<Pubkey>,
+) -> Result<RouteQuote> {
+    ensure!(
+        input_mint != output_mint,
+        "Input and output mint are the same"
+    );
+
+    let mut route_mints = vec![vec![input_mint, output_mint]];
+
+    for pool in pools {
+        let intermediate_mint = if pool.lb_pair.token_x_mint == input_mint {
+            pool.lb_pair.token_y_mint
+        } else if pool.lb_pair.token_y_mint == input_mint {
+            pool.lb_pair.token_x_mint
+        } else {
+            continue;
+        };
+
+        let mints = vec![input_mint, intermediate_mint, output_mint];
+        if intermediate_mint != output_mint && !route_mints.contains(&mints) {
+            route_mints.push(mints);
+        }
+    }
+
+    let mut best_route: Option<RouteQuote> = None;
+
+    for mints in route_mints {
+        let route = match quote_route(pools, &mints, amount_in, split_count, user) {
+            Ok(route) => route,
+            Err(err) if err.is::<RouteHopError>() => continue,
+            Err(err) => return Err(err),
+        };
+
+        if best_route
+            .as_ref()
+            .is_none_or(|best_route| route.amount_out > best_route.amount_out)
+        {
+            best_route = Some(route);
+        }
+    }
+
+    best_route.context("No route found")
+}
+
+/// Quote the route going through `mints`, in order.
+///
+/// The amount of every hop is split in `split_count` parts. Each part goes to the pair of the hop
+/// with the most amount out for it, given the parts already swapped against the pair. Pairs that
+/// `user` can't swap against yet, or that run out of liquidity or bin arrays, are skipped. Fails
+/// with [`RouteHopError`] when a hop can't be swapped.
+pub fn quote_route(
+    pools: &[PoolSnapshot],
+    mints: &[Pubkey],
+    amount_in: u64,
+    split_count: u8,
+    user: Option<Pubkey>,
+) -> Result<RouteQuote> {
+    ensure!(mints.len() >= 2, "Route requires at least 2 mints");
+
+    let mut hops = vec![];
+    let mut hop_amount_in = amount_in;
+
+    for hop_mints in mints.windows(2) {
+        let hop = quote_hop(
+            pools,
+            hop_mints[0],
+            hop_mints[1],
+            hop_amount_in,
+            split_count,
+            user,
+        )?;
+        hop_amount_in = hop.amount_out;
+        hops.push(hop);
+    }
+
+    Ok(RouteQuote {
+        input_mint: mints[0],
+        output_mint: mints[mints.len() - 1],
+        amount_in,
+        amount_out: hop_amount_in,
+        hops,
+    })
+}
+
+fn get_swap_for_y(lb_pair: &LbPair, input_mint: Pubkey, output_mint: Pubkey) -> Option<bool> {
+    if lb_pair.token_x_mint == input_mint && lb_pair.token_y_mint == output_mint {
+        Some(true)
+    } else if lb_pair.token_y_mint == input_mint && lb_pair.token_x_mint == output_mint {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn quote_hop(
+    pools: &[PoolSnapshot],
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    amount_in: u64,
+    split_count: u8,
+    user: Option<Pubkey>,
+) -> Result<RouteHopQuote> {
+    let mut hop_pools = vec![];
+
+    for pool in pools {
+        let Some(swap_for_y) = get_swap_for_y(&pool.lb_pair, input_mint, output_mint) else {
+            continue;
+        };
+
+        if is_swap_enabled(
+            &pool.lb_pair,
+            user,
+            pool.clock.unix_timestamp as u64,
+            pool.clock.slot,
+        )? {
+            hop_pools.push((pool, swap_for_y));
+        }
+    }
+
+    if hop_pools.is_empty() {
+        return Err(RouteHopError::NoPair {
+            input_mint,
+            output_mint,
+        }
+        .into());
+    }
+
+    // State of the pairs after the parts allocated so far
+    let mut pool_states = hop_pools
+        .iter()
+        .map(|(pool, _)| (pool.lb_pair, pool.bin_arrays.clone()))
+        .collect::<Vec<_>>();
+    let mut allocations = vec![0u64; hop_pools.len()];
+
+    let split_count = u128::from(split_count.max(1));
+    let mut allocated_amount = 0;
+
+    for part in 1..=split_count {
+        let part_end_amount: u64 = (u128::from(amount_in) * part / split_count)
+            .try_into()
+            .context("MathOverflow")?;
+        let part_amount_in = part_end_amount - allocated_amount;
+
+        if part_amount_in == 0 {
+            continue;
+        }
+
+        let mut best_part = None;
+
+        for (index, ((pool, swap_for_y), (lb_pair, bin_arrays))) in
+            hop_pools.iter().zip(pool_states.iter()).enumerate()
+        {
+            let mut lb_pair = *lb_pair;
+            let mut bin_arrays = bin_arrays.clone();
+
+            let part_quote = match simulate_swap_exact_in(
+                pool.lb_pair_pubkey,
+                &mut lb_pair,
+                part_amount_in,
+                *swap_for_y,
+                QuoteOptions {
+                    user,
+                    ..Default::default()
+                },
+                &mut bin_arrays,
+                pool.bitmap_extension.as_ref(),
+                &pool.clock,
+                &pool.mint_x_account,
+                &pool.mint_y_account,
+            ) {
+                Ok(part_quote) => part_quote,
+                Err(err)
+                    if err.is::<PoolOutOfLiquidityError>() || err.is::<MissingBinArraysError>() =>
+                {
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+
+            let amount_out = part_quote.quote.amount_out;
+            if best_part
+                .as_ref()
+                .is_none_or(|(_, best_amount_out, _)| amount_out > *best_amount_out)
+            {
+                best_part = Some((index, amount_out, (lb_pair, bin_arrays)));
+            }
+        }
+
+        let (index, _, pool_state) = best_part.ok_or(RouteHopError::InsufficientLiquidity {
+            input_mint,
+            output_mint,
+        })?;
+        pool_states[index] = pool_state;
+        allocations[index] += part_amount_in;
+        allocated_amount = part_end_amount;
+    }
+
+    // Quote every pair with its total allocation, as a single swap
+    let mut legs = vec![];
+    let mut amount_out: u64 = 0;
+
+    for ((pool, swap_for_y), leg_amount_in) in hop_pools.into_iter().zip(allocations) {
+        if leg_amount_in == 0 {
+            continue;
+        }
+
+        let quote = pool.quote_exact_in(
+            leg_amount_in,
+            swap_for_y,
+            QuoteOptions {
+                user,
+                ..Default::default()
+            },
+        )?;
+
+        amount_out = amount_out
+            .checked_add(quote.amount_out)
+            .context("MathOverflow")?;
+
+        legs.push(RouteLegQuote {
+            lb_pair: pool.lb_pair_pubkey,
+            swap_for_y,
+            amount_in: leg_amount_in,
+            bin_array_pubkeys: get_swap_bin_array_pubkeys(
+                pool,
+                swap_for_y,
+                quote.prices.end_active_id,
+            )?,
+            quote,
+        });
+    }
+
+    Ok(RouteHopQuote {
+        input_mint,
+        output_mint,
+        amount_in,
+        amount_out,
+        legs,
+    })
+}
+
+/// Bin arrays with liquidity from the active bin up to the bin array of `end_active_id`.
+fn get_swap_bin_array_pubkeys(
+    pool: &PoolSnapshot,
+    swap_for_y: bool,
+    end_active_id: i32,
+) -> Result<Vec<Pubkey>> {
+    let end_bin_array_index = i64::from(BinArray::bin_id_to_bin_array_index(end_active_id)?);
+    let mut bin_array_pubkeys = vec![];
+
+    for bin_array_pubkey in get_bin_array_pubkeys_for_swap(
+        pool.lb_pair_pubkey,
+        &pool.lb_pair,
+        pool.bitmap_extension.as_ref(),
+        swap_for_y,
+        u8::MAX,
+    )? {
+        let Some(bin_array) = pool.bin_arrays.get(&bin_array_pubkey) else {
+            break;
+        };
+
+        bin_array_pubkeys.push(bin_array_pubkey);
+
+        let is_end_bin_array = if swap_for_y {
+            bin_array.index <= end_bin_array_index
+        } else {
+            bin_array.index >= end_bin_array_index
+        };
+
+        if is_end_bin_array {
+            break;
+        }
+    }
+
+    Ok(bin_array_pubkeys)
+}
+
+#[cfg(feature = "spl")]
+impl RouteQuote {
+    /// Instructions executing the route with `swap2`, one per leg, after creating the `user`
+    /// token accounts of the intermediate and output mints. `pools` must contain the route pairs.
+    ///
+    /// Every leg fails when its amount out is below its quote, scaled to the leg amount in, minus
+    /// `slippage_bps`. The first hop swaps its quoted amounts in. Later hops swap the minimum
+    /// amount out of the previous hop, split across their legs like the quoted amount in, so they
+    /// don't fail when the previous hop returns less than quoted but within slippage. Whatever the
+    /// previous hop returns above its minimum amount out is left in the `user` token account.
+    /// Pairs with transfer hook mints are not supported.
+    pub fn get_swap_instructions(
+        &self,
+        pools: &[PoolSnapshot],
+        user: Pubkey,
+        slippage_bps: u16,
+    ) -> Result<Vec<Instruction>> {
+        ensure!(
+            i32::from(slippage_bps) <= BASIS_POINT_MAX,
+            "Invalid slippage bps"
+        );
+
+        let mut instructions = vec![];
+        let mut hop_amount_in = self.amount_in;
+
+        for hop in self.hops.iter() {
+            let mut hop_min_amount_out: u64 = 0;
+            let mut remaining_amount_in = hop_amount_in;
+
+            for (index, leg) in hop.legs.iter().enumerate() {
+                let pool = pools
+                    .iter()
+                    .find(|pool| pool.lb_pair_pubkey == leg.lb_pair)
+                    .context("Pair of the route not found")?;
+
+                if index == 0 {
+                    let output_mint_account = if leg.swap_for_y {
+                        &pool.mint_y_account
+                    } else {
+                        &pool.mint_x_account
+                    };
+
+                    instructions.push(create_associated_token_account_idempotent(
+                        &user,
+                        &user,
+                        &hop.output_mint,
+                        &output_mint_account.owner,
+                    ));
+                }
+
+                // The last leg takes the rounding remainder, so the legs swap the whole hop amount
+                let amount_in = if index == hop.legs.len() - 1 {
+                    remaining_amount_in
+                } else {
+                    scale_amount(leg.amount_in, hop_amount_in, hop.amount_in)?
+                };
+                remaining_amount_in = remaining_amount_in
+                    .checked_sub(amount_in)
+                    .context("MathOverflow")?;
+
+                let amount_out = scale_amount(leg.quote.amount_out, amount_in, leg.amount_in)?;
+                let min_amount_out = scale_amount(
+                    amount_out,
+                    (BASIS_POINT_MAX - i32::from(slippage_bps)) as u64,
+                    BASIS_POINT_MAX as u64,
+                )?;
+                hop_min_amount_out = hop_min_amount_out
+                    .checked_add(min_amount_out)
+                    .context("MathOverflow")?;
+
+                instructions.push(get_swap2_instruction(
+                    pool,
+                    leg,
+                    user,
+                    amount_in,
+                    min_amount_out,
+                )?);
+            }
+
+            hop_amount_in = hop_min_amount_out;
+        }
+
+        Ok(instructions)
+    }
+}
+
+/// `amount * numerator / denominator`, rounded down.
+#[cfg(feature = "spl")]
+fn scale_amount(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    ensure!(denominator > 0, "MathOverflow");
+
+    (u128::from(amount) * u128::from(numerator) / u128::from(denominator))
+        .try_into()
+        .context("MathOverflow")
+}
+
+#[cfg(feature = "spl")]
+fn has_transfer_hook(mint_account: &Account) -> Result<bool> {
+    if mint_account.owner == spl_token::ID {
+        return Ok(false);
+    }
+
+    let mint_state = StateWithExtensions::<Mint>::unpack(mint_account.data.as_ref())?;
+    Ok(transfer_hook::get_program_id(&mint_state).is_some())
+}
+
+#[cfg(feature = "spl")]
+fn get_swap2_instruction(
+    pool: &PoolSnapshot,
+    leg: &RouteLegQuote,
+    user: Pubkey,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<Instruction> {
+    let lb_pair = &pool.lb_pair;
+    let [token_x_program, token_y_program] = lb_pair.get_token_programs()?;
+
+    let user_token_x = get_associated_token_address_with_program_id(
+        &user,
+        &lb_pair.token_x_mint,
+        &token_x_program,
+    );
+    let user_token_y = get_associated_token_address_with_program_id(
+        &user,
+        &lb_pair.token_y_mint,
+        &token_y_program,
+    );
+
+    let (user_token_in, user_token_out) = if leg.swap_for_y {
+        (user_token_x, user_token_y)
+    } else {
+        (user_token_y, user_token_x)
+    };
+
+    ensure_no_transfer_hook(&pool.mint_x_account, &pool.mint_y_account)?;
+
+    instructions::swap2(
+        pool.lb_pair_pubkey,
+        lb_pair,
+        pool.bitmap_extension.is_some(),
+        user,
+        user_token_in,
+        user_token_out,
+        None,
+        &leg.bin_array_pubkeys,
+        None,
+        amount_in,
+        min_amount_out,
+    )
+}
+
+/// Swaps are sent without the transfer hook accounts, so pairs with transfer hook mints are not
+/// supported.
+#[cfg(feature = "spl")]
+pub(crate) fn ensure_no_transfer_hook(
+    mint_x_account: &Account,
+    mint_y_account: &Account,
+) -> Result<()> {
+    ensure!(
+        !has_transfer_hook(mint_x_account)? && !has_transfer_hook(mint_y_account)?,
+        "Transfer hook mint is not supported"
+    );
+    Ok(())
+}
@@ -1,5 +1,4 @@
 use crate::*;
-use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
 use async_trait::async_trait;
 use solana_sdk::{account::Account, pubkey::Pubkey};
 
@@ -13,13 +12,16 @@ pub trait RpcClientExtension {
 }
 
 #[async_trait]
-impl RpcClientExtension for RpcClient {
+impl<P: AccountProvider + ?Sized> RpcClientExtension for P {
     async fn get_account_and_deserialize<T>(
         &self,
         pubkey: &Pubkey,
         deserialize_fn: fn(Account) -> Result<T>,
     ) -> Result<T> {
-        let account = self.get_account(pubkey).await?;
+        let account = self
+            .get_account(pubkey)
+            .await?
+            .with_context(|| format!("AccountNotFound: pubkey={}", pubkey))?;
         let data = deserialize_fn(account)?;
         Ok(data)
     }
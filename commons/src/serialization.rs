@@ -0,0 +1,126 @@
+use crate::dlmm;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use solana_sdk::pubkey::Pubkey;
+
+/// Serde encoding of the field types of the program and SDK types. Pubkeys and 128 bits integers
+/// are encoded as strings so that JSON consumers don't lose precision.
+pub(crate) trait IdlSerde: Sized {
+    fn idl_serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    fn idl_deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+/// Serialize a value with its [`IdlSerde`] encoding.
+pub(crate) struct IdlSerialize<'a, T>(pub &'a T);
+
+impl<T: IdlSerde> Serialize for IdlSerialize<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.idl_serialize(serializer)
+    }
+}
+
+/// Deserialize a value with its [`IdlSerde`] encoding.
+pub(crate) struct IdlDeserialize<T>(pub T);
+
+impl<'de, T: IdlSerde> Deserialize<'de> for IdlDeserialize<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::idl_deserialize(deserializer).map(IdlDeserialize)
+    }
+}
+
+macro_rules! impl_idl_serde {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl IdlSerde for $ty {
+                fn idl_serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.serialize(serializer)
+                }
+
+                fn idl_deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    Self::deserialize(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_idl_serde_as_str {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl IdlSerde for $ty {
+                fn idl_serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+
+                fn idl_deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    String::deserialize(deserializer)?
+                        .parse()
+                        .map_err(D::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+impl_idl_serde!(bool, u8, u16, u32, u64, i8, i16, i32, i64);
+impl_idl_serde_as_str!(u128, i128, Pubkey);
+
+impl<T: IdlSerde, const N: usize> IdlSerde for [T; N] {
+    fn idl_serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(IdlSerialize))
+    }
+
+    fn idl_deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items = Vec::<T>::idl_deserialize(deserializer)?;
+        let len = items.len();
+        items
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(len, &N.to_string().as_str()))
+    }
+}
+
+impl<T: IdlSerde> IdlSerde for Vec<T> {
+    fn idl_serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(IdlSerialize))
+    }
+
+    fn idl_deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let items = Vec::<IdlDeserialize<T>>::deserialize(deserializer)?;
+        Ok(items.into_iter().map(|item| item.0).collect())
+    }
+}
+
+impl<T: IdlSerde> IdlSerde for Option<T> {
+    fn idl_serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Some(value) => serializer.serialize_some(&IdlSerialize(value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    fn idl_deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Option::<IdlDeserialize<T>>::deserialize(deserializer)?;
+        Ok(value.map(|value| value.0))
+    }
+}
+
+/// `#[serde(with = "...")]` module encoding pubkeys and 128 bits integers as strings, for fields
+/// of those types, possibly in arrays, vectors or options.
+pub(crate) mod string_encoded {
+    use super::*;
+
+    pub fn serialize<T: IdlSerde, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.idl_serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: IdlSerde, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::idl_deserialize(deserializer)
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/dlmm_serde.rs"));
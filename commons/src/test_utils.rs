@@ -0,0 +1,540 @@
+//! Synthetic pair accounts built from a declarative description, to test against deterministic
+//! pools without network access.
+
+use crate::*;
+use anchor_lang::Discriminator;
+use solana_sdk::{account::Account, clock::Clock, pubkey::Pubkey, rent::Rent, sysvar};
+use std::collections::{BTreeMap, HashMap};
+
+/// Bin array version of the pairs created by the program.
+const BIN_ARRAY_VERSION: u8 = 2;
+
+// The token accounts are written in the token program layouts, so the fixtures build without
+// `anchor-spl`, for `wasm32-unknown-unknown` tests.
+const TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+/// Length of the token account base state. Token 2022 mints with extensions are padded to it.
+const TOKEN_ACCOUNT_LEN: usize = 165;
+const MINT_ACCOUNT_TYPE: u8 = 1;
+const TOKEN_ACCOUNT_TYPE: u8 = 2;
+const TRANSFER_FEE_CONFIG_EXTENSION_TYPE: u16 = 1;
+const TRANSFER_FEE_AMOUNT_EXTENSION_TYPE: u16 = 2;
+
+/// Limit order liquidity of a bin. Bid orders hold token Y and are filled by X to Y swaps, ask
+/// orders hold token X and are filled by Y to X swaps.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LimitOrderFixture {
+    pub is_ask: bool,
+    pub open_order_amount: u64,
+    /// Remaining amount of the orders being processed, filled before the open orders.
+    pub processed_order_remaining_amount: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BinFixture {
+    pub bin_id: i32,
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub limit_order: Option<LimitOrderFixture>,
+}
+
+/// Fee parameters of the pair, see `StaticParameters`.
+#[derive(Debug, Clone, Copy)]
+pub struct FeeFixture {
+    pub base_factor: u16,
+    pub base_fee_power_factor: u8,
+    pub filter_period: u16,
+    pub decay_period: u16,
+    pub reduction_factor: u16,
+    pub variable_fee_control: u32,
+    pub max_volatility_accumulator: u32,
+    pub protocol_share: u16,
+    pub collect_fee_mode: CollectFeeMode,
+}
+
+impl Default for FeeFixture {
+    fn default() -> Self {
+        Self {
+            base_factor: 10_000,
+            base_fee_power_factor: 0,
+            filter_period: 30,
+            decay_period: 600,
+            reduction_factor: 5_000,
+            variable_fee_control: 40_000,
+            max_volatility_accumulator: 350_000,
+            protocol_share: 2_000,
+            collect_fee_mode: CollectFeeMode::InputOnly,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MintFixture {
+    pub address: Pubkey,
+    pub decimals: u8,
+    pub mint_authority: Option<Pubkey>,
+    pub is_token_2022: bool,
+    /// Transfer fee of a token 2022 mint, without maximum fee.
+    pub transfer_fee_bps: Option<u16>,
+}
+
+impl MintFixture {
+    pub fn new(address: Pubkey, decimals: u8) -> Self {
+        Self {
+            address,
+            decimals,
+            mint_authority: None,
+            is_token_2022: false,
+            transfer_fee_bps: None,
+        }
+    }
+
+    fn token_program(&self) -> Pubkey {
+        if self.is_token_2022 {
+            TOKEN_2022_PROGRAM_ID
+        } else {
+            TOKEN_PROGRAM_ID
+        }
+    }
+
+    fn build_mint_account(&self, supply: u64) -> Result<Account> {
+        ensure!(
+            self.is_token_2022 || self.transfer_fee_bps.is_none(),
+            "Transfer fee requires a token 2022 mint"
+        );
+
+        let mut data = vec![];
+        push_coption_pubkey(&mut data, self.mint_authority);
+        data.extend_from_slice(&supply.to_le_bytes());
+        data.push(self.decimals);
+        // Initialized, without freeze authority
+        data.push(1);
+        push_coption_pubkey(&mut data, None);
+
+        if let Some(transfer_fee_bps) = self.transfer_fee_bps {
+            // No fee config and withdraw authorities, nothing withheld, then the same older and
+            // newer transfer fee, without maximum fee
+            let mut transfer_fee_config = vec![0; 72];
+            for _ in 0..2 {
+                transfer_fee_config.extend_from_slice(&0u64.to_le_bytes());
+                transfer_fee_config.extend_from_slice(&u64::MAX.to_le_bytes());
+                transfer_fee_config.extend_from_slice(&transfer_fee_bps.to_le_bytes());
+            }
+            push_extension(
+                &mut data,
+                MINT_ACCOUNT_TYPE,
+                TRANSFER_FEE_CONFIG_EXTENSION_TYPE,
+                &transfer_fee_config,
+            );
+        }
+
+        Ok(rent_exempt_account(self.token_program(), data))
+    }
+
+    fn build_token_account(&self, owner: Pubkey, amount: u64) -> Result<Account> {
+        let mut data = vec![];
+        data.extend_from_slice(self.address.as_ref());
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(&amount.to_le_bytes());
+        push_coption_pubkey(&mut data, None);
+        // Initialized, not native, nothing delegated, without close authority
+        data.push(1);
+        data.extend_from_slice(&[0; 12]);
+        data.extend_from_slice(&0u64.to_le_bytes());
+        push_coption_pubkey(&mut data, None);
+
+        if self.transfer_fee_bps.is_some() {
+            push_extension(
+                &mut data,
+                TOKEN_ACCOUNT_TYPE,
+                TRANSFER_FEE_AMOUNT_EXTENSION_TYPE,
+                &0u64.to_le_bytes(),
+            );
+        }
+
+        Ok(rent_exempt_account(self.token_program(), data))
+    }
+}
+
+fn push_coption_pubkey(data: &mut Vec<u8>, pubkey: Option<Pubkey>) {
+    data.extend_from_slice(&u32::from(pubkey.is_some()).to_le_bytes());
+    data.extend_from_slice(pubkey.unwrap_or_default().as_ref());
+}
+
+/// Append a token 2022 extension to the base state of a mint or token account.
+fn push_extension(data: &mut Vec<u8>, account_type: u8, extension_type: u16, value: &[u8]) {
+    data.resize(TOKEN_ACCOUNT_LEN, 0);
+    data.push(account_type);
+    data.extend_from_slice(&extension_type.to_le_bytes());
+    data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    data.extend_from_slice(value);
+}
+
+/// Declarative description of a permissionless pair and its bins. `bins` are the bins with
+/// liquidity, their bin arrays are created and marked in the bitmap, with a bitmap extension
+/// when they are out of the range of the pair bitmap.
+#[derive(Debug, Clone)]
+pub struct PoolFixture {
+    pub bin_step: u16,
+    pub active_id: i32,
+    pub fee: FeeFixture,
+    pub volatility_accumulator: u32,
+    pub volatility_reference: u32,
+    pub last_update_timestamp: i64,
+    pub function_type: FunctionType,
+    pub token_x: MintFixture,
+    pub token_y: MintFixture,
+    pub bins: Vec<BinFixture>,
+    pub clock: Clock,
+}
+
+/// Accounts of a [`PoolFixture`], with their address.
+#[derive(Debug, Clone)]
+pub struct PoolFixtureAccounts {
+    pub lb_pair: (Pubkey, Account),
+    pub bin_arrays: Vec<(Pubkey, Account)>,
+    pub bitmap_extension: Option<(Pubkey, Account)>,
+    pub oracle: (Pubkey, Account),
+    pub reserve_x: (Pubkey, Account),
+    pub reserve_y: (Pubkey, Account),
+    pub mint_x: (Pubkey, Account),
+    pub mint_y: (Pubkey, Account),
+    pub clock: (Pubkey, Account),
+}
+
+fn rent_exempt_account(owner: Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn dlmm_account_data<T: Discriminator + bytemuck::Pod>(state: &T) -> Vec<u8> {
+    [T::DISCRIMINATOR, bytemuck::bytes_of(state)].concat()
+}
+
+fn set_bin_array_bit(
+    lb_pair: &mut LbPair,
+    bitmap_extension: &mut Option<BinArrayBitmapExtension>,
+    lb_pair_pubkey: Pubkey,
+    bin_array_index: i32,
+) -> Result<()> {
+    if !lb_pair.is_overflow_default_bin_array_bitmap(bin_array_index) {
+        let offset = LbPair::get_bin_array_offset(bin_array_index);
+        lb_pair.bin_array_bitmap[offset / 64] |= 1 << (offset % 64);
+        return Ok(());
+    }
+
+    let (min_bitmap_id, max_bitmap_id) = BinArrayBitmapExtension::bitmap_range();
+    ensure!(
+        bin_array_index >= min_bitmap_id && bin_array_index <= max_bitmap_id,
+        "Bin array {} out of the bitmap extension range",
+        bin_array_index
+    );
+
+    let bitmap_extension = bitmap_extension.get_or_insert_with(|| {
+        let mut bitmap_extension: BinArrayBitmapExtension = bytemuck::Zeroable::zeroed();
+        bitmap_extension.lb_pair = lb_pair_pubkey;
+        bitmap_extension
+    });
+    let offset = BinArrayBitmapExtension::get_bitmap_offset(bin_array_index)?;
+    let bit = BinArrayBitmapExtension::bin_array_offset_in_bitmap(bin_array_index)?;
+    let bitmap = if bin_array_index < 0 {
+        &mut bitmap_extension.negative_bin_array_bitmap[offset]
+    } else {
+        &mut bitmap_extension.positive_bin_array_bitmap[offset]
+    };
+    bitmap[bit / 64] |= 1 << (bit % 64);
+
+    Ok(())
+}
+
+impl PoolFixture {
+    /// Pair of two 6 and 9 decimals SPL tokens with the default fee parameters, and no bins.
+    pub fn new(bin_step: u16, active_id: i32) -> Self {
+        Self {
+            bin_step,
+            active_id,
+            fee: FeeFixture::default(),
+            volatility_accumulator: 0,
+            volatility_reference: 0,
+            last_update_timestamp: 0,
+            function_type: FunctionType::LimitOrder,
+            token_x: MintFixture::new(Pubkey::new_from_array([1; 32]), 6),
+            token_y: MintFixture::new(Pubkey::new_from_array([2; 32]), 9),
+            bins: vec![],
+            clock: Clock::default(),
+        }
+    }
+
+    /// Pair with `amount_x` in the active bin and the `bins_per_side` bins above it, and
+    /// `amount_y` in the active bin and the `bins_per_side` bins below it.
+    pub fn two_sided(
+        bin_step: u16,
+        active_id: i32,
+        bins_per_side: i32,
+        amount_x: u64,
+        amount_y: u64,
+    ) -> Self {
+        let mut fixture = Self::new(bin_step, active_id);
+        fixture.bins = (active_id - bins_per_side..=active_id + bins_per_side)
+            .map(|bin_id| BinFixture {
+                bin_id,
+                amount_x: if bin_id >= active_id { amount_x } else { 0 },
+                amount_y: if bin_id <= active_id { amount_y } else { 0 },
+                limit_order: None,
+            })
+            .collect();
+        fixture
+    }
+
+    /// Two sided pair around bin 0 with a transfer fee on token Y, a bid limit order below and an
+    /// ask limit order above the active bin, and a bin array tracked by the bitmap extension.
+    pub fn sample() -> Self {
+        let mut fixture = Self::two_sided(10, 0, 5, 1_000_000_000, 1_000_000_000);
+        fixture.token_y.is_token_2022 = true;
+        fixture.token_y.transfer_fee_bps = Some(150);
+        fixture.bins[2].limit_order = Some(LimitOrderFixture {
+            is_ask: false,
+            open_order_amount: 300_000_000,
+            processed_order_remaining_amount: 200_000_000,
+        });
+        fixture.bins[8].limit_order = Some(LimitOrderFixture {
+            is_ask: true,
+            open_order_amount: 500_000_000,
+            processed_order_remaining_amount: 0,
+        });
+        fixture.bins.push(BinFixture {
+            bin_id: -36_000,
+            amount_x: 0,
+            amount_y: 1_000_000_000,
+            limit_order: None,
+        });
+        fixture
+    }
+
+    /// Address of the pair, derived from its mints, bin step and base factor.
+    pub fn lb_pair(&self) -> Pubkey {
+        derive_lb_pair_pda2(
+            self.token_x.address,
+            self.token_y.address,
+            self.bin_step,
+            self.fee.base_factor,
+        )
+        .0
+    }
+
+    fn build_bin(&self, bin_fixture: &BinFixture) -> Result<Bin> {
+        let price = get_price_from_id(bin_fixture.bin_id, self.bin_step)?;
+        let liquidity_supply = price
+            .checked_mul(bin_fixture.amount_x.into())
+            .and_then(|liquidity_x| {
+                liquidity_x.checked_add(u128::from(bin_fixture.amount_y) << SCALE_OFFSET)
+            })
+            .context("MathOverflow")?;
+
+        let mut bin: Bin = bytemuck::Zeroable::zeroed();
+        bin.amount_x = bin_fixture.amount_x;
+        bin.amount_y = bin_fixture.amount_y;
+        bin.price = price;
+        bin.liquidity_supply = liquidity_supply;
+
+        if let Some(limit_order) = bin_fixture.limit_order {
+            bin.limit_order_ask_side = limit_order.is_ask.into();
+            bin.open_order_amount = limit_order.open_order_amount;
+            bin.processed_order_remaining_amount = limit_order.processed_order_remaining_amount;
+            bin.total_processing_order_amount = limit_order.processed_order_remaining_amount;
+        }
+
+        Ok(bin)
+    }
+
+    pub fn build(&self) -> Result<PoolFixtureAccounts> {
+        let (lb_pair_pubkey, bump) = derive_lb_pair_pda2(
+            self.token_x.address,
+            self.token_y.address,
+            self.bin_step,
+            self.fee.base_factor,
+        );
+        let (oracle, _bump) = derive_oracle_pda(lb_pair_pubkey);
+        let (reserve_x, _bump) = derive_reserve_pda(self.token_x.address, lb_pair_pubkey);
+        let (reserve_y, _bump) = derive_reserve_pda(self.token_y.address, lb_pair_pubkey);
+
+        let mut lb_pair: LbPair = bytemuck::Zeroable::zeroed();
+        lb_pair.parameters.base_factor = self.fee.base_factor;
+        lb_pair.parameters.base_fee_power_factor = self.fee.base_fee_power_factor;
+        lb_pair.parameters.filter_period = self.fee.filter_period;
+        lb_pair.parameters.decay_period = self.fee.decay_period;
+        lb_pair.parameters.reduction_factor = self.fee.reduction_factor;
+        lb_pair.parameters.variable_fee_control = self.fee.variable_fee_control;
+        lb_pair.parameters.max_volatility_accumulator = self.fee.max_volatility_accumulator;
+        lb_pair.parameters.min_bin_id = MIN_BIN_ID;
+        lb_pair.parameters.max_bin_id = MAX_BIN_ID;
+        lb_pair.parameters.protocol_share = self.fee.protocol_share;
+        lb_pair.parameters.function_type = self.function_type as u8;
+        lb_pair.parameters.collect_fee_mode = self.fee.collect_fee_mode as u8;
+        lb_pair.v_parameters.volatility_accumulator = self.volatility_accumulator;
+        lb_pair.v_parameters.volatility_reference = self.volatility_reference;
+        lb_pair.v_parameters.index_reference = self.active_id;
+        lb_pair.v_parameters.last_update_timestamp = self.last_update_timestamp;
+        lb_pair.bump_seed = [bump];
+        lb_pair.bin_step_seed = self.bin_step.to_le_bytes();
+        lb_pair.pair_type = PairType::Permissionless as u8;
+        lb_pair.active_id = self.active_id;
+        lb_pair.bin_step = self.bin_step;
+        lb_pair.require_base_factor_seed = 1;
+        lb_pair.base_factor_seed = self.fee.base_factor.to_le_bytes();
+        lb_pair.token_x_mint = self.token_x.address;
+        lb_pair.token_y_mint = self.token_y.address;
+        lb_pair.reserve_x = reserve_x;
+        lb_pair.reserve_y = reserve_y;
+        lb_pair.oracle = oracle;
+        lb_pair.token_mint_x_program_flag = self.token_x.is_token_2022.into();
+        lb_pair.token_mint_y_program_flag = self.token_y.is_token_2022.into();
+        lb_pair.version = 1;
+
+        let mut bin_arrays: BTreeMap<i32, BinArray> = BTreeMap::new();
+        let mut reserve_x_amount: u64 = 0;
+        let mut reserve_y_amount: u64 = 0;
+
+        for bin_fixture in self.bins.iter() {
+            let bin_array_index = BinArray::bin_id_to_bin_array_index(bin_fixture.bin_id)?;
+            let bin_array = bin_arrays.entry(bin_array_index).or_insert_with(|| {
+                let mut bin_array: BinArray = bytemuck::Zeroable::zeroed();
+                bin_array.index = bin_array_index.into();
+                bin_array.version = BIN_ARRAY_VERSION;
+                bin_array.lb_pair = lb_pair_pubkey;
+                bin_array
+            });
+            let bin = self.build_bin(bin_fixture)?;
+            *bin_array.get_bin_mut(bin_fixture.bin_id)? = bin;
+
+            let (limit_order_x, limit_order_y) = match bin_fixture.limit_order {
+                Some(limit_order) => {
+                    let amount = limit_order
+                        .open_order_amount
+                        .checked_add(limit_order.processed_order_remaining_amount)
+                        .context("MathOverflow")?;
+                    if limit_order.is_ask {
+                        (amount, 0)
+                    } else {
+                        (0, amount)
+                    }
+                }
+                None => (0, 0),
+            };
+            reserve_x_amount = reserve_x_amount
+                .checked_add(bin.amount_x)
+                .and_then(|amount| amount.checked_add(limit_order_x))
+                .context("MathOverflow")?;
+            reserve_y_amount = reserve_y_amount
+                .checked_add(bin.amount_y)
+                .and_then(|amount| amount.checked_add(limit_order_y))
+                .context("MathOverflow")?;
+        }
+
+        let mut bitmap_extension = None;
+        for &bin_array_index in bin_arrays.keys() {
+            set_bin_array_bit(
+                &mut lb_pair,
+                &mut bitmap_extension,
+                lb_pair_pubkey,
+                bin_array_index,
+            )?;
+        }
+
+        let oracle_state = dlmm::accounts::Oracle {
+            idx: 0,
+            active_size: 0,
+            length: DEFAULT_OBSERVATION_LENGTH,
+        };
+        let mut oracle_data = dlmm_account_data(&oracle_state);
+        oracle_data.resize(
+            ORACLE_METADATA_SIZE + DEFAULT_OBSERVATION_LENGTH as usize * OBSERVATION_SIZE,
+            0,
+        );
+
+        Ok(PoolFixtureAccounts {
+            lb_pair: (
+                lb_pair_pubkey,
+                rent_exempt_account(dlmm::ID, dlmm_account_data(&lb_pair)),
+            ),
+            bin_arrays: bin_arrays
+                .values()
+                .map(|bin_array| {
+                    (
+                        derive_bin_array_pda(lb_pair_pubkey, bin_array.index).0,
+                        rent_exempt_account(dlmm::ID, dlmm_account_data(bin_array)),
+                    )
+                })
+                .collect(),
+            bitmap_extension: bitmap_extension.map(|bitmap_extension| {
+                (
+                    derive_bin_array_bitmap_extension(lb_pair_pubkey).0,
+                    rent_exempt_account(dlmm::ID, dlmm_account_data(&bitmap_extension)),
+                )
+            }),
+            oracle: (oracle, rent_exempt_account(dlmm::ID, oracle_data)),
+            reserve_x: (
+                reserve_x,
+                self.token_x
+                    .build_token_account(lb_pair_pubkey, reserve_x_amount)?,
+            ),
+            reserve_y: (
+                reserve_y,
+                self.token_y
+                    .build_token_account(lb_pair_pubkey, reserve_y_amount)?,
+            ),
+            mint_x: (
+                self.token_x.address,
+                self.token_x.build_mint_account(reserve_x_amount)?,
+            ),
+            mint_y: (
+                self.token_y.address,
+                self.token_y.build_mint_account(reserve_y_amount)?,
+            ),
+            clock: (
+                sysvar::clock::ID,
+                rent_exempt_account(sysvar::ID, bincode::serialize(&self.clock)?),
+            ),
+        })
+    }
+}
+
+impl PoolFixtureAccounts {
+    /// Every account keyed by its address, clock sysvar included, for example for the in memory
+    /// `AccountProvider`.
+    pub fn to_account_map(&self) -> HashMap<Pubkey, Account> {
+        [
+            &self.lb_pair,
+            &self.oracle,
+            &self.reserve_x,
+            &self.reserve_y,
+            &self.mint_x,
+            &self.mint_y,
+            &self.clock,
+        ]
+        .into_iter()
+        .chain(self.bin_arrays.iter())
+        .chain(self.bitmap_extension.iter())
+        .cloned()
+        .collect()
+    }
+
+    pub fn to_pool_snapshot(&self) -> Result<PoolSnapshot> {
+        PoolSnapshot::from_accounts(
+            self.lb_pair.0,
+            &self.lb_pair.1,
+            self.bitmap_extension.as_ref().map(|(_, account)| account),
+            &self.clock.1,
+            self.mint_x.1.clone(),
+            self.mint_y.1.clone(),
+            &self.bin_arrays,
+        )
+    }
+}
@@ -1,23 +1,33 @@
 use crate::*;
-use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
-use anchor_client::solana_client::rpc_client::RpcClient as BlockingRpcClient;
+#[cfg(feature = "spl")]
 use anchor_spl::token_2022::spl_token_2022::extension;
+#[cfg(feature = "spl")]
 use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::*;
+#[cfg(feature = "rpc")]
+use anchor_spl::token_2022::spl_token_2022::{self, extension::transfer_hook};
+#[cfg(feature = "spl")]
 use anchor_spl::{token::spl_token, token_2022::spl_token_2022::extension::*};
 use solana_sdk::account::Account;
+#[cfg(feature = "rpc")]
 use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey};
+#[cfg(feature = "rpc")]
 use spl_transfer_hook_interface::offchain::add_extra_account_metas_for_execute;
 
+#[cfg(not(feature = "spl"))]
+pub use mint_extensions::{get_epoch_transfer_fee, TransferFee, MAX_FEE_BASIS_POINTS};
+
 const ONE_IN_BASIS_POINTS: u128 = MAX_FEE_BASIS_POINTS as u128;
 
+#[cfg(feature = "rpc")]
 pub enum ActionType {
     Liquidity,
     Reward(usize),
 }
 
-pub async fn get_potential_token_2022_related_ix_data_and_accounts(
+#[cfg(feature = "rpc")]
+pub async fn get_potential_token_2022_related_ix_data_and_accounts<P: AccountProvider + ?Sized>(
     lb_pair: &LbPair,
-    rpc_client: RpcClient,
+    account_provider: &P,
     action_type: ActionType,
 ) -> Result<Option<(Vec<RemainingAccountsSlice>, Vec<AccountMeta>)>> {
     let potential_token_2022_mints = match action_type {
@@ -40,8 +50,7 @@ pub async fn get_potential_token_2022_related_ix_data_and_accounts(
 
     for (mint, accounts_type) in potential_token_2022_mints {
         let extra_account_metas =
-            get_extra_account_metas_for_transfer_hook(mint, RpcClient::new(rpc_client.url()))
-                .await?;
+            get_extra_account_metas_for_transfer_hook(mint, account_provider).await?;
 
         if !extra_account_metas.is_empty() {
             slices.push(RemainingAccountsSlice {
@@ -60,44 +69,40 @@ pub async fn get_potential_token_2022_related_ix_data_and_accounts(
     }
 }
 
-pub async fn get_extra_account_metas_for_transfer_hook(
+#[cfg(feature = "rpc")]
+pub async fn get_extra_account_metas_for_transfer_hook<P: AccountProvider + ?Sized>(
     mint: Pubkey,
-    rpc_client: RpcClient,
+    account_provider: &P,
 ) -> Result<Vec<AccountMeta>> {
-    let mint_account = rpc_client.get_account(&mint).await?;
+    let mint_account = account_provider
+        .get_account(&mint)
+        .await?
+        .context("Failed to fetch mint account")?;
     if mint_account.owner.eq(&spl_token::ID) {
         return Ok(vec![]);
     }
 
     let mint_state =
-        StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Mint>::unpack(
-            mint_account.data.as_ref(),
-        )?;
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(mint_account.data.as_ref())?;
 
     if let Some(transfer_hook_program_id) = transfer_hook::get_program_id(&mint_state) {
-        let mut transfer_ix =
-            anchor_spl::token_2022::spl_token_2022::instruction::transfer_checked(
-                &mint_account.owner,
-                &Pubkey::default(),
-                &mint,
-                &Pubkey::default(),
-                &Pubkey::default(),
-                &[],
-                0,
-                mint_state.base.decimals,
-            )?;
-
-        let blocking_rpc_client = BlockingRpcClient::new(rpc_client.url());
-
-        let data_fetcher = |address: Pubkey| {
-            let account = blocking_rpc_client
+        let mut transfer_ix = spl_token_2022::instruction::transfer_checked(
+            &mint_account.owner,
+            &Pubkey::default(),
+            &mint,
+            &Pubkey::default(),
+            &Pubkey::default(),
+            &[],
+            0,
+            mint_state.base.decimals,
+        )?;
+
+        let data_fetcher = |address: Pubkey| async move {
+            account_provider
                 .get_account(&address)
-                .map(|account| account.data);
-            async move {
-                std::result::Result::Ok::<Option<Vec<u8>>, Box<dyn std::error::Error + Send + Sync>>(
-                    account.ok(),
-                )
-            }
+                .await
+                .map(|account| account.map(|account| account.data))
+                .map_err(Into::<Box<dyn std::error::Error + Send + Sync>>::into)
         };
 
         add_extra_account_metas_for_execute(
@@ -121,6 +126,7 @@ pub async fn get_extra_account_metas_for_transfer_hook(
     Ok(vec![])
 }
 
+#[cfg(feature = "spl")]
 pub fn get_epoch_transfer_fee(mint_account: &Account, epoch: u64) -> Result<Option<TransferFee>> {
     if mint_account.owner == spl_token::ID {
         return Ok(None);
@@ -244,3 +250,228 @@ pub fn calculate_inverse_fee(transfer_fee: &TransferFee, post_fee_amount: u64) -
     let pre_fee_amount = calculate_pre_fee_amount(transfer_fee, post_fee_amount)?;
     transfer_fee.calculate_fee(pre_fee_amount)
 }
+
+/// Transfer fee of token 2022 mints parsed from the mint extension data, for the builds without
+/// `spl-token-2022`. Mirrors the `spl-token-2022` transfer fee types.
+#[cfg(any(not(feature = "spl"), test))]
+mod mint_extensions {
+    use anyhow::{ensure, Context, Result};
+    use bytemuck::{Pod, Zeroable};
+    use solana_sdk::account::Account;
+
+    /// Maximum transfer fee of a token 2022 mint, in basis points.
+    pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+    const ONE_IN_BASIS_POINTS: u128 = MAX_FEE_BASIS_POINTS as u128;
+
+    /// Offset of the account type of a token 2022 account with extensions. The base state is
+    /// padded to the length of a token account.
+    const ACCOUNT_TYPE_OFFSET: usize = 165;
+    const MINT_ACCOUNT_TYPE: u8 = 1;
+    const TRANSFER_FEE_CONFIG_EXTENSION_TYPE: u16 = 1;
+    /// Length of the `TransferFeeConfig` extension: 2 authorities, the withheld amount, then the
+    /// older and newer transfer fees.
+    const TRANSFER_FEE_CONFIG_LEN: usize = 108;
+    const OLDER_TRANSFER_FEE_OFFSET: usize = 72;
+    const NEWER_TRANSFER_FEE_OFFSET: usize = 90;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+    #[repr(transparent)]
+    pub struct PodU16([u8; 2]);
+
+    impl From<PodU16> for u16 {
+        fn from(pod: PodU16) -> Self {
+            Self::from_le_bytes(pod.0)
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+    #[repr(transparent)]
+    pub struct PodU64([u8; 8]);
+
+    impl From<PodU64> for u64 {
+        fn from(pod: PodU64) -> Self {
+            Self::from_le_bytes(pod.0)
+        }
+    }
+
+    /// Transfer fee of a token 2022 mint, effective from `epoch`.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+    #[repr(C)]
+    pub struct TransferFee {
+        pub epoch: PodU64,
+        pub maximum_fee: PodU64,
+        pub transfer_fee_basis_points: PodU16,
+    }
+
+    impl TransferFee {
+        /// Fee charged on a transfer of `pre_fee_amount`, rounded up and capped at `maximum_fee`.
+        pub fn calculate_fee(&self, pre_fee_amount: u64) -> Option<u64> {
+            let transfer_fee_basis_points = u128::from(u16::from(self.transfer_fee_basis_points));
+            if transfer_fee_basis_points == 0 || pre_fee_amount == 0 {
+                return Some(0);
+            }
+
+            let numerator = u128::from(pre_fee_amount).checked_mul(transfer_fee_basis_points)?;
+            let raw_fee: u64 = numerator
+                .checked_add(ONE_IN_BASIS_POINTS)?
+                .checked_sub(1)?
+                .checked_div(ONE_IN_BASIS_POINTS)?
+                .try_into()
+                .ok()?;
+
+            Some(raw_fee.min(u64::from(self.maximum_fee)))
+        }
+    }
+
+    /// Value of the `extension_type` TLV entry of a token 2022 mint. `None` for mints without
+    /// extensions, which include all the token program mints.
+    fn get_mint_extension_data(mint_data: &[u8], extension_type: u16) -> Result<Option<&[u8]>> {
+        if mint_data.len() <= ACCOUNT_TYPE_OFFSET {
+            return Ok(None);
+        }
+        ensure!(
+            mint_data[ACCOUNT_TYPE_OFFSET] == MINT_ACCOUNT_TYPE,
+            "Invalid token 2022 mint data"
+        );
+
+        let mut tlv_data = &mint_data[ACCOUNT_TYPE_OFFSET + 1..];
+        while tlv_data.len() >= 4 {
+            let entry_type = u16::from_le_bytes([tlv_data[0], tlv_data[1]]);
+            let entry_len = usize::from(u16::from_le_bytes([tlv_data[2], tlv_data[3]]));
+            // Uninitialized entries mark the end of the extensions
+            if entry_type == 0 {
+                break;
+            }
+
+            let value = tlv_data
+                .get(4..4 + entry_len)
+                .context("Invalid token 2022 mint extension data")?;
+            if entry_type == extension_type {
+                return Ok(Some(value));
+            }
+            tlv_data = &tlv_data[4 + entry_len..];
+        }
+
+        Ok(None)
+    }
+
+    pub fn get_epoch_transfer_fee(
+        mint_account: &Account,
+        epoch: u64,
+    ) -> Result<Option<TransferFee>> {
+        let Some(transfer_fee_config) = get_mint_extension_data(
+            mint_account.data.as_ref(),
+            TRANSFER_FEE_CONFIG_EXTENSION_TYPE,
+        )?
+        else {
+            return Ok(None);
+        };
+        ensure!(
+            transfer_fee_config.len() == TRANSFER_FEE_CONFIG_LEN,
+            "Invalid transfer fee config"
+        );
+
+        let read_transfer_fee = |offset: usize| -> TransferFee {
+            bytemuck::pod_read_unaligned(
+                &transfer_fee_config[offset..offset + std::mem::size_of::<TransferFee>()],
+            )
+        };
+
+        let newer_transfer_fee = read_transfer_fee(NEWER_TRANSFER_FEE_OFFSET);
+        if epoch >= u64::from(newer_transfer_fee.epoch) {
+            Ok(Some(newer_transfer_fee))
+        } else {
+            Ok(Some(read_transfer_fee(OLDER_TRANSFER_FEE_OFFSET)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epoch_transfer_fee_matches_spl() {
+        use anchor_spl::token_2022::spl_token_2022::extension::{
+            mint_close_authority::MintCloseAuthority,
+            transfer_fee::{TransferFee, TransferFeeConfig},
+            BaseStateWithExtensions, BaseStateWithExtensionsMut, ExtensionType,
+            StateWithExtensions, StateWithExtensionsMut,
+        };
+        use anchor_spl::token_2022::spl_token_2022::{self, state::Mint};
+        use solana_sdk::program_pack::Pack;
+        use solana_sdk::pubkey::Pubkey;
+
+        let extension_types = [
+            ExtensionType::MintCloseAuthority,
+            ExtensionType::TransferFeeConfig,
+        ];
+        let len = ExtensionType::try_calculate_account_len::<Mint>(&extension_types).unwrap();
+        let mut data = vec![0; len];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        state
+            .init_extension::<MintCloseAuthority>(true)
+            .unwrap()
+            .close_authority = Some(Pubkey::new_unique()).try_into().unwrap();
+        let transfer_fee_config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+        transfer_fee_config.withheld_amount = 1_000.into();
+        transfer_fee_config.older_transfer_fee = TransferFee {
+            epoch: 10.into(),
+            maximum_fee: 5_000.into(),
+            transfer_fee_basis_points: 100.into(),
+        };
+        transfer_fee_config.newer_transfer_fee = TransferFee {
+            epoch: 20.into(),
+            maximum_fee: u64::MAX.into(),
+            transfer_fee_basis_points: 2_500.into(),
+        };
+        state.base.is_initialized = true;
+        state.pack_base();
+        state.init_account_type().unwrap();
+
+        let mint_account = solana_sdk::account::Account {
+            lamports: 1_000_000_000,
+            data: data.clone(),
+            owner: spl_token_2022::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        let state = StateWithExtensions::<Mint>::unpack(&data).unwrap();
+        let transfer_fee_config = state.get_extension::<TransferFeeConfig>().unwrap();
+
+        for epoch in [0, 10, 19, 20, 100] {
+            let expected = transfer_fee_config.get_epoch_fee(epoch);
+            let transfer_fee = mint_extensions::get_epoch_transfer_fee(&mint_account, epoch)
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(u64::from(transfer_fee.epoch), u64::from(expected.epoch));
+            assert_eq!(
+                u64::from(transfer_fee.maximum_fee),
+                u64::from(expected.maximum_fee)
+            );
+            assert_eq!(
+                u16::from(transfer_fee.transfer_fee_basis_points),
+                u16::from(expected.transfer_fee_basis_points)
+            );
+
+            for amount in [0, 1, 99, 10_000, 123_456_789, u64::MAX] {
+                assert_eq!(
+                    transfer_fee.calculate_fee(amount),
+                    expected.calculate_fee(amount)
+                );
+            }
+        }
+
+        let token_mint_account = solana_sdk::account::Account {
+            data: vec![0; spl_token::state::Mint::LEN],
+            owner: spl_token::ID,
+            ..mint_account
+        };
+        assert!(
+            mint_extensions::get_epoch_transfer_fee(&token_mint_account, 0)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -23,6 +23,16 @@ pub struct BinQuoteResult {
     pub amount_out: u64,
     /// Total trading fee
     pub fee: u64,
-    /// Protocol portion of the trading fee
+    /// Protocol portion of the trading fee, includes host fee
     pub protocol_fee: u64,
+    /// Host portion of the protocol fee
+    pub host_fee: u64,
+    /// Amount of input added to the MM liquidity (excludes trading fee)
+    pub mm_amount_in: u64,
+    /// Amount of MM liquidity consumed (before trading fee when fee_on_output)
+    pub mm_amount_out: u64,
+    /// Amount of processed limit order liquidity consumed
+    pub processed_order_amount_out: u64,
+    /// Amount of open limit order liquidity consumed
+    pub open_order_amount_out: u64,
 }
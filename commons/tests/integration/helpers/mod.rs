@@ -1 +1,3 @@
+pub mod spl_test_pair;
+pub mod swap_quote_test_pair;
 pub mod utils;
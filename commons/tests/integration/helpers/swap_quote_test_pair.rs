@@ -0,0 +1,264 @@
+use crate::*;
+use commons::dlmm::accounts::LbPair;
+use solana_sdk::signature::Keypair;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub const FIXTURE_FOLDER: &str = "9t3EyC9FweyL7PBWvKz3mrXg8B9fwFc9SK3QxM4ENqhd";
+
+pub struct SwapQuoteTestPair {
+    pub lb_pair: Pubkey,
+    pub reserve_x: Pubkey,
+    pub reserve_y: Pubkey,
+    pub token_x_mint: Pubkey,
+    pub token_y_mint: Pubkey,
+    pub oracle: Pubkey,
+    pub bin_array_1: Pubkey,
+    pub bin_array_2: Pubkey,
+    pub mint_authority: Keypair,
+}
+
+pub fn setup_swap_quote_test_pair() -> (ProgramTest, SwapQuoteTestPair) {
+    let mut test = ProgramTest::default();
+    test.prefer_bpf(true);
+    test.add_program("./tests/artifacts/lb_clmm_prod", dlmm::ID, None);
+
+    let lb_pair = Pubkey::from_str_const("9t3EyC9FweyL7PBWvKz3mrXg8B9fwFc9SK3QxM4ENqhd");
+    let reserve_x = Pubkey::from_str_const("DDVpGjEz7Ay6QSyCJX5FpJhnYYYFUJ5kNdwy3ZrQP9u6");
+    let reserve_y = Pubkey::from_str_const("9LqVUn45rtZqMmNSr7wSpJUy2Qqd6VE5q3QN7t7oJL8v");
+    let token_x_mint = Pubkey::from_str_const("BBZU4HYvY4qMGE5MbWsVxGweGBZJqGRsgH8tAEAKusNk");
+    let token_y_mint = Pubkey::from_str_const("31iVdsS8fkURXg737XQwYhXVAuGv2vNYHjyDiURStkaU");
+    let oracle = Pubkey::from_str_const("FviPunh9kSt1XRBdHiWd5FNdTe5MJmqGfSEQdNfHTQV4");
+    // bin_array_1 = index -1 (bins -70 to -1)
+    let bin_array_1 = Pubkey::from_str_const("338HBraHxVupeftangX6jySecbND4osxcJjjMSW7qmMs");
+    // bin_array_2 = index 0 (bins 0 to 69)
+    let bin_array_2 = Pubkey::from_str_const("28BX6QycwTKx3CqswpJQs7hJCmoUs469Qt4maKMdhgmQ");
+
+    let mint_authority = Keypair::new();
+
+    test.add_account_with_file_data(
+        lb_pair,
+        10 * LAMPORTS_PER_SOL,
+        dlmm::ID,
+        &format!("{FIXTURE_FOLDER}/lb_pair.bin"),
+    );
+
+    test.add_account_with_file_data(
+        oracle,
+        10 * LAMPORTS_PER_SOL,
+        dlmm::ID,
+        &format!("{FIXTURE_FOLDER}/oracle.bin"),
+    );
+
+    test.add_account_with_file_data(
+        bin_array_1,
+        10 * LAMPORTS_PER_SOL,
+        dlmm::ID,
+        &format!("{FIXTURE_FOLDER}/bin_array_1.bin"),
+    );
+
+    test.add_account_with_file_data(
+        bin_array_2,
+        10 * LAMPORTS_PER_SOL,
+        dlmm::ID,
+        &format!("{FIXTURE_FOLDER}/bin_array_2.bin"),
+    );
+
+    test.add_account_with_file_data(
+        reserve_x,
+        10 * LAMPORTS_PER_SOL,
+        spl_token::id(),
+        &format!("{FIXTURE_FOLDER}/reserve_x.bin"),
+    );
+
+    test.add_account_with_file_data(
+        reserve_y,
+        10 * LAMPORTS_PER_SOL,
+        spl_token::id(),
+        &format!("{FIXTURE_FOLDER}/reserve_y.bin"),
+    );
+
+    // Patch mint authority to our test keypair so we can mint tokens to users
+    for (mint_pubkey, filename) in [
+        (token_x_mint, "token_x_mint.bin"),
+        (token_y_mint, "token_y_mint.bin"),
+    ] {
+        let fixture_path = format!("tests/fixtures/{FIXTURE_FOLDER}/{filename}");
+        let mut mint_data = std::fs::read(&fixture_path).unwrap();
+        // SPL Mint layout: COption<Pubkey> tag(4) + pubkey(32) at offset 0
+        mint_data[0..4].copy_from_slice(&1u32.to_le_bytes());
+        mint_data[4..36].copy_from_slice(mint_authority.pubkey().as_ref());
+        test.add_account(
+            mint_pubkey,
+            solana_sdk::account::Account {
+                lamports: 10 * LAMPORTS_PER_SOL,
+                data: mint_data,
+                owner: spl_token::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    (
+        test,
+        SwapQuoteTestPair {
+            lb_pair,
+            reserve_x,
+            reserve_y,
+            token_x_mint,
+            token_y_mint,
+            oracle,
+            bin_array_1,
+            bin_array_2,
+            mint_authority,
+        },
+    )
+}
+
+pub async fn load_pool_snapshot(
+    banks_client: &mut BanksClient,
+    pair: &SwapQuoteTestPair,
+) -> commons::PoolSnapshot {
+    let lb_pair_account = fetch_account(banks_client, pair.lb_pair).await;
+    let clock_account = fetch_account(banks_client, solana_sdk::sysvar::clock::ID).await;
+    let mint_x_account = fetch_account(banks_client, pair.token_x_mint).await;
+    let mint_y_account = fetch_account(banks_client, pair.token_y_mint).await;
+
+    let mut bin_array_accounts = vec![];
+    for bin_array in [pair.bin_array_1, pair.bin_array_2] {
+        bin_array_accounts.push((bin_array, fetch_account(banks_client, bin_array).await));
+    }
+
+    commons::PoolSnapshot::from_accounts(
+        pair.lb_pair,
+        &lb_pair_account,
+        None,
+        &clock_account,
+        mint_x_account,
+        mint_y_account,
+        &bin_array_accounts,
+    )
+    .unwrap()
+}
+
+/// Accounts of the pair, the clock, the mints and the bin arrays, keyed by address.
+pub async fn fetch_pool_accounts(
+    banks_client: &mut BanksClient,
+    pair: &SwapQuoteTestPair,
+) -> HashMap<Pubkey, solana_sdk::account::Account> {
+    let mut accounts = HashMap::new();
+    for pubkey in [
+        pair.lb_pair,
+        solana_sdk::sysvar::clock::ID,
+        pair.token_x_mint,
+        pair.token_y_mint,
+        pair.bin_array_1,
+        pair.bin_array_2,
+    ] {
+        accounts.insert(pubkey, fetch_account(banks_client, pubkey).await);
+    }
+    accounts
+}
+
+pub struct StrategyDepositTest {
+    pub banks_client: BanksClient,
+    pub payer: Rc<Keypair>,
+    pub pair: SwapQuoteTestPair,
+    pub lb_pair_state: LbPair,
+    pub user_token_x: Pubkey,
+    pub user_token_y: Pubkey,
+    pub position: Keypair,
+}
+
+/// Activated pair, with a funded user owning an empty position covering 10 bins on each side of
+/// the active bin.
+pub async fn setup_strategy_deposit_test() -> StrategyDepositTest {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let payer = Rc::new(ctx.payer);
+    let mut banks_client = ctx.banks_client;
+
+    let user_token_x = get_or_create_ata(
+        &payer,
+        &pair.token_x_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_y = get_or_create_ata(
+        &payer,
+        &pair.token_y_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    for (mint, user_token) in [
+        (pair.token_x_mint, user_token_x),
+        (pair.token_y_mint, user_token_y),
+    ] {
+        mint_spl_tokens(
+            &payer,
+            &mint,
+            &user_token,
+            &pair.mint_authority,
+            100_000_000_000,
+            &mut banks_client,
+        )
+        .await;
+    }
+
+    let position = Keypair::new();
+    let init_position_ix = instructions::initialize_position(
+        pair.lb_pair,
+        payer.pubkey(),
+        position.pubkey(),
+        payer.pubkey(),
+        lb_pair_state.active_id - 10,
+        21,
+    )
+    .unwrap();
+    process_and_assert_ok(
+        &[init_position_ix],
+        &payer,
+        &[&*payer, &position],
+        &mut banks_client,
+    )
+    .await;
+
+    StrategyDepositTest {
+        banks_client,
+        payer,
+        pair,
+        lb_pair_state,
+        user_token_x,
+        user_token_y,
+        position,
+    }
+}
+
+/// Accounts read by `DlmmClient` on the test pair, with the position of the user.
+pub async fn fetch_dlmm_client_accounts(
+    banks_client: &mut BanksClient,
+    pair: &SwapQuoteTestPair,
+    position: Pubkey,
+) -> HashMap<Pubkey, solana_sdk::account::Account> {
+    let mut accounts = HashMap::new();
+    for pubkey in [
+        pair.lb_pair,
+        pair.token_x_mint,
+        pair.token_y_mint,
+        pair.bin_array_1,
+        pair.bin_array_2,
+        solana_sdk::sysvar::clock::ID,
+        position,
+    ] {
+        if let Some(account) = banks_client.get_account(pubkey).await.unwrap() {
+            accounts.insert(pubkey, account);
+        }
+    }
+    accounts
+}
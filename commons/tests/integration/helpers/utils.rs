@@ -202,3 +202,28 @@ pub async fn fetch_swap_state(
         clock,
     )
 }
+
+/// Add the accounts of a synthetic pair to the program test, the clock sysvar excepted.
+pub fn add_pool_fixture_accounts(
+    test: &mut solana_program_test::ProgramTest,
+    accounts: &commons::test_utils::PoolFixtureAccounts,
+) {
+    for (pubkey, account) in accounts.to_account_map() {
+        if pubkey != solana_sdk::sysvar::clock::ID {
+            test.add_account(pubkey, account);
+        }
+    }
+}
+
+/// Snapshot of a synthetic pair, loaded from its accounts on chain.
+pub async fn fetch_pool_fixture_snapshot(
+    banks_client: &mut BanksClient,
+    accounts: &commons::test_utils::PoolFixtureAccounts,
+) -> commons::PoolSnapshot {
+    let mut account_map = HashMap::new();
+    for pubkey in accounts.to_account_map().into_keys() {
+        account_map.insert(pubkey, fetch_account(banks_client, pubkey).await);
+    }
+
+    commons::PoolSnapshot::from_account_map(accounts.lb_pair.0, &account_map).unwrap()
+}
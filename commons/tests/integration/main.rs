@@ -1,4 +1,19 @@
 mod helpers;
+mod test_account_provider;
+mod test_amm;
+mod test_dlmm_account;
+mod test_dlmm_client;
+mod test_errors;
+mod test_events;
+mod test_instructions;
+mod test_liquidity_strategy;
+mod test_oracle;
+#[cfg(feature = "test-utils")]
+mod test_pool_fixture;
+mod test_pool_snapshot;
+mod test_routing;
+#[cfg(feature = "serde")]
+mod test_serde;
 mod test_swap;
 mod test_swap_quote_with_limit_order;
 mod test_swap_token2022;
@@ -8,6 +23,8 @@ use anchor_spl::token::spl_token;
 use anchor_spl::token_2022::spl_token_2022;
 use commons::dlmm::types::*;
 use commons::*;
+use helpers::spl_test_pair::*;
+use helpers::swap_quote_test_pair::*;
 use helpers::utils::*;
 use solana_program_test::*;
 use solana_sdk::instruction::{AccountMeta, Instruction};
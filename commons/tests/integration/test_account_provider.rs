@@ -0,0 +1,80 @@
+use crate::*;
+
+#[tokio::test]
+async fn test_pool_snapshot_load_from_account_map() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+
+    let expected = load_pool_snapshot(&mut ctx.banks_client, &pair).await;
+    let account_map = fetch_pool_accounts(&mut ctx.banks_client, &pair).await;
+
+    let pool = PoolSnapshot::load(&account_map, pair.lb_pair, 3)
+        .await
+        .unwrap();
+
+    assert_eq!(pool.bin_arrays.len(), 2);
+    assert!(pool.bitmap_extension.is_none());
+    assert_eq!(pool.clock.slot, expected.clock.slot);
+
+    for swap_for_y in [true, false] {
+        let amount_in = 10_000_000_000;
+        let quote = pool
+            .quote_exact_in(amount_in, swap_for_y, QuoteOptions::default())
+            .unwrap();
+        let expected_quote = expected
+            .quote_exact_in(amount_in, swap_for_y, QuoteOptions::default())
+            .unwrap();
+        assert_eq!(quote.amount_out, expected_quote.amount_out);
+        assert_eq!(quote.fee, expected_quote.fee);
+    }
+
+    // Mints without transfer hook need no extra accounts
+    let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+        &pool.lb_pair,
+        &account_map,
+        ActionType::Liquidity,
+    )
+    .await
+    .unwrap();
+    assert!(transfer_hook_accounts.is_none());
+
+    let mut stale_pool = pool.clone();
+    stale_pool.bin_arrays.clear();
+    stale_pool.refresh(&account_map, 3).await.unwrap();
+    assert_eq!(stale_pool.bin_arrays.len(), 2);
+
+    let missing = Pubkey::new_unique();
+    assert!(PoolSnapshot::load(&account_map, missing, 3).await.is_err());
+}
+
+#[cfg(feature = "litesvm")]
+#[tokio::test]
+async fn test_pool_snapshot_load_from_litesvm() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+
+    let account_map = fetch_pool_accounts(&mut ctx.banks_client, &pair).await;
+    let mut svm = litesvm::LiteSVM::new();
+    for (pubkey, account) in account_map.iter() {
+        svm.set_account(*pubkey, account.clone()).unwrap();
+    }
+
+    let pool = PoolSnapshot::load(&svm, pair.lb_pair, 3).await.unwrap();
+    let expected = PoolSnapshot::load(&account_map, pair.lb_pair, 3)
+        .await
+        .unwrap();
+
+    let quote = pool
+        .quote_exact_in(10_000_000_000, true, QuoteOptions::default())
+        .unwrap();
+    let expected_quote = expected
+        .quote_exact_in(10_000_000_000, true, QuoteOptions::default())
+        .unwrap();
+    assert_eq!(quote.amount_out, expected_quote.amount_out);
+}
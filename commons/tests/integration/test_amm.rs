@@ -0,0 +1,185 @@
+use crate::*;
+use std::rc::Rc;
+
+async fn update_amm(amm: &mut impl commons::amm::Amm, banks_client: &mut BanksClient) {
+    let mut account_map = commons::amm::AccountMap::new();
+    for key in amm.get_accounts_to_update() {
+        if let Some(account) = banks_client.get_account(key).await.unwrap() {
+            account_map.insert(key, account);
+        }
+    }
+    amm.update(&account_map).unwrap();
+}
+
+#[tokio::test]
+async fn test_amm_quote_and_swap() {
+    use commons::amm::*;
+
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let payer = Rc::new(ctx.payer);
+    let mut banks_client = ctx.banks_client;
+
+    let user_token_x = get_or_create_ata(
+        &payer,
+        &pair.token_x_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_y = get_or_create_ata(
+        &payer,
+        &pair.token_y_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+
+    let mut amm = DlmmAmm::from_keyed_account(&KeyedAccount {
+        key: pair.lb_pair,
+        account: fetch_account(&mut banks_client, pair.lb_pair).await,
+    })
+    .unwrap();
+    assert_eq!(
+        amm.get_reserve_mints(),
+        vec![pair.token_x_mint, pair.token_y_mint]
+    );
+    update_amm(&mut amm, &mut banks_client).await;
+
+    for (swap_mode, input_mint, output_mint, amount) in [
+        (
+            SwapMode::ExactIn,
+            pair.token_x_mint,
+            pair.token_y_mint,
+            40_000_000_000,
+        ),
+        (
+            SwapMode::ExactOut,
+            pair.token_y_mint,
+            pair.token_x_mint,
+            10_000_000_000,
+        ),
+    ] {
+        let swap_for_y = input_mint == pair.token_x_mint;
+
+        let (lb_pair_state, bin_arrays, mint_x_account, mint_y_account, clock) = fetch_swap_state(
+            &mut banks_client,
+            pair.lb_pair,
+            &[pair.bin_array_1, pair.bin_array_2],
+        )
+        .await;
+
+        let quote = amm
+            .quote(&QuoteParams {
+                amount,
+                input_mint,
+                output_mint,
+                swap_mode,
+            })
+            .unwrap();
+
+        let (in_amount, out_amount, fee) = match swap_mode {
+            SwapMode::ExactIn => {
+                let quote_result = commons::quote::quote_exact_in(
+                    pair.lb_pair,
+                    &lb_pair_state,
+                    amount,
+                    swap_for_y,
+                    QuoteOptions::default(),
+                    bin_arrays.clone(),
+                    None,
+                    &clock,
+                    &mint_x_account,
+                    &mint_y_account,
+                )
+                .unwrap();
+                (amount, quote_result.amount_out, quote_result.fee)
+            }
+            SwapMode::ExactOut => {
+                let quote_result = commons::quote::quote_exact_out(
+                    pair.lb_pair,
+                    &lb_pair_state,
+                    amount,
+                    swap_for_y,
+                    QuoteOptions::default(),
+                    bin_arrays.clone(),
+                    None,
+                    &clock,
+                    &mint_x_account,
+                    &mint_y_account,
+                )
+                .unwrap();
+                (quote_result.amount_in, amount, quote_result.fee)
+            }
+        };
+
+        assert_eq!(quote.in_amount, in_amount);
+        assert_eq!(quote.out_amount, out_amount);
+        assert_eq!(quote.fee_amount, fee);
+        // Trading fee of the pair is always charged in token Y
+        assert_eq!(quote.fee_mint, pair.token_y_mint);
+
+        let (source_token_account, destination_token_account) = if swap_for_y {
+            (user_token_x, user_token_y)
+        } else {
+            (user_token_y, user_token_x)
+        };
+
+        mint_spl_tokens(
+            &payer,
+            &input_mint,
+            &source_token_account,
+            &pair.mint_authority,
+            quote.in_amount,
+            &mut banks_client,
+        )
+        .await;
+
+        let SwapAndAccountMetas {
+            data,
+            account_metas,
+        } = amm
+            .get_swap_and_account_metas(&SwapParams {
+                swap_mode,
+                in_amount: quote.in_amount,
+                out_amount: quote.out_amount,
+                source_mint: input_mint,
+                destination_mint: output_mint,
+                source_token_account,
+                destination_token_account,
+                token_transfer_authority: payer.pubkey(),
+            })
+            .unwrap();
+
+        let source_state_before =
+            fetch_token_account_state(&mut banks_client, source_token_account).await;
+        let destination_state_before =
+            fetch_token_account_state(&mut banks_client, destination_token_account).await;
+
+        let swap_ix = Instruction {
+            program_id: amm.program_id(),
+            accounts: account_metas,
+            data,
+        };
+        process_and_assert_ok(&[swap_ix], &payer, &[&*payer], &mut banks_client).await;
+
+        let destination_state_after =
+            fetch_token_account_state(&mut banks_client, destination_token_account).await;
+        assert_eq!(
+            destination_state_after.amount - destination_state_before.amount,
+            quote.out_amount
+        );
+
+        let source_state_after =
+            fetch_token_account_state(&mut banks_client, source_token_account).await;
+        assert_eq!(
+            source_state_before.amount - source_state_after.amount,
+            quote.in_amount
+        );
+
+        update_amm(&mut amm, &mut banks_client).await;
+    }
+}
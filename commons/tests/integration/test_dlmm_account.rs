@@ -0,0 +1,28 @@
+use crate::*;
+
+#[tokio::test]
+async fn test_decode_dlmm_account() {
+    let StrategyDepositTest {
+        mut banks_client,
+        pair,
+        lb_pair_state,
+        position,
+        ..
+    } = setup_strategy_deposit_test().await;
+
+    let accounts = fetch_dlmm_client_accounts(&mut banks_client, &pair, position.pubkey()).await;
+
+    let DlmmAccount::LbPair(decoded_lb_pair) =
+        DlmmAccount::decode(&accounts[&pair.lb_pair].data).unwrap()
+    else {
+        panic!("LbPair not decoded");
+    };
+    assert_eq!(decoded_lb_pair.active_id, lb_pair_state.active_id);
+    assert_eq!(
+        DlmmAccount::decode(&accounts[&pair.bin_array_1].data)
+            .unwrap()
+            .name(),
+        "BinArray"
+    );
+    assert!(DlmmAccount::decode(&accounts[&pair.token_x_mint].data).is_err());
+}
@@ -0,0 +1,159 @@
+use crate::*;
+use commons::dlmm::accounts::BinArray;
+use solana_sdk::signature::Keypair;
+
+async fn sign_and_process_transaction(
+    banks_client: &mut BanksClient,
+    mut transaction: solana_sdk::transaction::Transaction,
+    signers: &[&Keypair],
+) {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    transaction.sign(signers, recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+}
+
+/// Deposit, read the bins and the position, swap, then withdraw, claim and close the position
+/// with the transactions of `DlmmClient`.
+#[tokio::test]
+async fn test_dlmm_client() {
+    let StrategyDepositTest {
+        mut banks_client,
+        payer,
+        pair,
+        lb_pair_state,
+        user_token_x,
+        user_token_y,
+        position,
+    } = setup_strategy_deposit_test().await;
+
+    let accounts = fetch_dlmm_client_accounts(&mut banks_client, &pair, position.pubkey()).await;
+    let mut client = DlmmClient::create(accounts, pair.lb_pair).await.unwrap();
+    assert_eq!(
+        client.get_user_token_accounts(payer.pubkey()).unwrap(),
+        [user_token_x, user_token_y]
+    );
+
+    let active_id = lb_pair_state.active_id;
+    let deposit = StrategyDeposit::new(
+        LiquidityStrategy::Spot,
+        active_id,
+        lb_pair_state.bin_step,
+        active_id - 10,
+        active_id + 10,
+        10_000_000_000,
+        10_000_000_000,
+        false,
+    )
+    .unwrap();
+    let add_liquidity_tx = client
+        .add_liquidity_by_strategy(position.pubkey(), payer.pubkey(), &deposit, 0)
+        .await
+        .unwrap();
+    sign_and_process_transaction(&mut banks_client, add_liquidity_tx, &[&*payer]).await;
+
+    client.account_provider =
+        fetch_dlmm_client_accounts(&mut banks_client, &pair, position.pubkey()).await;
+    client.refetch_states().await.unwrap();
+
+    let bins = client.get_bins_around_active_bin(10, 10).await.unwrap();
+    assert_eq!(bins.len(), 21);
+    assert_eq!(bins[10], client.get_active_bin().await.unwrap());
+    for bin in bins.iter() {
+        let bin_array_pubkey = if bin.bin_id < 0 {
+            pair.bin_array_1
+        } else {
+            pair.bin_array_2
+        };
+        let bin_array: BinArray = pod_read_unaligned_skip_disc(
+            &fetch_account(&mut banks_client, bin_array_pubkey)
+                .await
+                .data,
+        )
+        .unwrap();
+        let expected_bin = bin_array.get_bin(bin.bin_id).unwrap();
+        assert_eq!(bin.amount_x, expected_bin.amount_x);
+        assert_eq!(bin.amount_y, expected_bin.amount_y);
+        assert_eq!(
+            bin.price,
+            get_price_from_id(bin.bin_id, lb_pair_state.bin_step).unwrap()
+        );
+    }
+
+    let positions = client.get_positions_by_user(payer.pubkey()).await.unwrap();
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].pubkey, position.pubkey());
+    let (deposit_x, deposit_y) = deposit.get_total_amounts();
+    assert!(positions[0].position_data.total_x_amount <= deposit_x);
+    assert!(deposit_y - positions[0].position_data.total_y_amount <= 21);
+    assert!(client
+        .get_positions_by_user(Pubkey::new_unique())
+        .await
+        .unwrap()
+        .is_empty());
+
+    let user_y_before = fetch_token_account_state(&mut banks_client, user_token_y)
+        .await
+        .amount;
+    let amount_in = 1_000_000_000;
+    let quote = client
+        .swap_quote(
+            amount_in,
+            true,
+            QuoteOptions {
+                user: Some(payer.pubkey()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let swap_tx = client
+        .swap(payer.pubkey(), amount_in, quote.amount_out, true)
+        .await
+        .unwrap();
+    sign_and_process_transaction(&mut banks_client, swap_tx, &[&*payer]).await;
+    let user_y_after = fetch_token_account_state(&mut banks_client, user_token_y)
+        .await
+        .amount;
+    assert_eq!(user_y_after - user_y_before, quote.amount_out);
+
+    client.account_provider =
+        fetch_dlmm_client_accounts(&mut banks_client, &pair, position.pubkey()).await;
+    client.refetch_states().await.unwrap();
+    let lb_position = client.get_position(position.pubkey()).await.unwrap();
+    assert!(lb_position.position_data.fee_x + lb_position.position_data.fee_y > 0);
+
+    let user_x_before = fetch_token_account_state(&mut banks_client, user_token_x)
+        .await
+        .amount;
+    let user_y_before = fetch_token_account_state(&mut banks_client, user_token_y)
+        .await
+        .amount;
+    let remove_liquidity_txs = client
+        .remove_liquidity(payer.pubkey(), &lb_position, BASIS_POINT_MAX as u16, true)
+        .await
+        .unwrap();
+    assert_eq!(remove_liquidity_txs.len(), 1);
+    for transaction in remove_liquidity_txs {
+        sign_and_process_transaction(&mut banks_client, transaction, &[&*payer]).await;
+    }
+
+    assert!(banks_client
+        .get_account(position.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+    let user_x_after = fetch_token_account_state(&mut banks_client, user_token_x)
+        .await
+        .amount;
+    let user_y_after = fetch_token_account_state(&mut banks_client, user_token_y)
+        .await
+        .amount;
+    // Withdrawn amounts and claimed fees
+    assert_eq!(
+        user_x_after - user_x_before,
+        lb_position.position_data.total_x_amount + lb_position.position_data.fee_x
+    );
+    assert_eq!(
+        user_y_after - user_y_before,
+        lb_position.position_data.total_y_amount + lb_position.position_data.fee_y
+    );
+}
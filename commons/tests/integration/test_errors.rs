@@ -0,0 +1,88 @@
+use crate::*;
+
+#[tokio::test]
+async fn test_dlmm_error() {
+    let StrategyDepositTest {
+        banks_client,
+        payer,
+        pair,
+        lb_pair_state,
+        user_token_x,
+        user_token_y,
+        position,
+    } = setup_strategy_deposit_test().await;
+
+    let active_id = lb_pair_state.active_id;
+    let deposit = StrategyDeposit::new(
+        LiquidityStrategy::Spot,
+        active_id,
+        lb_pair_state.bin_step,
+        active_id - 10,
+        active_id + 10,
+        10_000_000_000,
+        10_000_000_000,
+        false,
+    )
+    .unwrap();
+    let add_liquidity_ix = instructions::add_liquidity_by_strategy2(
+        position.pubkey(),
+        pair.lb_pair,
+        &lb_pair_state,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        None,
+        deposit.get_liquidity_parameter_by_strategy(0),
+    )
+    .unwrap();
+    let swap_ix = instructions::swap2(
+        pair.lb_pair,
+        &lb_pair_state,
+        false,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        None,
+        &[pair.bin_array_2, pair.bin_array_1],
+        None,
+        1_000_000_000,
+        u64::MAX,
+    )
+    .unwrap();
+
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+        &[add_liquidity_ix, swap_ix],
+        Some(&payer.pubkey()),
+        &[&*payer],
+        recent_blockhash,
+    );
+    let simulation = banks_client.simulate_transaction(tx).await.unwrap();
+    let Some(Err(tx_error)) = simulation.result else {
+        panic!("Unexpected simulation result {:?}", simulation.result);
+    };
+    let logs = simulation.simulation_details.unwrap().logs;
+
+    assert_eq!(
+        get_dlmm_error_from_transaction_error(&tx_error),
+        Some(DlmmError::ExceededAmountSlippageTolerance)
+    );
+    assert_eq!(
+        get_dlmm_error_from_logs(&logs),
+        Some(DlmmError::ExceededAmountSlippageTolerance)
+    );
+
+    let error = add_dlmm_error_context(anyhow::Error::from(tx_error));
+    assert_eq!(
+        get_dlmm_error(&error),
+        Some(DlmmError::ExceededAmountSlippageTolerance)
+    );
+    assert_eq!(
+        error.to_string(),
+        "ExceededAmountSlippageTolerance: Exceeded amount slippage tolerance"
+    );
+
+    for dlmm_error in DlmmError::ALL {
+        assert_eq!(DlmmError::from_code(dlmm_error.code()), Some(dlmm_error));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::*;
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+/// Decode the self CPI events of a deposit and a swap, and the same events from logs.
+#[tokio::test]
+async fn test_parse_events() {
+    let StrategyDepositTest {
+        banks_client,
+        payer,
+        pair,
+        lb_pair_state,
+        user_token_x,
+        user_token_y,
+        position,
+    } = setup_strategy_deposit_test().await;
+
+    let active_id = lb_pair_state.active_id;
+    let deposit = StrategyDeposit::new(
+        LiquidityStrategy::Spot,
+        active_id,
+        lb_pair_state.bin_step,
+        active_id - 10,
+        active_id + 10,
+        10_000_000_000,
+        10_000_000_000,
+        false,
+    )
+    .unwrap();
+    let add_liquidity_ix = instructions::add_liquidity_by_strategy2(
+        position.pubkey(),
+        pair.lb_pair,
+        &lb_pair_state,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        None,
+        deposit.get_liquidity_parameter_by_strategy(0),
+    )
+    .unwrap();
+    let amount_in = 1_000_000_000;
+    let swap_ix = instructions::swap2(
+        pair.lb_pair,
+        &lb_pair_state,
+        false,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        None,
+        &[pair.bin_array_2, pair.bin_array_1],
+        None,
+        amount_in,
+        0,
+    )
+    .unwrap();
+
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+        &[add_liquidity_ix, swap_ix],
+        Some(&payer.pubkey()),
+        &[&*payer],
+        recent_blockhash,
+    );
+    let simulation = banks_client.simulate_transaction(tx.clone()).await.unwrap();
+    assert_eq!(simulation.result, Some(Ok(())));
+    let simulation_details = simulation.simulation_details.unwrap();
+    let inner_instructions = simulation_details.inner_instructions.unwrap();
+
+    let events = parse_events_from_instructions(
+        &tx.message.account_keys,
+        inner_instructions
+            .iter()
+            .flatten()
+            .map(|inner_ix| &inner_ix.instruction),
+    )
+    .unwrap();
+    let [DlmmEvent::AddLiquidity(add_liquidity_event), DlmmEvent::Swap(swap_event), DlmmEvent::Swap2Evt(swap2_event)] =
+        events.as_slice()
+    else {
+        panic!("Unexpected events {:?}", events);
+    };
+    assert_eq!(add_liquidity_event.position, position.pubkey());
+    assert_eq!(
+        add_liquidity_event.amounts[1],
+        deposit.get_total_amounts().1
+    );
+    assert_eq!(swap_event.amount_in, amount_in);
+    assert_eq!(swap2_event.amount_in, amount_in);
+    assert_eq!(swap2_event.amount_out, swap_event.amount_out);
+
+    // Events emitted in logs, only `Program data:` of the DLMM program are decoded
+    let program_data_log =
+        |data: Vec<u8>| format!("Program data: {}", BASE64_STANDARD.encode(data));
+    let logs = [
+        format!("Program {} invoke [1]", dlmm::ID),
+        "Program log: Instruction: Swap2".to_string(),
+        program_data_log(anchor_lang::Event::data(swap_event)),
+        format!("Program {} invoke [2]", spl_token::ID),
+        program_data_log(anchor_lang::Event::data(swap_event)),
+        format!("Program {} success", spl_token::ID),
+        program_data_log(anchor_lang::Event::data(swap2_event)),
+        format!("Program {} consumed 100 of 200 compute units", dlmm::ID),
+        format!("Program {} success", dlmm::ID),
+        program_data_log(anchor_lang::Event::data(swap_event)),
+    ];
+    let log_events = parse_events_from_logs(&logs).unwrap();
+    assert_matches::assert_matches!(
+        log_events.as_slice(),
+        [DlmmEvent::Swap(_), DlmmEvent::Swap2Evt(_)]
+    );
+}
@@ -0,0 +1,484 @@
+use crate::*;
+use commons::dlmm::accounts::{LbPair, PositionV2};
+use commons::test_utils::*;
+use solana_sdk::{program_pack::Pack, signature::Keypair};
+use std::collections::HashMap;
+
+/// Deposit, claim the fees, withdraw everything and close the position with the instruction
+/// builders.
+#[tokio::test]
+async fn test_position_lifecycle_instructions() {
+    let StrategyDepositTest {
+        mut banks_client,
+        payer,
+        pair,
+        lb_pair_state,
+        user_token_x,
+        user_token_y,
+        position,
+    } = setup_strategy_deposit_test().await;
+
+    let active_id = lb_pair_state.active_id;
+    let user_x_before = fetch_token_account_state(&mut banks_client, user_token_x)
+        .await
+        .amount;
+    let user_y_before = fetch_token_account_state(&mut banks_client, user_token_y)
+        .await
+        .amount;
+
+    let deposit = StrategyDeposit::new(
+        LiquidityStrategy::Spot,
+        active_id,
+        lb_pair_state.bin_step,
+        active_id - 10,
+        active_id + 10,
+        10_000_000_000,
+        10_000_000_000,
+        false,
+    )
+    .unwrap();
+    let add_liquidity_ix = instructions::add_liquidity_by_strategy2(
+        position.pubkey(),
+        pair.lb_pair,
+        &lb_pair_state,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        None,
+        deposit.get_liquidity_parameter_by_strategy(0),
+    )
+    .unwrap();
+    process_and_assert_ok(&[add_liquidity_ix], &payer, &[&*payer], &mut banks_client).await;
+
+    let position_state: PositionV2 = pod_read_unaligned_skip_disc(
+        &fetch_account(&mut banks_client, position.pubkey())
+            .await
+            .data,
+    )
+    .unwrap();
+    let (lower_bin_id, upper_bin_id) = (position_state.lower_bin_id, position_state.upper_bin_id);
+
+    let claim_fee_ix = instructions::claim_fee2(
+        position.pubkey(),
+        pair.lb_pair,
+        &lb_pair_state,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        None,
+        lower_bin_id,
+        upper_bin_id,
+    )
+    .unwrap();
+    let remove_liquidity_ix = instructions::remove_liquidity_by_range2(
+        position.pubkey(),
+        pair.lb_pair,
+        &lb_pair_state,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        None,
+        lower_bin_id,
+        upper_bin_id,
+        BASIS_POINT_MAX as u16,
+    )
+    .unwrap();
+    let close_position_ix = instructions::close_position2(
+        position.pubkey(),
+        &position_state,
+        payer.pubkey(),
+        payer.pubkey(),
+    )
+    .unwrap();
+    process_and_assert_ok(
+        &[claim_fee_ix, remove_liquidity_ix, close_position_ix],
+        &payer,
+        &[&*payer],
+        &mut banks_client,
+    )
+    .await;
+
+    assert!(banks_client
+        .get_account(position.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+
+    // Only the rounding of the withdrawal is lost
+    let user_x_after = fetch_token_account_state(&mut banks_client, user_token_x)
+        .await
+        .amount;
+    let user_y_after = fetch_token_account_state(&mut banks_client, user_token_y)
+        .await
+        .amount;
+    assert!(user_x_after <= user_x_before && user_x_before - user_x_after <= 21);
+    assert!(user_y_after <= user_y_before && user_y_before - user_y_after <= 21);
+}
+
+async fn fetch_token_amounts(
+    banks_client: &mut BanksClient,
+    token_accounts: &[Pubkey],
+) -> Vec<u64> {
+    let mut amounts = vec![];
+    for &token_account in token_accounts {
+        amounts.push(
+            fetch_token_account_state(banks_client, token_account)
+                .await
+                .amount,
+        );
+    }
+    amounts
+}
+
+/// Swap with every swap instruction builder and check the amounts against the quotes: the host
+/// fee of `swap` and `swap_exact_out`, and `swap2` jumping to the bin array tracked by the bitmap
+/// extension.
+#[tokio::test]
+async fn test_swap_instructions() {
+    let mint_authority = Keypair::new();
+    let host = Pubkey::new_unique();
+
+    let mut fixture = PoolFixture::two_sided(1, 0, 5, 1_000_000_000, 1_000_000_000);
+    fixture.token_x.mint_authority = Some(mint_authority.pubkey());
+    fixture.token_y.mint_authority = Some(mint_authority.pubkey());
+    fixture.bins.push(BinFixture {
+        bin_id: -36_000,
+        amount_x: 0,
+        amount_y: 1_000_000_000,
+        limit_order: None,
+    });
+    let accounts = fixture.build().unwrap();
+    let lb_pair = fixture.lb_pair();
+    let (token_x_mint, token_y_mint) = (fixture.token_x.address, fixture.token_y.address);
+
+    let mut test = ProgramTest::default();
+    test.prefer_bpf(true);
+    test.add_program("./tests/artifacts/lb_clmm_prod", dlmm::ID, None);
+    add_pool_fixture_accounts(&mut test, &accounts);
+    let (mut banks_client, payer, _recent_blockhash) = test.start().await;
+
+    let mut token_accounts = HashMap::new();
+    for owner in [payer.pubkey(), host] {
+        for mint in [token_x_mint, token_y_mint] {
+            let token_account = get_or_create_ata(&payer, &mint, &owner, &mut banks_client).await;
+            token_accounts.insert((owner, mint), token_account);
+        }
+    }
+    let user_token_x = token_accounts[&(payer.pubkey(), token_x_mint)];
+    let user_token_y = token_accounts[&(payer.pubkey(), token_y_mint)];
+    let host_token_x = token_accounts[&(host, token_x_mint)];
+    let host_token_y = token_accounts[&(host, token_y_mint)];
+    for (mint, user_token) in [(token_x_mint, user_token_x), (token_y_mint, user_token_y)] {
+        mint_spl_tokens(
+            &payer,
+            &mint,
+            &user_token,
+            &mint_authority,
+            20_000_000_000,
+            &mut banks_client,
+        )
+        .await;
+    }
+
+    let host_fee_options = QuoteOptions {
+        host_fee_bps: Some(HOST_FEE_BPS),
+        ..Default::default()
+    };
+
+    // swap X to Y, with the host fee paid in X
+    let snapshot = fetch_pool_fixture_snapshot(&mut banks_client, &accounts).await;
+    let amount_in = 1_000_000_000;
+    let quote = snapshot
+        .quote_exact_in(amount_in, true, host_fee_options)
+        .unwrap();
+    assert!(quote.host_fee > 0);
+    let swap_ix = instructions::swap(
+        lb_pair,
+        &snapshot.lb_pair,
+        true,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        Some(host_token_x),
+        &get_bin_array_pubkeys_for_swap(
+            lb_pair,
+            &snapshot.lb_pair,
+            snapshot.bitmap_extension.as_ref(),
+            true,
+            3,
+        )
+        .unwrap(),
+        amount_in,
+        quote.amount_out,
+    )
+    .unwrap();
+
+    let token_accounts = [user_token_x, user_token_y, host_token_x];
+    let before = fetch_token_amounts(&mut banks_client, &token_accounts).await;
+    process_and_assert_ok(&[swap_ix], &payer, &[&payer], &mut banks_client).await;
+    let after = fetch_token_amounts(&mut banks_client, &token_accounts).await;
+    assert_eq!(before[0] - after[0], amount_in);
+    assert_eq!(after[1] - before[1], quote.amount_out);
+    assert_eq!(after[2] - before[2], quote.host_fee);
+
+    // swap_exact_out Y to X, with the host fee paid in Y
+    let snapshot = fetch_pool_fixture_snapshot(&mut banks_client, &accounts).await;
+    let amount_out = 500_000_000;
+    let quote = snapshot
+        .quote_exact_out(amount_out, false, host_fee_options)
+        .unwrap();
+    assert!(quote.host_fee > 0);
+    let swap_ix = instructions::swap_exact_out(
+        lb_pair,
+        &snapshot.lb_pair,
+        true,
+        payer.pubkey(),
+        user_token_y,
+        user_token_x,
+        Some(host_token_y),
+        &get_bin_array_pubkeys_for_swap(
+            lb_pair,
+            &snapshot.lb_pair,
+            snapshot.bitmap_extension.as_ref(),
+            false,
+            3,
+        )
+        .unwrap(),
+        quote.amount_in,
+        amount_out,
+    )
+    .unwrap();
+
+    let token_accounts = [user_token_y, user_token_x, host_token_y];
+    let before = fetch_token_amounts(&mut banks_client, &token_accounts).await;
+    process_and_assert_ok(&[swap_ix], &payer, &[&payer], &mut banks_client).await;
+    let after = fetch_token_amounts(&mut banks_client, &token_accounts).await;
+    assert_eq!(before[0] - after[0], quote.amount_in);
+    assert_eq!(after[1] - before[1], amount_out);
+    assert_eq!(after[2] - before[2], quote.host_fee);
+
+    // swap_with_price_impact X to Y
+    let snapshot = fetch_pool_fixture_snapshot(&mut banks_client, &accounts).await;
+    let quote = snapshot
+        .quote_exact_in_with_price_impact(amount_in, true, None, 100, QuoteOptions::default())
+        .unwrap();
+    assert_eq!(quote.amount_in, amount_in);
+    let swap_ix = instructions::swap_with_price_impact(
+        lb_pair,
+        &snapshot.lb_pair,
+        true,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        None,
+        &get_bin_array_pubkeys_for_swap(
+            lb_pair,
+            &snapshot.lb_pair,
+            snapshot.bitmap_extension.as_ref(),
+            true,
+            3,
+        )
+        .unwrap(),
+        amount_in,
+        None,
+        100,
+    )
+    .unwrap();
+
+    let token_accounts = [user_token_x, user_token_y];
+    let before = fetch_token_amounts(&mut banks_client, &token_accounts).await;
+    process_and_assert_ok(&[swap_ix], &payer, &[&payer], &mut banks_client).await;
+    let after = fetch_token_amounts(&mut banks_client, &token_accounts).await;
+    assert_eq!(before[0] - after[0], quote.amount_in);
+    assert_eq!(after[1] - before[1], quote.amount_out);
+
+    // swap2 X to Y, through the bin array tracked by the bitmap extension
+    let snapshot = fetch_pool_fixture_snapshot(&mut banks_client, &accounts).await;
+    assert!(snapshot.bitmap_extension.is_some());
+    let amount_in = 12_000_000_000;
+    let quote = snapshot
+        .quote_exact_in(amount_in, true, host_fee_options)
+        .unwrap();
+    assert!(quote.prices.end_active_id < -35_840);
+    let swap_ix = instructions::swap2(
+        lb_pair,
+        &snapshot.lb_pair,
+        true,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        Some(host_token_x),
+        &get_bin_array_pubkeys_for_swap(
+            lb_pair,
+            &snapshot.lb_pair,
+            snapshot.bitmap_extension.as_ref(),
+            true,
+            3,
+        )
+        .unwrap(),
+        None,
+        amount_in,
+        quote.amount_out,
+    )
+    .unwrap();
+
+    let token_accounts = [user_token_x, user_token_y, host_token_x];
+    let before = fetch_token_amounts(&mut banks_client, &token_accounts).await;
+    process_and_assert_ok(&[swap_ix], &payer, &[&payer], &mut banks_client).await;
+    let after = fetch_token_amounts(&mut banks_client, &token_accounts).await;
+    assert_eq!(before[0] - after[0], amount_in);
+    assert_eq!(after[1] - before[1], quote.amount_out);
+    assert_eq!(after[2] - before[2], quote.host_fee);
+
+    let lb_pair_state = fetch_lb_pair(&mut banks_client, lb_pair).await;
+    assert_eq!(lb_pair_state.active_id, quote.prices.end_active_id);
+}
+
+/// Claim the reward accrued by a position with `claim_reward2`.
+#[tokio::test]
+async fn test_claim_reward2_instruction() {
+    let mint_authority = Keypair::new();
+
+    // Limit order pairs don't distribute rewards
+    let mut fixture = PoolFixture::two_sided(10, 0, 5, 1_000_000_000, 1_000_000_000);
+    fixture.function_type = FunctionType::LiquidityMining;
+    fixture.token_x.mint_authority = Some(mint_authority.pubkey());
+    fixture.token_y.mint_authority = Some(mint_authority.pubkey());
+    let mut accounts = fixture.build().unwrap();
+    let lb_pair = fixture.lb_pair();
+    let reward_mint = fixture.token_y.address;
+    let (reward_vault, _bump) = derive_reward_vault_pda(lb_pair, 0);
+    let reward_vault_amount = 1_000_000_000_000;
+
+    // Reward of token Y, 1_000 per second until the end of the test
+    let mut lb_pair_state: LbPair = pod_read_unaligned_skip_disc(&accounts.lb_pair.1.data).unwrap();
+    lb_pair_state.reward_infos[0].mint = reward_mint;
+    lb_pair_state.reward_infos[0].vault = reward_vault;
+    lb_pair_state.reward_infos[0].funder = Pubkey::new_unique();
+    lb_pair_state.reward_infos[0].reward_duration = u64::MAX;
+    lb_pair_state.reward_infos[0].reward_duration_end = u64::MAX;
+    lb_pair_state.reward_infos[0].reward_rate = 1_000u128 << SCALE_OFFSET;
+    accounts.lb_pair.1.data = [LbPair::DISCRIMINATOR, bytemuck::bytes_of(&lb_pair_state)].concat();
+
+    let mut reward_vault_data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: reward_mint,
+        owner: lb_pair,
+        amount: reward_vault_amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut reward_vault_data);
+
+    let mut test = ProgramTest::default();
+    test.prefer_bpf(true);
+    test.add_program("./tests/artifacts/lb_clmm_prod", dlmm::ID, None);
+    add_pool_fixture_accounts(&mut test, &accounts);
+    test.add_account(
+        reward_vault,
+        solana_sdk::account::Account {
+            lamports: LAMPORTS_PER_SOL,
+            data: reward_vault_data,
+            owner: spl_token::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let ctx = test.start_with_context().await;
+    let payer = ctx.payer.insecure_clone();
+    let mut banks_client = ctx.banks_client.clone();
+
+    let user_token_x = get_or_create_ata(
+        &payer,
+        &fixture.token_x.address,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_y =
+        get_or_create_ata(&payer, &reward_mint, &payer.pubkey(), &mut banks_client).await;
+    for (mint, user_token) in [
+        (fixture.token_x.address, user_token_x),
+        (reward_mint, user_token_y),
+    ] {
+        mint_spl_tokens(
+            &payer,
+            &mint,
+            &user_token,
+            &mint_authority,
+            10_000_000_000,
+            &mut banks_client,
+        )
+        .await;
+    }
+
+    let position = Keypair::new();
+    let init_position_ix = instructions::initialize_position(
+        lb_pair,
+        payer.pubkey(),
+        position.pubkey(),
+        payer.pubkey(),
+        -5,
+        11,
+    )
+    .unwrap();
+    let deposit = StrategyDeposit::new(
+        LiquidityStrategy::Spot,
+        0,
+        fixture.bin_step,
+        -5,
+        5,
+        1_000_000_000,
+        1_000_000_000,
+        false,
+    )
+    .unwrap();
+    let add_liquidity_ix = instructions::add_liquidity_by_strategy2(
+        position.pubkey(),
+        lb_pair,
+        &lb_pair_state,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        None,
+        deposit.get_liquidity_parameter_by_strategy(0),
+    )
+    .unwrap();
+    process_and_assert_ok(
+        &[init_position_ix, add_liquidity_ix],
+        &payer,
+        &[&position],
+        &mut banks_client,
+    )
+    .await;
+
+    let elapsed = 1_000;
+    let mut clock = get_clock(&mut banks_client).await;
+    clock.unix_timestamp += elapsed;
+    ctx.set_sysvar(&clock);
+
+    let claim_reward_ix = instructions::claim_reward2(
+        position.pubkey(),
+        lb_pair,
+        &lb_pair_state,
+        spl_token::ID,
+        payer.pubkey(),
+        user_token_y,
+        None,
+        0,
+        -5,
+        5,
+    )
+    .unwrap();
+
+    let token_accounts = [user_token_y, reward_vault];
+    let before = fetch_token_amounts(&mut banks_client, &token_accounts).await;
+    process_and_assert_ok(&[claim_reward_ix], &payer, &[], &mut banks_client).await;
+    let after = fetch_token_amounts(&mut banks_client, &token_accounts).await;
+
+    // The position shares the reward of the active bin with the liquidity of the fixture
+    let claimed_reward = after[0] - before[0];
+    assert!(claimed_reward > 0);
+    assert!(claimed_reward <= 1_000 * elapsed as u64);
+    assert_eq!(before[1] - after[1], claimed_reward);
+}
@@ -0,0 +1,285 @@
+use crate::*;
+use commons::dlmm::accounts::{BinArray, PositionV2};
+use std::collections::HashMap;
+
+async fn fetch_bin_amounts(
+    banks_client: &mut BanksClient,
+    pair: &SwapQuoteTestPair,
+    min_bin_id: i32,
+    max_bin_id: i32,
+) -> Vec<(u64, u64)> {
+    let mut bin_arrays = HashMap::new();
+    for bin_array in [pair.bin_array_1, pair.bin_array_2] {
+        let account = fetch_account(banks_client, bin_array).await;
+        let bin_array_state: BinArray = bytemuck::pod_read_unaligned(&account.data[8..]);
+        bin_arrays.insert(bin_array, bin_array_state);
+    }
+
+    (min_bin_id..=max_bin_id)
+        .map(|bin_id| {
+            let bin_array_key = BinArray::bin_id_to_bin_array_key(pair.lb_pair, bin_id).unwrap();
+            let bin = bin_arrays[&bin_array_key].get_bin(bin_id).unwrap();
+            (bin.amount_x, bin.amount_y)
+        })
+        .collect()
+}
+
+/// Deposit with each strategy through `add_liquidity_by_strategy2`, and compare the amounts
+/// deposited to each bin with the expected amounts.
+#[tokio::test]
+async fn test_strategy_deposit() {
+    let StrategyDepositTest {
+        mut banks_client,
+        payer,
+        pair,
+        lb_pair_state,
+        user_token_x,
+        user_token_y,
+        position,
+    } = setup_strategy_deposit_test().await;
+
+    let active_id = lb_pair_state.active_id;
+    let amount_x = 10_000_000_000;
+    let amount_y = 10_000_000_000;
+
+    // Both sides, bid side only and ask side only
+    let bin_ranges = [
+        (active_id - 10, active_id + 10),
+        (active_id - 10, active_id - 1),
+        (active_id + 1, active_id + 10),
+    ];
+
+    for strategy in [
+        LiquidityStrategy::Spot,
+        LiquidityStrategy::Curve,
+        LiquidityStrategy::BidAsk,
+    ] {
+        for (min_bin_id, max_bin_id) in bin_ranges {
+            let deposit = StrategyDeposit::new(
+                strategy,
+                active_id,
+                lb_pair_state.bin_step,
+                min_bin_id,
+                max_bin_id,
+                amount_x,
+                amount_y,
+                false,
+            )
+            .unwrap();
+
+            let (expected_amount_x, expected_amount_y) = deposit.get_total_amounts();
+            assert!(expected_amount_x <= amount_x && expected_amount_y <= amount_y);
+            if min_bin_id < active_id {
+                assert!(expected_amount_y > amount_y * 99 / 100);
+            }
+            if max_bin_id > active_id {
+                assert!(expected_amount_x > amount_x * 99 / 100);
+            }
+
+            let distribution = deposit.get_bin_liquidity_distribution();
+            assert_eq!(distribution.len(), deposit.bin_amounts.len());
+            assert!(
+                distribution
+                    .iter()
+                    .map(|d| u32::from(d.distribution_x))
+                    .sum::<u32>()
+                    <= 10_000
+            );
+            assert!(
+                distribution
+                    .iter()
+                    .map(|d| u32::from(d.distribution_y))
+                    .sum::<u32>()
+                    <= 10_000
+            );
+
+            let bin_amounts_before =
+                fetch_bin_amounts(&mut banks_client, &pair, min_bin_id, max_bin_id).await;
+            let user_x_before = fetch_token_account_state(&mut banks_client, user_token_x)
+                .await
+                .amount;
+            let user_y_before = fetch_token_account_state(&mut banks_client, user_token_y)
+                .await
+                .amount;
+
+            let add_liquidity_ix = instructions::add_liquidity_by_strategy2(
+                position.pubkey(),
+                pair.lb_pair,
+                &lb_pair_state,
+                payer.pubkey(),
+                user_token_x,
+                user_token_y,
+                None,
+                deposit.get_liquidity_parameter_by_strategy(0),
+            )
+            .unwrap();
+            process_and_assert_ok(&[add_liquidity_ix], &payer, &[&*payer], &mut banks_client).await;
+
+            let bin_amounts_after =
+                fetch_bin_amounts(&mut banks_client, &pair, min_bin_id, max_bin_id).await;
+
+            for ((bin_amount, before), after) in deposit
+                .bin_amounts
+                .iter()
+                .zip(bin_amounts_before)
+                .zip(bin_amounts_after)
+            {
+                assert_eq!(
+                    (bin_amount.amount_x, bin_amount.amount_y),
+                    (after.0 - before.0, after.1 - before.1),
+                    "{strategy:?} bin {}",
+                    bin_amount.bin_id
+                );
+            }
+
+            let user_x_after = fetch_token_account_state(&mut banks_client, user_token_x)
+                .await
+                .amount;
+            let user_y_after = fetch_token_account_state(&mut banks_client, user_token_y)
+                .await
+                .amount;
+            assert_eq!(expected_amount_x, user_x_before - user_x_after);
+            assert_eq!(expected_amount_y, user_y_before - user_y_after);
+        }
+    }
+}
+
+/// Deposit with each strategy through `rebalance_liquidity`, and compare the amounts deposited to
+/// each bin with the amounts of the strategy parameters.
+#[tokio::test]
+async fn test_rebalance_strategy_deposit() {
+    let StrategyDepositTest {
+        mut banks_client,
+        payer,
+        pair,
+        lb_pair_state,
+        user_token_x,
+        user_token_y,
+        position,
+    } = setup_strategy_deposit_test().await;
+
+    let active_id = lb_pair_state.active_id;
+    let amount_x = 10_000_000_000;
+    let amount_y = 10_000_000_000;
+
+    // Both sides, bid side only and ask side only
+    let delta_id_ranges = [(-10, 10), (-10, -1), (1, 10)];
+
+    for strategy in [
+        LiquidityStrategy::Spot,
+        LiquidityStrategy::Curve,
+        LiquidityStrategy::BidAsk,
+    ] {
+        for (min_delta_id, max_delta_id) in delta_id_ranges {
+            let parameters = get_liquidity_strategy_parameters(
+                strategy,
+                amount_x,
+                amount_y,
+                min_delta_id,
+                max_delta_id,
+                lb_pair_state.bin_step,
+                false,
+                active_id,
+            )
+            .unwrap();
+            let expected_bin_amounts = parameters
+                .get_amounts_into_bins(
+                    active_id,
+                    min_delta_id,
+                    max_delta_id,
+                    lb_pair_state.bin_step,
+                    false,
+                )
+                .unwrap();
+
+            let expected_amount_x: u64 = expected_bin_amounts.iter().map(|b| b.amount_x).sum();
+            let expected_amount_y: u64 = expected_bin_amounts.iter().map(|b| b.amount_y).sum();
+            assert!(expected_amount_x <= amount_x && expected_amount_y <= amount_y);
+
+            let (min_bin_id, max_bin_id) = (active_id + min_delta_id, active_id + max_delta_id);
+            let bin_amounts_before =
+                fetch_bin_amounts(&mut banks_client, &pair, min_bin_id, max_bin_id).await;
+
+            let position_state: PositionV2 = pod_read_unaligned_skip_disc(
+                &fetch_account(&mut banks_client, position.pubkey())
+                    .await
+                    .data,
+            )
+            .unwrap();
+
+            let rebalance_ix = instructions::rebalance_liquidity(
+                position.pubkey(),
+                &position_state,
+                &lb_pair_state,
+                payer.pubkey(),
+                payer.pubkey(),
+                user_token_x,
+                user_token_y,
+                None,
+                RebalanceLiquidityParams {
+                    active_id,
+                    max_active_bin_slippage: 0,
+                    should_claim_fee: false,
+                    should_claim_reward: false,
+                    min_withdraw_x_amount: 0,
+                    max_deposit_x_amount: amount_x,
+                    min_withdraw_y_amount: 0,
+                    max_deposit_y_amount: amount_y,
+                    // No shrink
+                    shrink_mode: 3,
+                    padding: [0; 31],
+                    removes: vec![],
+                    adds: vec![parameters
+                        .to_add_liquidity_params(min_delta_id, max_delta_id, false)
+                        .unwrap()],
+                },
+            )
+            .unwrap();
+            process_and_assert_ok(&[rebalance_ix], &payer, &[&*payer], &mut banks_client).await;
+
+            let bin_amounts_after =
+                fetch_bin_amounts(&mut banks_client, &pair, min_bin_id, max_bin_id).await;
+
+            for ((bin_amount, before), after) in expected_bin_amounts
+                .iter()
+                .zip(bin_amounts_before)
+                .zip(bin_amounts_after)
+            {
+                assert_eq!(
+                    (bin_amount.amount_x, bin_amount.amount_y),
+                    (after.0 - before.0, after.1 - before.1),
+                    "{strategy:?} bin {}",
+                    bin_amount.bin_id
+                );
+            }
+        }
+    }
+}
+
+/// The parameters are searched in a bounded number of steps, also where the initial estimate is
+/// far from the result, such as far from the zero bin.
+#[test]
+fn test_liquidity_strategy_parameters_far_from_zero_bin() {
+    let (amount_x, amount_y) = (10_000_000_000, 10_000_000_000);
+
+    for strategy in [
+        LiquidityStrategy::Spot,
+        LiquidityStrategy::Curve,
+        LiquidityStrategy::BidAsk,
+    ] {
+        for active_id in [-5000, 4000] {
+            let parameters = get_liquidity_strategy_parameters(
+                strategy, amount_x, amount_y, -10, 10, 25, false, active_id,
+            )
+            .unwrap();
+            let bin_amounts = parameters
+                .get_amounts_into_bins(active_id, -10, 10, 25, false)
+                .unwrap();
+
+            let total_amount_x: u64 = bin_amounts.iter().map(|b| b.amount_x).sum();
+            let total_amount_y: u64 = bin_amounts.iter().map(|b| b.amount_y).sum();
+            assert!(total_amount_x <= amount_x && total_amount_y <= amount_y);
+            assert!(total_amount_x > 0 && total_amount_y > 0);
+        }
+    }
+}
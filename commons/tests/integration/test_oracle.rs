@@ -0,0 +1,155 @@
+use crate::*;
+
+#[tokio::test]
+async fn test_oracle_twap() {
+    let (
+        test,
+        SplTestPair {
+            lb_pair,
+            reserve_x,
+            reserve_y,
+            token_x_mint,
+            token_y_mint,
+            oracle,
+            bin_array_1,
+            bin_array_2,
+        },
+    ) = setup_spl_test_pair();
+
+    let (mut banks_client, payer, _recent_blockhash) = test.start().await;
+
+    let oracle_account = fetch_account(&mut banks_client, oracle).await;
+    let dynamic_oracle = DynamicOracle::parse(&oracle_account.data).unwrap();
+
+    // Observations are ordered from the oldest to the latest, the latest being at `idx`
+    assert_eq!(
+        dynamic_oracle.observations.len(),
+        dynamic_oracle.oracle.active_size as usize
+    );
+    assert!(dynamic_oracle
+        .observations
+        .windows(2)
+        .all(|pair| pair[0].last_updated_at < pair[1].last_updated_at));
+    let latest_offset =
+        ORACLE_METADATA_SIZE + dynamic_oracle.oracle.get_latest_index() * OBSERVATION_SIZE;
+    let latest_cumulative_active_bin_id = i128::from_le_bytes(
+        oracle_account.data[latest_offset..latest_offset + 16]
+            .try_into()
+            .unwrap(),
+    );
+    let latest = *dynamic_oracle.observations.last().unwrap();
+    assert_eq!(
+        latest.cumulative_active_bin_id,
+        latest_cumulative_active_bin_id
+    );
+
+    let lb_pair_state = fetch_lb_pair(&mut banks_client, lb_pair).await;
+
+    // TWAP between two observations is the average of the accumulated active bin ids
+    let first = dynamic_oracle.observations[0];
+    let second = dynamic_oracle.observations[1];
+    let twap = dynamic_oracle
+        .get_twap(
+            &lb_pair_state,
+            first.last_updated_at,
+            second.last_updated_at,
+        )
+        .unwrap();
+    let expected_active_id = (second.cumulative_active_bin_id - first.cumulative_active_bin_id)
+        / i128::from(second.last_updated_at - first.last_updated_at);
+    assert_eq!(i128::from(twap.active_id), expected_active_id);
+    assert_eq!(
+        twap.price,
+        get_price_from_id(twap.active_id, lb_pair_state.bin_step).unwrap()
+    );
+
+    // After the latest observation, the current active bin is accumulated
+    let twap = dynamic_oracle
+        .get_twap_over_window(
+            &lb_pair_state,
+            latest.last_updated_at + SAMPLE_LIFETIME as i64 * 10,
+            SAMPLE_LIFETIME * 5,
+        )
+        .unwrap();
+    assert_eq!(twap.active_id, lb_pair_state.active_id);
+
+    assert!(dynamic_oracle
+        .get_twap(
+            &lb_pair_state,
+            first.last_updated_at - 1,
+            latest.last_updated_at
+        )
+        .is_err());
+    assert!(dynamic_oracle
+        .get_twap(
+            &lb_pair_state,
+            latest.last_updated_at,
+            latest.last_updated_at + SAMPLE_LIFETIME as i64 - 1
+        )
+        .is_err());
+
+    // The program accumulates the oracle as predicted
+    let clock = get_clock(&mut banks_client).await;
+    let expected_cumulative_active_bin_id = dynamic_oracle
+        .get_cumulative_active_bin_id(lb_pair_state.active_id, clock.unix_timestamp)
+        .unwrap();
+
+    let amount_in = 100_000;
+
+    warp_sol(&payer, payer.pubkey(), amount_in, &mut banks_client).await;
+
+    let user_token_in =
+        get_or_create_ata(&payer, &token_y_mint, &payer.pubkey(), &mut banks_client).await;
+
+    let user_token_out =
+        get_or_create_ata(&payer, &token_x_mint, &payer.pubkey(), &mut banks_client).await;
+
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let main_accounts = dlmm::client::accounts::Swap2 {
+        lb_pair,
+        oracle,
+        bin_array_bitmap_extension: Some(dlmm::ID),
+        reserve_x,
+        reserve_y,
+        user_token_in,
+        user_token_out,
+        token_x_mint,
+        token_y_mint,
+        host_fee_in: Some(dlmm::ID),
+        user: payer.pubkey(),
+        token_x_program: spl_token::id(),
+        token_y_program: spl_token::id(),
+        program: dlmm::ID,
+        event_authority,
+        memo_program: spl_memo::id(),
+    }
+    .to_account_metas(None);
+
+    let mut all_accounts = main_accounts.to_vec();
+    all_accounts.push(AccountMeta::new(bin_array_1, false));
+    all_accounts.push(AccountMeta::new(bin_array_2, false));
+
+    let swap_ix = Instruction {
+        program_id: dlmm::ID,
+        accounts: all_accounts,
+        data: dlmm::client::args::Swap2 {
+            amount_in,
+            min_amount_out: 0,
+            remaining_accounts_info: RemainingAccountsInfo { slices: vec![] },
+        }
+        .data(),
+    };
+
+    process_and_assert_ok(&[swap_ix], &payer, &[&payer], &mut banks_client).await;
+
+    let oracle_account = fetch_account(&mut banks_client, oracle).await;
+    let dynamic_oracle = DynamicOracle::parse(&oracle_account.data).unwrap();
+    let latest = dynamic_oracle.observations.last().unwrap();
+
+    assert_eq!(latest.last_updated_at, clock.unix_timestamp);
+    assert_eq!(
+        latest.cumulative_active_bin_id,
+        expected_cumulative_active_bin_id
+    );
+}
@@ -0,0 +1,272 @@
+use crate::*;
+use solana_sdk::signature::Keypair;
+
+/// Swaps quoted against the accounts of a synthetic pool, limit orders and bitmap extension
+/// included, match the program swaps on the same accounts.
+#[tokio::test]
+async fn test_pool_fixture_quote_matches_swap() {
+    use commons::test_utils::*;
+
+    let mint_authority = Keypair::new();
+
+    let mut fixture = PoolFixture::two_sided(1, 0, 5, 1_000_000_000, 1_000_000_000);
+    fixture.token_x.mint_authority = Some(mint_authority.pubkey());
+    fixture.token_y.mint_authority = Some(mint_authority.pubkey());
+    fixture.bins[2].limit_order = Some(LimitOrderFixture {
+        is_ask: false,
+        open_order_amount: 300_000_000,
+        processed_order_remaining_amount: 200_000_000,
+    });
+    fixture.bins[8].limit_order = Some(LimitOrderFixture {
+        is_ask: true,
+        open_order_amount: 500_000_000,
+        processed_order_remaining_amount: 0,
+    });
+    // Bin array out of the pair bitmap, tracked by the bitmap extension
+    fixture.bins.push(BinFixture {
+        bin_id: -36_000,
+        amount_x: 0,
+        amount_y: 1_000_000_000,
+        limit_order: None,
+    });
+
+    let accounts = fixture.build().unwrap();
+    let lb_pair = fixture.lb_pair();
+    assert_eq!(accounts.lb_pair.0, lb_pair);
+    assert!(accounts.bitmap_extension.is_some());
+    assert_eq!(accounts.bin_arrays.len(), 3);
+
+    let mut test = ProgramTest::default();
+    test.prefer_bpf(true);
+    test.add_program("./tests/artifacts/lb_clmm_prod", dlmm::ID, None);
+    for (pubkey, account) in accounts.to_account_map() {
+        if pubkey != solana_sdk::sysvar::clock::ID {
+            test.add_account(pubkey, account);
+        }
+    }
+
+    let (mut banks_client, payer, _recent_blockhash) = test.start().await;
+
+    let user_token_x = get_or_create_ata(
+        &payer,
+        &fixture.token_x.address,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_y = get_or_create_ata(
+        &payer,
+        &fixture.token_y.address,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+
+    // Y to X fills the ask order, then X to Y walks back to the bid order and jumps to the bin
+    // array of the bitmap extension
+    let swaps = [(4_400_000_000, false), (12_000_000_000, true)];
+
+    for (amount_in, swap_for_y) in swaps {
+        let user_token_in = if swap_for_y {
+            user_token_x
+        } else {
+            user_token_y
+        };
+        let token_in_mint = if swap_for_y {
+            fixture.token_x.address
+        } else {
+            fixture.token_y.address
+        };
+        mint_spl_tokens(
+            &payer,
+            &token_in_mint,
+            &user_token_in,
+            &mint_authority,
+            amount_in,
+            &mut banks_client,
+        )
+        .await;
+    }
+
+    let mut snapshot = accounts.to_pool_snapshot().unwrap();
+    snapshot.clock = get_clock(&mut banks_client).await;
+
+    for (amount_in, swap_for_y) in swaps {
+        let bin_array_pubkeys = get_bin_array_pubkeys_for_swap(
+            lb_pair,
+            &snapshot.lb_pair,
+            snapshot.bitmap_extension.as_ref(),
+            swap_for_y,
+            3,
+        )
+        .unwrap();
+
+        let detailed_quote = simulate_swap_exact_in(
+            lb_pair,
+            &mut snapshot.lb_pair,
+            amount_in,
+            swap_for_y,
+            QuoteOptions::default(),
+            &mut snapshot.bin_arrays,
+            snapshot.bitmap_extension.as_ref(),
+            &snapshot.clock,
+            &snapshot.mint_x_account,
+            &snapshot.mint_y_account,
+        )
+        .unwrap();
+        assert!(detailed_quote
+            .bin_fills
+            .iter()
+            .any(|bin_fill| bin_fill.open_order_amount_out > 0));
+
+        let (user_token_in, user_token_out) = if swap_for_y {
+            (user_token_x, user_token_y)
+        } else {
+            (user_token_y, user_token_x)
+        };
+        let user_token_out_before =
+            fetch_token_account_state(&mut banks_client, user_token_out).await;
+
+        let swap_ix = instructions::swap2(
+            lb_pair,
+            &snapshot.lb_pair,
+            true,
+            payer.pubkey(),
+            user_token_in,
+            user_token_out,
+            None,
+            &bin_array_pubkeys,
+            None,
+            amount_in,
+            0,
+        )
+        .unwrap();
+        process_and_assert_ok(&[swap_ix], &payer, &[&payer], &mut banks_client).await;
+
+        let user_token_out_after =
+            fetch_token_account_state(&mut banks_client, user_token_out).await;
+        assert_eq!(
+            user_token_out_after.amount - user_token_out_before.amount,
+            detailed_quote.quote.amount_out,
+            "Actual swap out amount must match the fixture quote"
+        );
+
+        let lb_pair_state = fetch_lb_pair(&mut banks_client, lb_pair).await;
+        assert_eq!(lb_pair_state.active_id, snapshot.lb_pair.active_id);
+    }
+
+    assert!(snapshot.lb_pair.active_id < -35_840);
+}
+
+/// Snapshots built from the account map of several pairs only keep the accounts of their pair.
+#[test]
+fn test_pool_snapshot_from_account_map() {
+    use commons::test_utils::*;
+    use std::collections::BTreeSet;
+
+    let mut fixture = PoolFixture::new(10, 0);
+    fixture.bins = [-100, 0, 100, -36_000]
+        .into_iter()
+        .map(|bin_id| BinFixture {
+            bin_id,
+            amount_x: 0,
+            amount_y: 1_000_000,
+            limit_order: None,
+        })
+        .collect();
+    let accounts = fixture.build().unwrap();
+
+    let mut other_fixture = fixture.clone();
+    other_fixture.token_y = MintFixture::new(Pubkey::new_unique(), 9);
+    other_fixture.bins.truncate(1);
+    let other_accounts = other_fixture.build().unwrap();
+
+    let mut account_map = accounts.to_account_map();
+    account_map.extend(other_accounts.to_account_map());
+
+    let snapshot = PoolSnapshot::from_account_map(fixture.lb_pair(), &account_map).unwrap();
+    let expected = accounts.to_pool_snapshot().unwrap();
+
+    assert_eq!(
+        bytemuck::bytes_of(&snapshot.lb_pair),
+        bytemuck::bytes_of(&expected.lb_pair)
+    );
+    assert_eq!(
+        snapshot.bin_arrays.keys().collect::<BTreeSet<_>>(),
+        expected.bin_arrays.keys().collect::<BTreeSet<_>>()
+    );
+    assert_eq!(
+        snapshot.bitmap_extension.as_ref().map(bytemuck::bytes_of),
+        expected.bitmap_extension.as_ref().map(bytemuck::bytes_of)
+    );
+    assert_eq!(snapshot.clock, expected.clock);
+    assert_eq!(snapshot.mint_x_account, expected.mint_x_account);
+    assert_eq!(snapshot.mint_y_account, expected.mint_y_account);
+
+    let bin_array_map = get_bin_array_map_from_account_map(&account_map, fixture.lb_pair());
+    assert_eq!(
+        bin_array_map.keys().copied().collect::<BTreeSet<_>>(),
+        BTreeSet::from([-515, -2, 0, 1])
+    );
+
+    account_map.remove(&solana_sdk::sysvar::clock::ID);
+    let err = PoolSnapshot::from_account_map(fixture.lb_pair(), &account_map).unwrap_err();
+    assert!(err.to_string().contains("Missing clock account"));
+}
+
+/// Mints and reserves written by the fixtures unpack with the token programs, transfer fee
+/// extensions included.
+#[test]
+fn test_pool_fixture_token_accounts_unpack() {
+    use commons::test_utils::*;
+    use spl_token_2022::extension::{
+        transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+        BaseStateWithExtensions, StateWithExtensions,
+    };
+    use spl_token_2022::state::{Account as TokenAccount, Mint};
+
+    let mint_authority = Pubkey::new_unique();
+    let mut fixture = PoolFixture::sample();
+    fixture.token_x.mint_authority = Some(mint_authority);
+    let accounts = fixture.build().unwrap();
+
+    for (mint, reserve, mint_fixture) in [
+        (&accounts.mint_x, &accounts.reserve_x, &fixture.token_x),
+        (&accounts.mint_y, &accounts.reserve_y, &fixture.token_y),
+    ] {
+        let token_program = if mint_fixture.is_token_2022 {
+            spl_token_2022::ID
+        } else {
+            spl_token::ID
+        };
+        assert_eq!(mint.1.owner, token_program);
+        assert_eq!(reserve.1.owner, token_program);
+
+        let mint_state = StateWithExtensions::<Mint>::unpack(&mint.1.data).unwrap();
+        assert_eq!(mint_state.base.decimals, mint_fixture.decimals);
+        assert_eq!(
+            Option::<Pubkey>::from(mint_state.base.mint_authority),
+            mint_fixture.mint_authority
+        );
+        assert!(mint_state.base.is_initialized);
+
+        let reserve_state = StateWithExtensions::<TokenAccount>::unpack(&reserve.1.data).unwrap();
+        assert_eq!(reserve_state.base.mint, mint.0);
+        assert_eq!(reserve_state.base.owner, accounts.lb_pair.0);
+        assert_eq!(mint_state.base.supply, reserve_state.base.amount);
+
+        match mint_fixture.transfer_fee_bps {
+            Some(transfer_fee_bps) => {
+                let transfer_fee_config = mint_state.get_extension::<TransferFeeConfig>().unwrap();
+                let transfer_fee = transfer_fee_config.get_epoch_fee(fixture.clock.epoch);
+                assert_eq!(
+                    u16::from(transfer_fee.transfer_fee_basis_points),
+                    transfer_fee_bps
+                );
+                assert_eq!(u64::from(transfer_fee.maximum_fee), u64::MAX);
+                assert!(reserve_state.get_extension::<TransferFeeAmount>().is_ok());
+            }
+            None => assert!(mint_state.get_extension_types().unwrap().is_empty()),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::*;
+use async_trait::async_trait;
+use commons::dlmm::accounts::LbPair;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Mutex;
+
+/// Account provider recording the accounts fetched from it.
+struct RecordingAccountProvider {
+    accounts: HashMap<Pubkey, solana_sdk::account::Account>,
+    fetched: Mutex<Vec<Pubkey>>,
+}
+
+#[async_trait]
+impl AccountProvider for RecordingAccountProvider {
+    async fn get_multiple_accounts(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> anyhow::Result<Vec<Option<solana_sdk::account::Account>>> {
+        self.fetched.lock().unwrap().extend_from_slice(pubkeys);
+        self.accounts.get_multiple_accounts(pubkeys).await
+    }
+}
+
+/// Account updates of the bin arrays and refresh of the pair pick up the state changed by a swap,
+/// and keep the mint accounts.
+#[tokio::test]
+async fn test_pool_snapshot_refresh_after_swap() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let payer = Rc::new(ctx.payer);
+    let mut banks_client = ctx.banks_client;
+
+    let amount_in = 40_000_000_000;
+
+    let user_token_x = get_or_create_ata(
+        &payer,
+        &pair.token_x_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_y = get_or_create_ata(
+        &payer,
+        &pair.token_y_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+
+    mint_spl_tokens(
+        &payer,
+        &pair.token_x_mint,
+        &user_token_x,
+        &pair.mint_authority,
+        amount_in,
+        &mut banks_client,
+    )
+    .await;
+
+    let account_map = fetch_pool_accounts(&mut banks_client, &pair).await;
+    let mut pool = PoolSnapshot::load(&account_map, pair.lb_pair, 3)
+        .await
+        .unwrap();
+
+    let quote = pool
+        .quote_exact_in(amount_in, true, QuoteOptions::default())
+        .unwrap();
+    assert_ne!(quote.prices.end_active_id, pool.lb_pair.active_id);
+
+    let swap_ix = instructions::swap2(
+        pair.lb_pair,
+        &pool.lb_pair,
+        false,
+        payer.pubkey(),
+        user_token_x,
+        user_token_y,
+        None,
+        &[pair.bin_array_2, pair.bin_array_1],
+        None,
+        amount_in,
+        0,
+    )
+    .unwrap();
+    process_and_assert_ok(&[swap_ix], &payer, &[&*payer], &mut banks_client).await;
+
+    // Mint accounts are kept, so they are not needed to refresh
+    let mut account_map = fetch_pool_accounts(&mut banks_client, &pair).await;
+    account_map.remove(&pair.token_x_mint);
+    account_map.remove(&pair.token_y_mint);
+
+    let bin_array_updates = [pair.bin_array_1, pair.bin_array_2]
+        .into_iter()
+        .map(|bin_array| (bin_array, account_map[&bin_array].clone()))
+        .collect();
+    pool.apply_account_updates(&bin_array_updates).unwrap();
+    pool.refresh(&account_map, 3).await.unwrap();
+
+    let expected = load_pool_snapshot(&mut banks_client, &pair).await;
+    assert_eq!(pool.lb_pair.active_id, quote.prices.end_active_id);
+    assert_eq!(pool.lb_pair.active_id, expected.lb_pair.active_id);
+    assert_eq!(pool.bin_arrays.len(), 2);
+
+    for swap_for_y in [true, false] {
+        let quote = pool
+            .quote_exact_in(amount_in, swap_for_y, QuoteOptions::default())
+            .unwrap();
+        let expected_quote = expected
+            .quote_exact_in(amount_in, swap_for_y, QuoteOptions::default())
+            .unwrap();
+        assert_eq!(quote.amount_out, expected_quote.amount_out);
+        assert_eq!(quote.fee, expected_quote.fee);
+    }
+}
+
+/// Refresh refetches the pair state, but only the bin arrays missing from the snapshot.
+#[tokio::test]
+async fn test_pool_snapshot_refresh_fetches_only_new_bin_arrays() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+
+    let account_provider = RecordingAccountProvider {
+        accounts: fetch_pool_accounts(&mut ctx.banks_client, &pair).await,
+        fetched: Mutex::new(vec![]),
+    };
+    let (bitmap_extension, _bump) = derive_bin_array_bitmap_extension(pair.lb_pair);
+
+    let mut pool = PoolSnapshot::load(&account_provider, pair.lb_pair, 3)
+        .await
+        .unwrap();
+    let bin_array_1 = pool.bin_arrays[&pair.bin_array_1];
+    pool.bin_arrays.remove(&pair.bin_array_2);
+    account_provider.fetched.lock().unwrap().clear();
+
+    pool.refresh(&account_provider, 3).await.unwrap();
+
+    assert_eq!(
+        *account_provider.fetched.lock().unwrap(),
+        vec![
+            pair.lb_pair,
+            bitmap_extension,
+            solana_sdk::sysvar::clock::ID,
+            pair.bin_array_2,
+        ]
+    );
+    assert_eq!(pool.bin_arrays.len(), 2);
+    assert_eq!(
+        bytemuck::bytes_of(&pool.bin_arrays[&pair.bin_array_1]),
+        bytemuck::bytes_of(&bin_array_1)
+    );
+    account_provider.fetched.lock().unwrap().clear();
+
+    // Nothing new to fetch once the snapshot has the bin arrays around the active bin
+    pool.refresh(&account_provider, 3).await.unwrap();
+    assert_eq!(account_provider.fetched.lock().unwrap().len(), 3);
+
+    // Updates replace the accounts passed in only
+    let mut lb_pair_account = account_provider.accounts[&pair.lb_pair].clone();
+    let mut lb_pair_state = pool.lb_pair;
+    lb_pair_state.active_id += 1;
+    lb_pair_account.data = [LbPair::DISCRIMINATOR, bytemuck::bytes_of(&lb_pair_state)].concat();
+    let mint_x_account = pool.mint_x_account.clone();
+
+    pool.apply_account_updates(&HashMap::from([(pair.lb_pair, lb_pair_account)]))
+        .unwrap();
+    assert_eq!(pool.lb_pair.active_id, lb_pair_state.active_id);
+    assert_eq!(pool.mint_x_account, mint_x_account);
+    assert_eq!(pool.bin_arrays.len(), 2);
+}
@@ -0,0 +1,426 @@
+use crate::*;
+use solana_sdk::signature::Keypair;
+use std::rc::Rc;
+
+/// Copy of the pair state under a new pair address and mints, to quote routes across pairs.
+fn clone_pool_snapshot(
+    pool: &commons::PoolSnapshot,
+    token_x_mint: Pubkey,
+    token_y_mint: Pubkey,
+) -> commons::PoolSnapshot {
+    let lb_pair_pubkey = Pubkey::new_unique();
+
+    let mut lb_pair = pool.lb_pair;
+    lb_pair.token_x_mint = token_x_mint;
+    lb_pair.token_y_mint = token_y_mint;
+
+    let bin_arrays = pool
+        .bin_arrays
+        .values()
+        .map(|bin_array| {
+            let mut bin_array = *bin_array;
+            bin_array.lb_pair = lb_pair_pubkey;
+            (
+                derive_bin_array_pda(lb_pair_pubkey, bin_array.index).0,
+                bin_array,
+            )
+        })
+        .collect();
+
+    commons::PoolSnapshot {
+        lb_pair_pubkey,
+        lb_pair,
+        bin_arrays,
+        ..pool.clone()
+    }
+}
+
+#[tokio::test]
+async fn test_route_swap_instructions() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let payer = Rc::new(ctx.payer);
+    let mut banks_client = ctx.banks_client;
+
+    let amount_in = 40_000_000_000;
+
+    let user_token_x = get_or_create_ata(
+        &payer,
+        &pair.token_x_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+
+    mint_spl_tokens(
+        &payer,
+        &pair.token_x_mint,
+        &user_token_x,
+        &pair.mint_authority,
+        amount_in,
+        &mut banks_client,
+    )
+    .await;
+
+    let pools = [load_pool_snapshot(&mut banks_client, &pair).await];
+
+    let route = commons::quote_best_route(
+        &pools,
+        pair.token_x_mint,
+        pair.token_y_mint,
+        amount_in,
+        4,
+        Some(payer.pubkey()),
+    )
+    .unwrap();
+
+    assert_eq!(route.hops.len(), 1);
+    assert_eq!(route.hops[0].legs.len(), 1);
+    let leg = &route.hops[0].legs[0];
+    assert!(leg.swap_for_y);
+    assert_eq!(
+        leg.bin_array_pubkeys,
+        vec![pair.bin_array_2, pair.bin_array_1]
+    );
+
+    let (lb_pair_state, bin_arrays, mint_x_account, mint_y_account, clock) = fetch_swap_state(
+        &mut banks_client,
+        pair.lb_pair,
+        &[pair.bin_array_1, pair.bin_array_2],
+    )
+    .await;
+
+    let quote_result = commons::quote::quote_exact_in(
+        pair.lb_pair,
+        &lb_pair_state,
+        amount_in,
+        true,
+        QuoteOptions::default(),
+        bin_arrays,
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+    assert_eq!(route.amount_out, quote_result.amount_out);
+    assert_eq!(
+        pools[0]
+            .quote_exact_in(amount_in, true, QuoteOptions::default())
+            .unwrap()
+            .amount_out,
+        quote_result.amount_out
+    );
+
+    let instructions = route
+        .get_swap_instructions(&pools, payer.pubkey(), 0)
+        .unwrap();
+    process_and_assert_ok(&instructions, &payer, &[&*payer], &mut banks_client).await;
+
+    let user_token_y = get_or_create_ata(
+        &payer,
+        &pair.token_y_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_y_state = fetch_token_account_state(&mut banks_client, user_token_y).await;
+    assert_eq!(user_token_y_state.amount, route.amount_out);
+}
+
+/// Later hops swap the minimum amount out of the previous hop, so a two hop route goes through
+/// when the price of its first pair moves within the slippage.
+#[tokio::test]
+async fn test_two_hop_route_swap_instructions_after_price_move() {
+    use commons::test_utils::*;
+
+    let mint_authority = Keypair::new();
+    let intermediate_mint = MintFixture::new(Pubkey::new_unique(), 9);
+
+    let mut first_hop = PoolFixture::two_sided(10, 0, 10, 1_000_000_000_000, 1_000_000_000_000);
+    first_hop.token_x.mint_authority = Some(mint_authority.pubkey());
+    first_hop.token_y = intermediate_mint;
+    let mut second_hop = PoolFixture::two_sided(10, 0, 10, 1_000_000_000_000, 1_000_000_000_000);
+    second_hop.token_x = intermediate_mint;
+    second_hop.token_y = MintFixture::new(Pubkey::new_unique(), 6);
+
+    let mut test = ProgramTest::default();
+    test.prefer_bpf(true);
+    test.add_program("./tests/artifacts/lb_clmm_prod", dlmm::ID, None);
+    let mut pools = vec![];
+    for fixture in [&first_hop, &second_hop] {
+        let accounts = fixture.build().unwrap();
+        for (pubkey, account) in accounts.to_account_map() {
+            if pubkey != solana_sdk::sysvar::clock::ID {
+                test.add_account(pubkey, account);
+            }
+        }
+        pools.push(accounts.to_pool_snapshot().unwrap());
+    }
+
+    let (mut banks_client, payer, _recent_blockhash) = test.start().await;
+    for pool in pools.iter_mut() {
+        pool.clock = get_clock(&mut banks_client).await;
+    }
+
+    let input_mint = first_hop.token_x.address;
+    let output_mint = second_hop.token_y.address;
+    let amount_in = 2_000_000_000_000;
+    let price_move_amount_in = 1_000_000_000_000;
+    let slippage_bps: u16 = 100;
+
+    // Another trader swaps against the first pair after the route is quoted, moving its price by
+    // a bin
+    let trader = Keypair::new();
+    process_and_assert_ok(
+        &[solana_sdk::system_instruction::transfer(
+            &payer.pubkey(),
+            &trader.pubkey(),
+            LAMPORTS_PER_SOL,
+        )],
+        &payer,
+        &[],
+        &mut banks_client,
+    )
+    .await;
+
+    for (user, amount) in [
+        (payer.pubkey(), amount_in),
+        (trader.pubkey(), price_move_amount_in),
+    ] {
+        let user_token_in = get_or_create_ata(&payer, &input_mint, &user, &mut banks_client).await;
+        mint_spl_tokens(
+            &payer,
+            &input_mint,
+            &user_token_in,
+            &mint_authority,
+            amount,
+            &mut banks_client,
+        )
+        .await;
+    }
+
+    let route = commons::quote_route(
+        &pools,
+        &[input_mint, intermediate_mint.address, output_mint],
+        amount_in,
+        1,
+        None,
+    )
+    .unwrap();
+    assert_eq!(route.hops.len(), 2);
+
+    let price_move = commons::quote_route(
+        &pools,
+        &[input_mint, intermediate_mint.address],
+        price_move_amount_in,
+        1,
+        None,
+    )
+    .unwrap();
+    let instructions = price_move
+        .get_swap_instructions(&pools, trader.pubkey(), 0)
+        .unwrap();
+    process_and_assert_ok(&instructions, &payer, &[&trader], &mut banks_client).await;
+
+    let instructions = route
+        .get_swap_instructions(&pools, payer.pubkey(), slippage_bps)
+        .unwrap();
+    process_and_assert_ok(&instructions, &payer, &[], &mut banks_client).await;
+
+    let first_leg = &route.hops[0].legs[0];
+    let second_leg = &route.hops[1].legs[0];
+    let second_hop_amount_in =
+        first_leg.quote.amount_out * (10_000 - u64::from(slippage_bps)) / 10_000;
+    let second_hop_min_amount_out = (u128::from(second_leg.quote.amount_out)
+        * u128::from(second_hop_amount_in)
+        / u128::from(second_leg.amount_in)) as u64
+        * (10_000 - u64::from(slippage_bps))
+        / 10_000;
+
+    // The first hop returned less than quoted, the second hop swapped its minimum amount out and
+    // the rest is left in the intermediate token account
+    let user_token_intermediate = get_or_create_ata(
+        &payer,
+        &intermediate_mint.address,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let intermediate_left = fetch_token_account_state(&mut banks_client, user_token_intermediate)
+        .await
+        .amount;
+    let first_hop_amount_out = second_hop_amount_in + intermediate_left;
+    assert!(first_hop_amount_out < route.hops[1].amount_in);
+    assert!(first_hop_amount_out >= second_hop_amount_in);
+
+    let user_token_out =
+        get_or_create_ata(&payer, &output_mint, &payer.pubkey(), &mut banks_client).await;
+    let amount_out = fetch_token_account_state(&mut banks_client, user_token_out)
+        .await
+        .amount;
+    assert!(amount_out >= second_hop_min_amount_out);
+    assert!(amount_out < route.amount_out);
+}
+
+#[tokio::test]
+async fn test_route_split_and_two_hop() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let mut banks_client = ctx.banks_client;
+
+    let amount_in = 40_000_000_000;
+    let pool = load_pool_snapshot(&mut banks_client, &pair).await;
+
+    // Split across two pairs of the same tokens
+    let parallel_pool = clone_pool_snapshot(&pool, pair.token_x_mint, pair.token_y_mint);
+    let pools = [pool.clone(), parallel_pool];
+
+    let single_route = commons::quote_best_route(
+        &pools[..1],
+        pair.token_x_mint,
+        pair.token_y_mint,
+        amount_in,
+        10,
+        None,
+    )
+    .unwrap();
+
+    let split_route = commons::quote_best_route(
+        &pools,
+        pair.token_x_mint,
+        pair.token_y_mint,
+        amount_in,
+        10,
+        None,
+    )
+    .unwrap();
+
+    let split_legs = &split_route.hops[0].legs;
+    assert_eq!(split_legs.len(), 2);
+    assert_eq!(split_legs[0].amount_in, amount_in / 2);
+    assert_eq!(split_legs[1].amount_in, amount_in / 2);
+    assert_eq!(
+        split_route.amount_out,
+        split_legs[0].quote.amount_out + split_legs[1].quote.amount_out
+    );
+    assert!(split_route.amount_out > single_route.amount_out);
+
+    // Two hops through an intermediate mint
+    let intermediate_mint = Pubkey::new_unique();
+    let first_hop_pool = clone_pool_snapshot(&pool, pair.token_x_mint, intermediate_mint);
+    let second_hop_pool = clone_pool_snapshot(&pool, intermediate_mint, pair.token_y_mint);
+
+    let two_hop_route = commons::quote_best_route(
+        &[first_hop_pool.clone(), second_hop_pool.clone()],
+        pair.token_x_mint,
+        pair.token_y_mint,
+        amount_in,
+        10,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(two_hop_route.hops.len(), 2);
+    assert_eq!(two_hop_route.hops[0].output_mint, intermediate_mint);
+    assert_eq!(
+        two_hop_route.hops[1].amount_in,
+        two_hop_route.hops[0].amount_out
+    );
+    assert_eq!(
+        two_hop_route.hops[1].legs[0].lb_pair,
+        second_hop_pool.lb_pair_pubkey
+    );
+    assert!(two_hop_route.hops[1].legs[0].swap_for_y);
+
+    // Direct route pays a single trading fee
+    let best_route = commons::quote_best_route(
+        &[first_hop_pool, second_hop_pool, pool],
+        pair.token_x_mint,
+        pair.token_y_mint,
+        amount_in,
+        10,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(best_route.hops.len(), 1);
+    assert_eq!(best_route.amount_out, single_route.amount_out);
+}
+
+#[tokio::test]
+async fn test_best_route_skips_only_unswappable_hops() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let mut banks_client = ctx.banks_client;
+
+    let amount_in = 40_000_000_000;
+    let pool = load_pool_snapshot(&mut banks_client, &pair).await;
+
+    // Dead end intermediate mint, and a pair without its bin arrays
+    let intermediate_mint = Pubkey::new_unique();
+    let dead_end_pool = clone_pool_snapshot(&pool, pair.token_x_mint, intermediate_mint);
+    let mut empty_pool = clone_pool_snapshot(&pool, pair.token_x_mint, intermediate_mint);
+    empty_pool.bin_arrays.clear();
+
+    let err = commons::quote_route(
+        &[dead_end_pool.clone()],
+        &[pair.token_x_mint, intermediate_mint, pair.token_y_mint],
+        amount_in,
+        10,
+        None,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RouteHopError>(),
+        Some(RouteHopError::NoPair { input_mint, .. }) if *input_mint == intermediate_mint
+    ));
+
+    let err = commons::quote_route(
+        &[empty_pool],
+        &[pair.token_x_mint, intermediate_mint],
+        amount_in,
+        10,
+        None,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RouteHopError>(),
+        Some(RouteHopError::InsufficientLiquidity { .. })
+    ));
+
+    let route = commons::quote_best_route(
+        &[dead_end_pool.clone(), pool.clone()],
+        pair.token_x_mint,
+        pair.token_y_mint,
+        amount_in,
+        10,
+        None,
+    )
+    .unwrap();
+    assert_eq!(route.hops.len(), 1);
+
+    // Invalid pair state is an error, not a route to skip
+    let mut invalid_pool = pool;
+    invalid_pool.lb_pair.status = u8::MAX;
+
+    let err = commons::quote_best_route(
+        &[dead_end_pool, invalid_pool],
+        pair.token_x_mint,
+        pair.token_y_mint,
+        amount_in,
+        10,
+        None,
+    )
+    .unwrap_err();
+    assert!(!err.is::<RouteHopError>());
+}
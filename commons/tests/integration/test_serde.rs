@@ -0,0 +1,85 @@
+use crate::*;
+use commons::dlmm::accounts::PositionV2;
+
+#[tokio::test]
+async fn test_serde() {
+    let StrategyDepositTest {
+        mut banks_client,
+        pair,
+        lb_pair_state,
+        position,
+        ..
+    } = setup_strategy_deposit_test().await;
+
+    let accounts = fetch_dlmm_client_accounts(&mut banks_client, &pair, position.pubkey()).await;
+
+    // Program accounts, with pubkeys and 128 bits integers as strings
+    let lb_pair_account = DlmmAccount::decode(&accounts[&pair.lb_pair].data).unwrap();
+    let lb_pair_json = serde_json::to_value(&lb_pair_account).unwrap();
+    assert_eq!(
+        lb_pair_json["LbPair"]["token_x_mint"],
+        lb_pair_state.token_x_mint.to_string()
+    );
+    assert_eq!(lb_pair_json["LbPair"]["active_id"], lb_pair_state.active_id);
+    assert_eq!(
+        lb_pair_json["LbPair"]["parameters"]["base_factor"],
+        lb_pair_state.parameters.base_factor
+    );
+    let DlmmAccount::LbPair(decoded_lb_pair) = serde_json::from_value(lb_pair_json).unwrap() else {
+        panic!("LbPair not deserialized");
+    };
+    assert_eq!(
+        bytemuck::bytes_of(&decoded_lb_pair),
+        bytemuck::bytes_of(&lb_pair_state)
+    );
+
+    let position_state: PositionV2 =
+        pod_read_unaligned_skip_disc(&accounts[&position.pubkey()].data).unwrap();
+    let position_json =
+        serde_json::to_value(DlmmAccount::decode(&accounts[&position.pubkey()].data).unwrap())
+            .unwrap();
+    assert_eq!(
+        position_json["PositionV2"]["lower_bin_id"],
+        position_state.lower_bin_id
+    );
+    assert_eq!(
+        position_json["PositionV2"]["liquidity_shares"][0],
+        position_state.liquidity_shares[0].to_string()
+    );
+
+    // SDK results
+    let client = DlmmClient::create(accounts, pair.lb_pair).await.unwrap();
+    let lb_position = client.get_position(position.pubkey()).await.unwrap();
+    let lb_position_json = serde_json::to_value(&lb_position).unwrap();
+    assert_eq!(lb_position_json["pubkey"], position.pubkey().to_string());
+    assert_eq!(
+        lb_position_json["position_data"]["bins"][0]["price"],
+        lb_position.position_data.bins[0].price.to_string()
+    );
+    let decoded_lb_position: LbPosition = serde_json::from_value(lb_position_json).unwrap();
+    assert_eq!(
+        decoded_lb_position.position_data.bins.len(),
+        lb_position.position_data.bins.len()
+    );
+
+    let quote = SwapExactInQuote {
+        amount_out: 1_000,
+        fee: 10,
+        protocol_fee: 1,
+        host_fee: 0,
+        protocol_fee_after_host_fee: 1,
+        prices: SwapQuotePrices {
+            start_active_id: 0,
+            start_price: 1 << 64,
+            end_active_id: -1,
+            end_price: u128::MAX,
+            execution_price: 1 << 64,
+            effective_price: 1 << 64,
+            price_impact_bps: 0,
+        },
+    };
+    let quote_json = serde_json::to_value(&quote).unwrap();
+    assert_eq!(quote_json["prices"]["end_price"], u128::MAX.to_string());
+    let decoded_quote: SwapExactInQuote = serde_json::from_value(quote_json).unwrap();
+    assert_eq!(decoded_quote.prices.end_price, u128::MAX);
+}
@@ -1,94 +1,5 @@
 use crate::*;
 
-struct SplTestPair {
-    lb_pair: Pubkey,
-    reserve_x: Pubkey,
-    reserve_y: Pubkey,
-    token_x_mint: Pubkey,
-    token_y_mint: Pubkey,
-    oracle: Pubkey,
-    bin_array_1: Pubkey,
-    bin_array_2: Pubkey,
-}
-
-fn setup_spl_test_pair() -> (ProgramTest, SplTestPair) {
-    let mut test = ProgramTest::default();
-    test.prefer_bpf(true);
-    test.add_program("./tests/artifacts/lb_clmm_prod", dlmm::ID, None);
-
-    let lb_pair = Pubkey::from_str_const("EtAdVRLFH22rjWh3mcUasKFF27WtHhsaCvK27tPFFWig");
-    let reserve_x = Pubkey::from_str_const("BmW4cCRpJwwL8maFB1AoAuEQf96t64Eq5gUvXikZardM");
-    let reserve_y = Pubkey::from_str_const("FDZDrPtCjmSHeq14goCxp5pCJSRekSXY3XSgGz5Rvass");
-    let token_x_mint = Pubkey::from_str_const("Df6yfrKC8kZE3KNkrHERKzAetSxbrWeniQfyJY4Jpump");
-    let token_y_mint = anchor_spl::token::spl_token::native_mint::id();
-    let oracle = Pubkey::from_str_const("Fnkg415DEx72GSPooKUWTbPS9wzKucQe4qnvFrrvcZK2");
-    let bin_array_1 = Pubkey::from_str_const("5Sm2ecMeqohRkNpFJPWSqHL1BkA7AEW4ck8TmdF1gD4t");
-    let bin_array_2 = Pubkey::from_str_const("E6gur9Jw8675DCR7GpJVhoSrkruRgt8EdEVqLAc5RLUt");
-
-    test.add_account_with_file_data(
-        lb_pair,
-        10 * LAMPORTS_PER_SOL,
-        dlmm::ID,
-        "EtAdVRLFH22rjWh3mcUasKFF27WtHhsaCvK27tPFFWig/lb_pair.bin",
-    );
-
-    test.add_account_with_file_data(
-        oracle,
-        10 * LAMPORTS_PER_SOL,
-        dlmm::ID,
-        "EtAdVRLFH22rjWh3mcUasKFF27WtHhsaCvK27tPFFWig/oracle.bin",
-    );
-
-    test.add_account_with_file_data(
-        bin_array_1,
-        10 * LAMPORTS_PER_SOL,
-        dlmm::ID,
-        "EtAdVRLFH22rjWh3mcUasKFF27WtHhsaCvK27tPFFWig/bin_array_1.bin",
-    );
-
-    test.add_account_with_file_data(
-        bin_array_2,
-        10 * LAMPORTS_PER_SOL,
-        dlmm::ID,
-        "EtAdVRLFH22rjWh3mcUasKFF27WtHhsaCvK27tPFFWig/bin_array_2.bin",
-    );
-
-    test.add_account_with_file_data(
-        token_x_mint,
-        10 * LAMPORTS_PER_SOL,
-        spl_token::id(),
-        "EtAdVRLFH22rjWh3mcUasKFF27WtHhsaCvK27tPFFWig/token_x_mint.bin",
-    );
-
-    test.add_account_with_file_data(
-        reserve_x,
-        10 * LAMPORTS_PER_SOL,
-        spl_token::id(),
-        "EtAdVRLFH22rjWh3mcUasKFF27WtHhsaCvK27tPFFWig/reserve_x.bin",
-    );
-
-    test.add_account_with_file_data(
-        reserve_y,
-        10 * LAMPORTS_PER_SOL,
-        spl_token::id(),
-        "EtAdVRLFH22rjWh3mcUasKFF27WtHhsaCvK27tPFFWig/reserve_y.bin",
-    );
-
-    (
-        test,
-        SplTestPair {
-            lb_pair,
-            reserve_x,
-            reserve_y,
-            token_x_mint,
-            token_y_mint,
-            oracle,
-            bin_array_1,
-            bin_array_2,
-        },
-    )
-}
-
 #[tokio::test]
 async fn test_swap_exact_out() {
     let (
@@ -133,6 +44,7 @@ async fn test_swap_exact_out() {
         &lb_pair_state,
         out_amount,
         false,
+        QuoteOptions::default(),
         bin_arrays,
         None,
         &clock,
@@ -245,6 +157,7 @@ async fn test_swap() {
         &lb_pair_state,
         amount_in,
         false,
+        QuoteOptions::default(),
         bin_arrays,
         None,
         &clock,
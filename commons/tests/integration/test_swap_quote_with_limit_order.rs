@@ -1,121 +1,8 @@
 use crate::*;
-use commons::dlmm::accounts::BinArray;
+use commons::dlmm::accounts::{BinArray, LbPair};
 use solana_sdk::signature::Keypair;
-use std::collections::HashMap;
 use std::rc::Rc;
 
-const FIXTURE_FOLDER: &str = "9t3EyC9FweyL7PBWvKz3mrXg8B9fwFc9SK3QxM4ENqhd";
-
-struct SwapQuoteTestPair {
-    lb_pair: Pubkey,
-    reserve_x: Pubkey,
-    reserve_y: Pubkey,
-    token_x_mint: Pubkey,
-    token_y_mint: Pubkey,
-    oracle: Pubkey,
-    bin_array_1: Pubkey,
-    bin_array_2: Pubkey,
-    mint_authority: Keypair,
-}
-
-fn setup_swap_quote_test_pair() -> (ProgramTest, SwapQuoteTestPair) {
-    let mut test = ProgramTest::default();
-    test.prefer_bpf(true);
-    test.add_program("./tests/artifacts/lb_clmm_prod", dlmm::ID, None);
-
-    let lb_pair = Pubkey::from_str_const("9t3EyC9FweyL7PBWvKz3mrXg8B9fwFc9SK3QxM4ENqhd");
-    let reserve_x = Pubkey::from_str_const("DDVpGjEz7Ay6QSyCJX5FpJhnYYYFUJ5kNdwy3ZrQP9u6");
-    let reserve_y = Pubkey::from_str_const("9LqVUn45rtZqMmNSr7wSpJUy2Qqd6VE5q3QN7t7oJL8v");
-    let token_x_mint = Pubkey::from_str_const("BBZU4HYvY4qMGE5MbWsVxGweGBZJqGRsgH8tAEAKusNk");
-    let token_y_mint = Pubkey::from_str_const("31iVdsS8fkURXg737XQwYhXVAuGv2vNYHjyDiURStkaU");
-    let oracle = Pubkey::from_str_const("FviPunh9kSt1XRBdHiWd5FNdTe5MJmqGfSEQdNfHTQV4");
-    // bin_array_1 = index -1 (bins -70 to -1)
-    let bin_array_1 = Pubkey::from_str_const("338HBraHxVupeftangX6jySecbND4osxcJjjMSW7qmMs");
-    // bin_array_2 = index 0 (bins 0 to 69)
-    let bin_array_2 = Pubkey::from_str_const("28BX6QycwTKx3CqswpJQs7hJCmoUs469Qt4maKMdhgmQ");
-
-    let mint_authority = Keypair::new();
-
-    test.add_account_with_file_data(
-        lb_pair,
-        10 * LAMPORTS_PER_SOL,
-        dlmm::ID,
-        &format!("{FIXTURE_FOLDER}/lb_pair.bin"),
-    );
-
-    test.add_account_with_file_data(
-        oracle,
-        10 * LAMPORTS_PER_SOL,
-        dlmm::ID,
-        &format!("{FIXTURE_FOLDER}/oracle.bin"),
-    );
-
-    test.add_account_with_file_data(
-        bin_array_1,
-        10 * LAMPORTS_PER_SOL,
-        dlmm::ID,
-        &format!("{FIXTURE_FOLDER}/bin_array_1.bin"),
-    );
-
-    test.add_account_with_file_data(
-        bin_array_2,
-        10 * LAMPORTS_PER_SOL,
-        dlmm::ID,
-        &format!("{FIXTURE_FOLDER}/bin_array_2.bin"),
-    );
-
-    test.add_account_with_file_data(
-        reserve_x,
-        10 * LAMPORTS_PER_SOL,
-        spl_token::id(),
-        &format!("{FIXTURE_FOLDER}/reserve_x.bin"),
-    );
-
-    test.add_account_with_file_data(
-        reserve_y,
-        10 * LAMPORTS_PER_SOL,
-        spl_token::id(),
-        &format!("{FIXTURE_FOLDER}/reserve_y.bin"),
-    );
-
-    // Patch mint authority to our test keypair so we can mint tokens to users
-    for (mint_pubkey, filename) in [
-        (token_x_mint, "token_x_mint.bin"),
-        (token_y_mint, "token_y_mint.bin"),
-    ] {
-        let fixture_path = format!("tests/fixtures/{FIXTURE_FOLDER}/{filename}");
-        let mut mint_data = std::fs::read(&fixture_path).unwrap();
-        // SPL Mint layout: COption<Pubkey> tag(4) + pubkey(32) at offset 0
-        mint_data[0..4].copy_from_slice(&1u32.to_le_bytes());
-        mint_data[4..36].copy_from_slice(mint_authority.pubkey().as_ref());
-        test.add_account(
-            mint_pubkey,
-            solana_sdk::account::Account {
-                lamports: 10 * LAMPORTS_PER_SOL,
-                data: mint_data,
-                owner: spl_token::id(),
-                executable: false,
-                rent_epoch: 0,
-            },
-        );
-    }
-
-    (
-        test,
-        SwapQuoteTestPair {
-            lb_pair,
-            reserve_x,
-            reserve_y,
-            token_x_mint,
-            token_y_mint,
-            oracle,
-            bin_array_1,
-            bin_array_2,
-            mint_authority,
-        },
-    )
-}
-
 /// Build remaining accounts for swap: bin arrays in traversal order.
 /// For swap_for_y=true (X→Y, left): active bin's array first, then lower index.
 /// For swap_for_y=false (Y→X, right): active bin's array first, then higher index.
@@ -191,6 +78,7 @@ async fn test_swap_exact_in_x_to_y_with_limit_order() {
         &lb_pair_state,
         amount_in,
         swap_for_y,
+        QuoteOptions::default(),
         bin_arrays,
         None,
         &clock,
@@ -324,6 +212,7 @@ async fn test_swap_exact_in_y_to_x_with_limit_order() {
         &lb_pair_state,
         amount_in,
         swap_for_y,
+        QuoteOptions::default(),
         bin_arrays,
         None,
         &clock,
@@ -460,6 +349,7 @@ async fn test_swap_exact_out_x_to_y_with_limit_order() {
         &lb_pair_state,
         out_amount,
         swap_for_y,
+        QuoteOptions::default(),
         bin_arrays,
         None,
         &clock,
@@ -594,6 +484,7 @@ async fn test_swap_exact_out_y_to_x_with_limit_order() {
         &lb_pair_state,
         out_amount,
         swap_for_y,
+        QuoteOptions::default(),
         bin_arrays,
         None,
         &clock,
@@ -756,6 +647,7 @@ async fn run_swap_exact_out_active_bin_partition(kind: ActiveBinOutKind) {
         &lb_pair_state,
         out_amount,
         swap_for_y,
+        QuoteOptions::default(),
         bin_arrays,
         None,
         &clock,
@@ -838,3 +730,1159 @@ async fn test_swap_exact_out_mm_plus_processed_active_bin() {
 async fn test_swap_exact_out_mm_plus_processed_plus_open_active_bin() {
     run_swap_exact_out_active_bin_partition(ActiveBinOutKind::MmPlusProcessedPlusOpen).await;
 }
+
+/// Per-bin fills of the detailed quote must add up to the aggregate quote and match the
+/// liquidity actually consumed from each bin on chain.
+#[tokio::test]
+async fn test_swap_exact_in_detailed_bin_fills() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let payer = Rc::new(ctx.payer);
+    let mut banks_client = ctx.banks_client;
+
+    let amount_in: u64 = 40_000_000_000;
+
+    let user_token_in = get_or_create_ata(
+        &payer,
+        &pair.token_x_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_out = get_or_create_ata(
+        &payer,
+        &pair.token_y_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+
+    mint_spl_tokens(
+        &payer,
+        &pair.token_x_mint,
+        &user_token_in,
+        &pair.mint_authority,
+        amount_in,
+        &mut banks_client,
+    )
+    .await;
+
+    let (lb_pair_state, bin_arrays, mint_x_account, mint_y_account, clock) = fetch_swap_state(
+        &mut banks_client,
+        pair.lb_pair,
+        &[pair.bin_array_1, pair.bin_array_2],
+    )
+    .await;
+
+    let swap_for_y = true;
+    let detailed_quote = commons::quote::quote_exact_in_detailed(
+        pair.lb_pair,
+        &lb_pair_state,
+        amount_in,
+        swap_for_y,
+        QuoteOptions::default(),
+        bin_arrays.clone(),
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+
+    let bin_fills = &detailed_quote.bin_fills;
+    assert!(bin_fills.len() >= 5);
+    assert_eq!(
+        bin_fills.iter().map(|f| f.amount_in).sum::<u64>(),
+        amount_in
+    );
+    assert_eq!(
+        bin_fills.iter().map(|f| f.amount_out).sum::<u64>(),
+        detailed_quote.quote.amount_out
+    );
+    assert_eq!(
+        bin_fills.iter().map(|f| f.fee).sum::<u64>(),
+        detailed_quote.quote.fee
+    );
+    assert_eq!(
+        bin_fills.iter().map(|f| f.protocol_fee).sum::<u64>(),
+        detailed_quote.quote.protocol_fee
+    );
+    assert!(
+        bin_fills.iter().any(|f| f.limit_order_amount_out() > 0),
+        "Swap should consume limit order liquidity"
+    );
+
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let main_accounts = dlmm::client::accounts::Swap2 {
+        lb_pair: pair.lb_pair,
+        oracle: pair.oracle,
+        bin_array_bitmap_extension: Some(dlmm::ID),
+        reserve_x: pair.reserve_x,
+        reserve_y: pair.reserve_y,
+        user_token_in,
+        user_token_out,
+        token_x_mint: pair.token_x_mint,
+        token_y_mint: pair.token_y_mint,
+        host_fee_in: Some(dlmm::ID),
+        user: payer.pubkey(),
+        token_x_program: spl_token::id(),
+        token_y_program: spl_token::id(),
+        program: dlmm::ID,
+        event_authority,
+        memo_program: spl_memo::id(),
+    }
+    .to_account_metas(None);
+
+    let mut all_accounts = main_accounts.to_vec();
+    all_accounts.extend(bin_array_remaining_accounts(&pair, swap_for_y));
+
+    let swap_ix = Instruction {
+        program_id: dlmm::ID,
+        accounts: all_accounts,
+        data: dlmm::client::args::Swap2 {
+            amount_in,
+            min_amount_out: 0,
+            remaining_accounts_info: RemainingAccountsInfo { slices: vec![] },
+        }
+        .data(),
+    };
+
+    process_and_assert_ok(&[swap_ix], &payer, &[&*payer], &mut banks_client).await;
+
+    let (lb_pair_state_after, bin_arrays_after, _, _, _) = fetch_swap_state(
+        &mut banks_client,
+        pair.lb_pair,
+        &[pair.bin_array_1, pair.bin_array_2],
+    )
+    .await;
+
+    assert_eq!(
+        bin_fills.last().unwrap().bin_id,
+        lb_pair_state_after.active_id
+    );
+
+    let prices = &detailed_quote.quote.prices;
+    assert_eq!(prices.start_active_id, lb_pair_state.active_id);
+    assert_eq!(prices.end_active_id, lb_pair_state_after.active_id);
+    assert!(prices.end_price <= prices.execution_price);
+    assert!(prices.execution_price <= prices.start_price);
+    assert!(prices.effective_price < prices.execution_price);
+    assert!(prices.price_impact_bps > 0);
+
+    for fill in bin_fills {
+        let bin_array_key = BinArray::bin_id_to_bin_array_key(pair.lb_pair, fill.bin_id).unwrap();
+        let bin_before = bin_arrays[&bin_array_key].get_bin(fill.bin_id).unwrap();
+        let bin_after = bin_arrays_after[&bin_array_key]
+            .get_bin(fill.bin_id)
+            .unwrap();
+
+        let (open_before, processed_before) =
+            bin_before.get_limit_order_amounts_by_direction(swap_for_y);
+        let (open_after, processed_after) =
+            bin_after.get_limit_order_amounts_by_direction(swap_for_y);
+
+        assert_eq!(
+            bin_before.amount_y - bin_after.amount_y,
+            fill.mm_amount_out,
+            "MM amount consumed in bin {} must match quote",
+            fill.bin_id
+        );
+        assert_eq!(
+            open_before + processed_before - open_after - processed_after,
+            fill.limit_order_amount_out(),
+            "Limit order amount consumed in bin {} must match quote",
+            fill.bin_id
+        );
+    }
+}
+
+fn swap_with_price_impact2_ix(
+    pair: &SwapQuoteTestPair,
+    user: Pubkey,
+    user_token_in: Pubkey,
+    user_token_out: Pubkey,
+    amount_in: u64,
+    active_id: i32,
+    max_price_impact_bps: u16,
+) -> Instruction {
+    let swap_for_y = true;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let main_accounts = dlmm::client::accounts::SwapWithPriceImpact2 {
+        lb_pair: pair.lb_pair,
+        oracle: pair.oracle,
+        bin_array_bitmap_extension: Some(dlmm::ID),
+        reserve_x: pair.reserve_x,
+        reserve_y: pair.reserve_y,
+        user_token_in,
+        user_token_out,
+        token_x_mint: pair.token_x_mint,
+        token_y_mint: pair.token_y_mint,
+        host_fee_in: Some(dlmm::ID),
+        user,
+        token_x_program: spl_token::id(),
+        token_y_program: spl_token::id(),
+        program: dlmm::ID,
+        event_authority,
+        memo_program: spl_memo::id(),
+    }
+    .to_account_metas(None);
+
+    let mut all_accounts = main_accounts.to_vec();
+    all_accounts.extend(bin_array_remaining_accounts(pair, swap_for_y));
+
+    Instruction {
+        program_id: dlmm::ID,
+        accounts: all_accounts,
+        data: dlmm::client::args::SwapWithPriceImpact2 {
+            amount_in,
+            active_id: Some(active_id),
+            max_price_impact_bps,
+            remaining_accounts_info: RemainingAccountsInfo { slices: vec![] },
+        }
+        .data(),
+    }
+}
+
+/// Swapping the amount quoted by `quote_exact_in_with_price_impact` must be accepted by the program
+/// and match the quote. When the quote is a partial fill, the whole amount of input must be
+/// rejected.
+async fn run_swap_with_price_impact(max_price_impact_bps: u16) -> SwapWithPriceImpactQuote {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let payer = Rc::new(ctx.payer);
+    let mut banks_client = ctx.banks_client;
+
+    let amount_in: u64 = 40_000_000_000;
+
+    let user_token_in = get_or_create_ata(
+        &payer,
+        &pair.token_x_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_out = get_or_create_ata(
+        &payer,
+        &pair.token_y_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+
+    mint_spl_tokens(
+        &payer,
+        &pair.token_x_mint,
+        &user_token_in,
+        &pair.mint_authority,
+        amount_in,
+        &mut banks_client,
+    )
+    .await;
+
+    let (lb_pair_state, bin_arrays, mint_x_account, mint_y_account, clock) = fetch_swap_state(
+        &mut banks_client,
+        pair.lb_pair,
+        &[pair.bin_array_1, pair.bin_array_2],
+    )
+    .await;
+
+    let swap_for_y = true;
+
+    let quote_result = commons::quote::quote_exact_in_with_price_impact(
+        pair.lb_pair,
+        &lb_pair_state,
+        amount_in,
+        swap_for_y,
+        Some(lb_pair_state.active_id),
+        max_price_impact_bps,
+        QuoteOptions::default(),
+        bin_arrays,
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+
+    assert_eq!(quote_result.amount_in + quote_result.amount_left, amount_in);
+    assert!(quote_result.amount_out >= quote_result.min_amount_out);
+
+    if quote_result.amount_left > 0 {
+        let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+            &[swap_with_price_impact2_ix(
+                &pair,
+                payer.pubkey(),
+                user_token_in,
+                user_token_out,
+                amount_in,
+                lb_pair_state.active_id,
+                max_price_impact_bps,
+            )],
+            Some(&payer.pubkey()),
+            &[&*payer],
+            recent_blockhash,
+        );
+        let simulation = banks_client.simulate_transaction(tx).await.unwrap();
+        let Some(Err(tx_error)) = simulation.result else {
+            panic!("Unexpected simulation result {:?}", simulation.result);
+        };
+        assert_eq!(
+            get_dlmm_error_from_transaction_error(&tx_error),
+            Some(DlmmError::ExceededAmountSlippageTolerance)
+        );
+    }
+
+    let user_token_out_state_before =
+        fetch_token_account_state(&mut banks_client, user_token_out).await;
+
+    let swap_ix = swap_with_price_impact2_ix(
+        &pair,
+        payer.pubkey(),
+        user_token_in,
+        user_token_out,
+        quote_result.amount_in,
+        lb_pair_state.active_id,
+        max_price_impact_bps,
+    );
+    process_and_assert_ok(&[swap_ix], &payer, &[&*payer], &mut banks_client).await;
+
+    let lb_pair_state_after = fetch_lb_pair(&mut banks_client, pair.lb_pair).await;
+    assert_eq!(
+        lb_pair_state_after.active_id,
+        quote_result.prices.end_active_id
+    );
+
+    let user_token_out_state_after =
+        fetch_token_account_state(&mut banks_client, user_token_out).await;
+
+    assert_eq!(
+        user_token_out_state_after.amount - user_token_out_state_before.amount,
+        quote_result.amount_out,
+        "Actual swap out amount must match quote"
+    );
+
+    quote_result
+}
+
+/// Price impact swap is fully filled from 124 bps for this swap.
+#[tokio::test]
+async fn test_swap_with_price_impact_x_to_y_with_limit_order() {
+    let quote_result = run_swap_with_price_impact(124).await;
+    assert_eq!(quote_result.amount_left, 0);
+}
+
+/// Below 124 bps, the price impact swap is partially filled with the bins swapped before the price
+/// impact is reached.
+#[tokio::test]
+async fn test_swap_with_price_impact_partial_fill_x_to_y_with_limit_order() {
+    let quote_result = run_swap_with_price_impact(123).await;
+    assert!(quote_result.amount_in > 0);
+    assert!(quote_result.amount_left > 0);
+}
+
+/// Swapping the amount quoted by `quote_exact_in_to_bin` must end exactly at the target bin, and
+/// 1 unit less must not reach it.
+async fn run_swap_exact_in_to_bin(bin_delta: i32) {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let payer = Rc::new(ctx.payer);
+    let mut banks_client = ctx.banks_client;
+
+    let (lb_pair_state, bin_arrays, mint_x_account, mint_y_account, clock) = fetch_swap_state(
+        &mut banks_client,
+        pair.lb_pair,
+        &[pair.bin_array_1, pair.bin_array_2],
+    )
+    .await;
+
+    let target_active_id = lb_pair_state.active_id + bin_delta;
+
+    let quote_result = commons::quote::quote_exact_in_to_bin(
+        pair.lb_pair,
+        &lb_pair_state,
+        target_active_id,
+        QuoteOptions::default(),
+        bin_arrays.clone(),
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+
+    let swap_for_y = quote_result.swap_for_y;
+    let amount_in = quote_result.amount_in;
+    assert_eq!(swap_for_y, bin_delta < 0);
+    assert_eq!(quote_result.prices.end_active_id, target_active_id);
+
+    let short_quote_result = commons::quote::quote_exact_in(
+        pair.lb_pair,
+        &lb_pair_state,
+        amount_in - 1,
+        swap_for_y,
+        QuoteOptions::default(),
+        bin_arrays.clone(),
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+    assert_ne!(short_quote_result.prices.end_active_id, target_active_id);
+
+    let exact_in_quote_result = commons::quote::quote_exact_in(
+        pair.lb_pair,
+        &lb_pair_state,
+        amount_in,
+        swap_for_y,
+        QuoteOptions::default(),
+        bin_arrays,
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+    assert_eq!(exact_in_quote_result.amount_out, quote_result.amount_out);
+    assert_eq!(exact_in_quote_result.fee, quote_result.fee);
+
+    let (token_in_mint, token_out_mint) = if swap_for_y {
+        (pair.token_x_mint, pair.token_y_mint)
+    } else {
+        (pair.token_y_mint, pair.token_x_mint)
+    };
+
+    let user_token_in =
+        get_or_create_ata(&payer, &token_in_mint, &payer.pubkey(), &mut banks_client).await;
+    let user_token_out =
+        get_or_create_ata(&payer, &token_out_mint, &payer.pubkey(), &mut banks_client).await;
+
+    mint_spl_tokens(
+        &payer,
+        &token_in_mint,
+        &user_token_in,
+        &pair.mint_authority,
+        amount_in,
+        &mut banks_client,
+    )
+    .await;
+
+    let user_token_out_state_before =
+        fetch_token_account_state(&mut banks_client, user_token_out).await;
+
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let main_accounts = dlmm::client::accounts::Swap2 {
+        lb_pair: pair.lb_pair,
+        oracle: pair.oracle,
+        bin_array_bitmap_extension: Some(dlmm::ID),
+        reserve_x: pair.reserve_x,
+        reserve_y: pair.reserve_y,
+        user_token_in,
+        user_token_out,
+        token_x_mint: pair.token_x_mint,
+        token_y_mint: pair.token_y_mint,
+        host_fee_in: Some(dlmm::ID),
+        user: payer.pubkey(),
+        token_x_program: spl_token::id(),
+        token_y_program: spl_token::id(),
+        program: dlmm::ID,
+        event_authority,
+        memo_program: spl_memo::id(),
+    }
+    .to_account_metas(None);
+
+    let mut all_accounts = main_accounts.to_vec();
+    all_accounts.extend(bin_array_remaining_accounts(&pair, swap_for_y));
+
+    let swap_ix = Instruction {
+        program_id: dlmm::ID,
+        accounts: all_accounts,
+        data: dlmm::client::args::Swap2 {
+            amount_in,
+            min_amount_out: 0,
+            remaining_accounts_info: RemainingAccountsInfo { slices: vec![] },
+        }
+        .data(),
+    };
+
+    process_and_assert_ok(&[swap_ix], &payer, &[&*payer], &mut banks_client).await;
+
+    let lb_pair_state_after = fetch_lb_pair(&mut banks_client, pair.lb_pair).await;
+    assert_eq!(lb_pair_state_after.active_id, target_active_id);
+
+    let user_token_out_state_after =
+        fetch_token_account_state(&mut banks_client, user_token_out).await;
+
+    assert_eq!(
+        user_token_out_state_after.amount - user_token_out_state_before.amount,
+        quote_result.amount_out,
+        "Actual swap out amount must match quote"
+    );
+}
+
+#[tokio::test]
+async fn test_swap_exact_in_to_bin_x_to_y_with_limit_order() {
+    run_swap_exact_in_to_bin(-3).await;
+}
+
+#[tokio::test]
+async fn test_swap_exact_in_to_bin_y_to_x_with_limit_order() {
+    run_swap_exact_in_to_bin(3).await;
+}
+
+/// Each depth level must match an exact in quote of its cumulative amount in, which drains the
+/// bins up to and including the level bin.
+#[tokio::test]
+async fn test_swap_depth_quote_with_limit_order() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let mut banks_client = ctx.banks_client;
+
+    let (lb_pair_state, bin_arrays, mint_x_account, mint_y_account, clock) = fetch_swap_state(
+        &mut banks_client,
+        pair.lb_pair,
+        &[pair.bin_array_1, pair.bin_array_2],
+    )
+    .await;
+
+    let bin_limit = 5;
+    let depth_quote = commons::quote::quote_depth(
+        pair.lb_pair,
+        &lb_pair_state,
+        bin_limit,
+        QuoteOptions::default(),
+        &bin_arrays,
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+
+    for (swap_for_y, levels) in [(true, &depth_quote.x_to_y), (false, &depth_quote.y_to_x)] {
+        assert_eq!(levels.len(), usize::from(bin_limit));
+
+        for (i, level) in levels.iter().enumerate() {
+            if i > 0 {
+                let previous_level = &levels[i - 1];
+                assert!(level.amount_in > previous_level.amount_in);
+                assert!(level.amount_out > previous_level.amount_out);
+                if swap_for_y {
+                    assert!(level.bin_id < previous_level.bin_id);
+                    assert!(level.price < previous_level.price);
+                } else {
+                    assert!(level.bin_id > previous_level.bin_id);
+                    assert!(level.price > previous_level.price);
+                }
+            }
+
+            let quote_result = commons::quote::quote_exact_in(
+                pair.lb_pair,
+                &lb_pair_state,
+                level.amount_in,
+                swap_for_y,
+                QuoteOptions::default(),
+                bin_arrays.clone(),
+                None,
+                &clock,
+                &mint_x_account,
+                &mint_y_account,
+            )
+            .unwrap();
+
+            assert_eq!(quote_result.amount_out, level.amount_out);
+            assert_eq!(quote_result.fee, level.fee);
+            assert_eq!(quote_result.protocol_fee, level.protocol_fee);
+            assert_eq!(quote_result.prices.end_active_id, level.bin_id);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn process_swap2(
+    pair: &SwapQuoteTestPair,
+    payer: &Keypair,
+    user_token_x: Pubkey,
+    user_token_y: Pubkey,
+    amount_in: u64,
+    swap_for_y: bool,
+    bin_array_pubkeys: &[Pubkey],
+    host_fee_in: Option<Pubkey>,
+    banks_client: &mut BanksClient,
+) {
+    let (user_token_in, user_token_out) = if swap_for_y {
+        (user_token_x, user_token_y)
+    } else {
+        (user_token_y, user_token_x)
+    };
+
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let main_accounts = dlmm::client::accounts::Swap2 {
+        lb_pair: pair.lb_pair,
+        oracle: pair.oracle,
+        bin_array_bitmap_extension: Some(dlmm::ID),
+        reserve_x: pair.reserve_x,
+        reserve_y: pair.reserve_y,
+        user_token_in,
+        user_token_out,
+        token_x_mint: pair.token_x_mint,
+        token_y_mint: pair.token_y_mint,
+        host_fee_in: host_fee_in.or(Some(dlmm::ID)),
+        user: payer.pubkey(),
+        token_x_program: spl_token::id(),
+        token_y_program: spl_token::id(),
+        program: dlmm::ID,
+        event_authority,
+        memo_program: spl_memo::id(),
+    }
+    .to_account_metas(None);
+
+    let mut all_accounts = main_accounts.to_vec();
+    all_accounts.extend(
+        bin_array_pubkeys
+            .iter()
+            .map(|&pubkey| AccountMeta::new(pubkey, false)),
+    );
+
+    let swap_ix = Instruction {
+        program_id: dlmm::ID,
+        accounts: all_accounts,
+        data: dlmm::client::args::Swap2 {
+            amount_in,
+            min_amount_out: 0,
+            remaining_accounts_info: RemainingAccountsInfo { slices: vec![] },
+        }
+        .data(),
+    };
+
+    process_and_assert_ok(&[swap_ix], payer, &[payer], banks_client).await;
+}
+
+/// Chained swap simulations must track the pair and bin liquidity state of the program, so the
+/// second swap is quoted with the variable fee accumulated by the first one.
+#[tokio::test]
+async fn test_simulate_chained_swaps_with_limit_order() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let payer = Rc::new(ctx.payer);
+    let mut banks_client = ctx.banks_client;
+
+    let user_token_x = get_or_create_ata(
+        &payer,
+        &pair.token_x_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_y = get_or_create_ata(
+        &payer,
+        &pair.token_y_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+
+    let amount_x_in: u64 = 40_000_000_000;
+    let amount_y_in: u64 = 45_000_000_000_000;
+
+    mint_spl_tokens(
+        &payer,
+        &pair.token_x_mint,
+        &user_token_x,
+        &pair.mint_authority,
+        amount_x_in,
+        &mut banks_client,
+    )
+    .await;
+    mint_spl_tokens(
+        &payer,
+        &pair.token_y_mint,
+        &user_token_y,
+        &pair.mint_authority,
+        amount_y_in,
+        &mut banks_client,
+    )
+    .await;
+
+    let (mut lb_pair_state, mut bin_arrays, mint_x_account, mint_y_account, clock) =
+        fetch_swap_state(
+            &mut banks_client,
+            pair.lb_pair,
+            &[pair.bin_array_1, pair.bin_array_2],
+        )
+        .await;
+
+    for (amount_in, swap_for_y) in [(amount_x_in, true), (amount_y_in, false)] {
+        let bin_array_pubkeys = commons::quote::get_bin_array_pubkeys_for_swap(
+            pair.lb_pair,
+            &lb_pair_state,
+            None,
+            swap_for_y,
+            2,
+        )
+        .unwrap();
+
+        let detailed_quote = commons::quote::simulate_swap_exact_in(
+            pair.lb_pair,
+            &mut lb_pair_state,
+            amount_in,
+            swap_for_y,
+            QuoteOptions::default(),
+            &mut bin_arrays,
+            None,
+            &clock,
+            &mint_x_account,
+            &mint_y_account,
+        )
+        .unwrap();
+
+        let user_token_out = if swap_for_y {
+            user_token_y
+        } else {
+            user_token_x
+        };
+        let user_token_out_state_before =
+            fetch_token_account_state(&mut banks_client, user_token_out).await;
+
+        process_swap2(
+            &pair,
+            &payer,
+            user_token_x,
+            user_token_y,
+            amount_in,
+            swap_for_y,
+            &bin_array_pubkeys,
+            None,
+            &mut banks_client,
+        )
+        .await;
+
+        let user_token_out_state_after =
+            fetch_token_account_state(&mut banks_client, user_token_out).await;
+
+        assert_eq!(
+            user_token_out_state_after.amount - user_token_out_state_before.amount,
+            detailed_quote.quote.amount_out,
+            "Actual swap out amount must match simulation"
+        );
+
+        let (lb_pair_state_after, bin_arrays_after, _, _, _) = fetch_swap_state(
+            &mut banks_client,
+            pair.lb_pair,
+            &[pair.bin_array_1, pair.bin_array_2],
+        )
+        .await;
+
+        assert_eq!(lb_pair_state.active_id, lb_pair_state_after.active_id);
+        assert_eq!(
+            lb_pair_state.v_parameters.volatility_accumulator,
+            lb_pair_state_after.v_parameters.volatility_accumulator
+        );
+        assert_eq!(
+            lb_pair_state.v_parameters.volatility_reference,
+            lb_pair_state_after.v_parameters.volatility_reference
+        );
+        assert_eq!(
+            lb_pair_state.v_parameters.index_reference,
+            lb_pair_state_after.v_parameters.index_reference
+        );
+        assert_eq!(
+            lb_pair_state.v_parameters.last_update_timestamp,
+            lb_pair_state_after.v_parameters.last_update_timestamp
+        );
+        assert_eq!(
+            lb_pair_state.protocol_fee.amount_x,
+            lb_pair_state_after.protocol_fee.amount_x
+        );
+        assert_eq!(
+            lb_pair_state.protocol_fee.amount_y,
+            lb_pair_state_after.protocol_fee.amount_y
+        );
+
+        for (bin_array_key, bin_array_after) in bin_arrays_after.iter() {
+            let bin_array = &bin_arrays[bin_array_key];
+            for (bin, bin_after) in bin_array.bins.iter().zip(bin_array_after.bins.iter()) {
+                assert_eq!(bin.amount_x, bin_after.amount_x);
+                assert_eq!(bin.amount_y, bin_after.amount_y);
+                assert_eq!(bin.open_order_amount, bin_after.open_order_amount);
+                assert_eq!(
+                    bin.processed_order_remaining_amount,
+                    bin_after.processed_order_remaining_amount
+                );
+            }
+        }
+    }
+}
+
+/// Host fee is a cut of the protocol fee, paid in the token the trading fee is charged in. The
+/// limit order part of the host fee is taken from the protocol share of the whole limit order fee,
+/// as emitted in the `Swap` event of the program.
+async fn run_swap_exact_in_with_host_fee(amount_in: u64, swap_for_y: bool) {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let payer = Rc::new(ctx.payer);
+    let mut banks_client = ctx.banks_client;
+
+    let user_token_x = get_or_create_ata(
+        &payer,
+        &pair.token_x_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_y = get_or_create_ata(
+        &payer,
+        &pair.token_y_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+
+    let (token_in_mint, user_token_in) = if swap_for_y {
+        (pair.token_x_mint, user_token_x)
+    } else {
+        (pair.token_y_mint, user_token_y)
+    };
+
+    mint_spl_tokens(
+        &payer,
+        &token_in_mint,
+        &user_token_in,
+        &pair.mint_authority,
+        amount_in,
+        &mut banks_client,
+    )
+    .await;
+
+    // Trading fee of the pair is always charged in token Y
+    let host = Keypair::new();
+    let host_fee_in = get_or_create_ata(
+        &payer,
+        &pair.token_y_mint,
+        &host.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+
+    let (lb_pair_state, bin_arrays, mint_x_account, mint_y_account, clock) = fetch_swap_state(
+        &mut banks_client,
+        pair.lb_pair,
+        &[pair.bin_array_1, pair.bin_array_2],
+    )
+    .await;
+
+    let detailed_quote = commons::quote::quote_exact_in_detailed(
+        pair.lb_pair,
+        &lb_pair_state,
+        amount_in,
+        swap_for_y,
+        QuoteOptions {
+            host_fee_bps: Some(HOST_FEE_BPS),
+            ..Default::default()
+        },
+        bin_arrays,
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+    let quote_result = detailed_quote.quote;
+
+    assert!(detailed_quote
+        .bin_fills
+        .iter()
+        .any(|bin_fill| bin_fill.processed_order_amount_out + bin_fill.open_order_amount_out > 0));
+    assert!(quote_result.host_fee > 0);
+    assert_eq!(
+        quote_result.host_fee + quote_result.protocol_fee_after_host_fee,
+        quote_result.protocol_fee
+    );
+
+    let (user_token_in, user_token_out) = if swap_for_y {
+        (user_token_x, user_token_y)
+    } else {
+        (user_token_y, user_token_x)
+    };
+    let swap_ix = instructions::swap2(
+        pair.lb_pair,
+        &lb_pair_state,
+        false,
+        payer.pubkey(),
+        user_token_in,
+        user_token_out,
+        Some(host_fee_in),
+        &[pair.bin_array_2, pair.bin_array_1],
+        None,
+        amount_in,
+        0,
+    )
+    .unwrap();
+
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
+        &[swap_ix],
+        Some(&payer.pubkey()),
+        &[&*payer],
+        recent_blockhash,
+    );
+    let simulation = banks_client.simulate_transaction(tx.clone()).await.unwrap();
+    assert_eq!(simulation.result, Some(Ok(())));
+    let inner_instructions = simulation
+        .simulation_details
+        .unwrap()
+        .inner_instructions
+        .unwrap();
+    let events = parse_events_from_instructions(
+        &tx.message.account_keys,
+        inner_instructions
+            .iter()
+            .flatten()
+            .map(|inner_ix| &inner_ix.instruction),
+    )
+    .unwrap();
+    let Some(DlmmEvent::Swap(swap_event)) = events.first() else {
+        panic!("Unexpected events {:?}", events);
+    };
+    assert_eq!(swap_event.fee, quote_result.fee);
+    assert_eq!(
+        swap_event.protocol_fee,
+        quote_result.protocol_fee_after_host_fee
+    );
+    assert_eq!(swap_event.host_fee, quote_result.host_fee);
+
+    banks_client.process_transaction(tx).await.unwrap();
+
+    let host_fee_in_state = fetch_token_account_state(&mut banks_client, host_fee_in).await;
+    assert_eq!(host_fee_in_state.amount, quote_result.host_fee);
+
+    let lb_pair_state_after = fetch_lb_pair(&mut banks_client, pair.lb_pair).await;
+    assert_eq!(
+        lb_pair_state_after.protocol_fee.amount_y - lb_pair_state.protocol_fee.amount_y,
+        quote_result.protocol_fee_after_host_fee
+    );
+}
+
+#[tokio::test]
+async fn test_swap_exact_in_x_to_y_with_host_fee() {
+    run_swap_exact_in_with_host_fee(40_000_000_000, true).await;
+}
+
+#[tokio::test]
+async fn test_swap_exact_in_y_to_x_with_host_fee() {
+    run_swap_exact_in_with_host_fee(45_000_000_000_000, false).await;
+}
+
+#[tokio::test]
+async fn test_swap_quote_reports_missing_bin_arrays() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+    let lb_pair_state = fetch_lb_pair(&mut ctx.banks_client, pair.lb_pair).await;
+    ctx.warp_to_slot(lb_pair_state.activation_point + 1)
+        .unwrap();
+    let mut banks_client = ctx.banks_client;
+
+    let (lb_pair_state, bin_arrays, mint_x_account, mint_y_account, clock) = fetch_swap_state(
+        &mut banks_client,
+        pair.lb_pair,
+        &[pair.bin_array_1, pair.bin_array_2],
+    )
+    .await;
+
+    // Swap X -> Y crosses from the active bin array (index 0) into index -1.
+    let amount_in = 40_000_000_000;
+    let mut partial_bin_arrays = bin_arrays.clone();
+    partial_bin_arrays.remove(&pair.bin_array_1);
+
+    let err = commons::quote::quote_exact_in(
+        pair.lb_pair,
+        &lb_pair_state,
+        amount_in,
+        true,
+        QuoteOptions::default(),
+        partial_bin_arrays.clone(),
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap_err();
+
+    let missing = err
+        .downcast_ref::<commons::quote::MissingBinArraysError>()
+        .unwrap();
+    assert_eq!(missing.bin_array_pubkeys, vec![pair.bin_array_1]);
+
+    // Fetch the reported bin arrays and retry
+    let (_, fetched_bin_arrays, _, _, _) =
+        fetch_swap_state(&mut banks_client, pair.lb_pair, &missing.bin_array_pubkeys).await;
+    partial_bin_arrays.extend(fetched_bin_arrays);
+
+    let quote_result = commons::quote::quote_exact_in(
+        pair.lb_pair,
+        &lb_pair_state,
+        amount_in,
+        true,
+        QuoteOptions::default(),
+        partial_bin_arrays,
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+
+    let expected_quote_result = commons::quote::quote_exact_in(
+        pair.lb_pair,
+        &lb_pair_state,
+        amount_in,
+        true,
+        QuoteOptions::default(),
+        bin_arrays,
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+
+    assert_eq!(quote_result.amount_out, expected_quote_result.amount_out);
+    assert_eq!(quote_result.fee, expected_quote_result.fee);
+}
+
+#[tokio::test]
+async fn test_swap_quote_pre_activation_swap_address() {
+    let (test, pair) = setup_swap_quote_test_pair();
+    let mut ctx = test.start_with_context().await;
+
+    // Whitelist the payer to swap during the pre-activation window
+    let pre_activation_duration = 1000;
+    let mut lb_pair_account = fetch_account(&mut ctx.banks_client, pair.lb_pair).await;
+    let mut lb_pair_state: LbPair = bytemuck::pod_read_unaligned(&lb_pair_account.data[8..]);
+    lb_pair_state.pre_activation_swap_address = ctx.payer.pubkey();
+    lb_pair_state.pre_activation_duration = pre_activation_duration;
+    lb_pair_account.data[8..].copy_from_slice(bytemuck::bytes_of(&lb_pair_state));
+    ctx.set_account(&pair.lb_pair, &lb_pair_account.into());
+
+    ctx.warp_to_slot(lb_pair_state.activation_point - pre_activation_duration / 2)
+        .unwrap();
+    let payer = Rc::new(ctx.payer);
+    let mut banks_client = ctx.banks_client;
+
+    let amount_in = 40_000_000_000;
+    let swap_for_y = true;
+
+    let user_token_x = get_or_create_ata(
+        &payer,
+        &pair.token_x_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+    let user_token_y = get_or_create_ata(
+        &payer,
+        &pair.token_y_mint,
+        &payer.pubkey(),
+        &mut banks_client,
+    )
+    .await;
+
+    mint_spl_tokens(
+        &payer,
+        &pair.token_x_mint,
+        &user_token_x,
+        &pair.mint_authority,
+        amount_in,
+        &mut banks_client,
+    )
+    .await;
+
+    let (lb_pair_state, bin_arrays, mint_x_account, mint_y_account, clock) = fetch_swap_state(
+        &mut banks_client,
+        pair.lb_pair,
+        &[pair.bin_array_1, pair.bin_array_2],
+    )
+    .await;
+
+    for user in [None, Some(Keypair::new().pubkey())] {
+        let err = commons::quote::quote_exact_in(
+            pair.lb_pair,
+            &lb_pair_state,
+            amount_in,
+            swap_for_y,
+            QuoteOptions {
+                user,
+                ..Default::default()
+            },
+            bin_arrays.clone(),
+            None,
+            &clock,
+            &mint_x_account,
+            &mint_y_account,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Pair is disabled");
+    }
+
+    let quote_result = commons::quote::quote_exact_in(
+        pair.lb_pair,
+        &lb_pair_state,
+        amount_in,
+        swap_for_y,
+        QuoteOptions {
+            user: Some(payer.pubkey()),
+            ..Default::default()
+        },
+        bin_arrays,
+        None,
+        &clock,
+        &mint_x_account,
+        &mint_y_account,
+    )
+    .unwrap();
+
+    let user_token_y_state_before =
+        fetch_token_account_state(&mut banks_client, user_token_y).await;
+
+    process_swap2(
+        &pair,
+        &payer,
+        user_token_x,
+        user_token_y,
+        amount_in,
+        swap_for_y,
+        &[pair.bin_array_2, pair.bin_array_1],
+        None,
+        &mut banks_client,
+    )
+    .await;
+
+    let user_token_y_state_after = fetch_token_account_state(&mut banks_client, user_token_y).await;
+    assert_eq!(
+        user_token_y_state_after.amount - user_token_y_state_before.amount,
+        quote_result.amount_out
+    );
+}
@@ -138,6 +138,7 @@ async fn test_swap_exact_out() {
             &lb_pair_state,
             out_amount,
             swap_for_y,
+            QuoteOptions::default(),
             bin_arrays,
             None,
             &clock,
@@ -261,6 +262,7 @@ async fn test_swap() {
             &lb_pair_state,
             amount_in,
             swap_for_y,
+            QuoteOptions::default(),
             bin_arrays,
             None,
             &clock,
@@ -5,8 +5,6 @@ use anchor_spl::token_interface::Mint;
 use anchor_spl::token_interface::TokenAccount;
 use compute_budget::ComputeBudgetInstruction;
 use dlmm::events::Swap as SwapEvent;
-use instruction::AccountMeta;
-use instruction::Instruction;
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -195,33 +193,21 @@ impl Core {
 
         let payer = self.wallet.clone().context("Requires keypair")?;
 
-        let (event_authority, _bump) = derive_event_authority_pda();
-
         let lb_pair = state.lb_pair;
         let lb_pair_state = state.lb_pair_state.context("Missing lb pair state")?;
 
         let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
 
-        let mut remaining_account_info = RemainingAccountsInfo { slices: vec![] };
-        let mut transfer_hook_remaining_accounts = vec![];
-
-        if let Some((slices, remaining_accounts)) =
-            get_potential_token_2022_related_ix_data_and_accounts(
-                &lb_pair_state,
-                RpcClient::new(self.provider.url().to_owned()),
-                ActionType::Liquidity,
-            )
-            .await?
-        {
-            remaining_account_info.slices = slices;
-            transfer_hook_remaining_accounts = remaining_accounts;
-        }
+        let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+            &lb_pair_state,
+            &RpcClient::new(self.provider.url().to_owned()),
+            ActionType::Liquidity,
+        )
+        .await?;
 
         for (i, &position) in state.position_pks.iter().enumerate() {
             let position_state = &state.positions[i];
 
-            let bin_arrays_account_meta = position_state.get_bin_array_accounts_meta_coverage()?;
-
             let user_token_x = get_associated_token_address_with_program_id(
                 &payer.pubkey(),
                 &lb_pair_state.token_x_mint,
@@ -237,106 +223,41 @@ impl Core {
             let mut instructions =
                 vec![ComputeBudgetInstruction::set_compute_unit_limit(1_400_000)];
 
-            let main_accounts = dlmm::client::accounts::RemoveLiquidityByRange2 {
+            let remove_all_ix = commons::instructions::remove_liquidity_by_range2(
                 position,
                 lb_pair,
-                bin_array_bitmap_extension: Some(dlmm_ID),
+                &lb_pair_state,
+                payer.pubkey(),
                 user_token_x,
                 user_token_y,
-                reserve_x: lb_pair_state.reserve_x,
-                reserve_y: lb_pair_state.reserve_y,
-                token_x_mint: lb_pair_state.token_x_mint,
-                token_y_mint: lb_pair_state.token_y_mint,
-                sender: payer.pubkey(),
-                token_x_program,
-                token_y_program,
-                memo_program: spl_memo::ID,
-                event_authority,
-                program: dlmm_ID,
-            }
-            .to_account_metas(None);
-
-            let remaining_accounts = [
-                transfer_hook_remaining_accounts.clone(),
-                bin_arrays_account_meta.clone(),
-            ]
-            .concat();
-
-            let data = dlmm::client::args::RemoveLiquidityByRange2 {
-                from_bin_id: position_state.lower_bin_id,
-                to_bin_id: position_state.upper_bin_id,
-                bps_to_remove: BASIS_POINT_MAX as u16,
-                remaining_accounts_info: remaining_account_info.clone(),
-            }
-            .data();
-
-            let accounts = [main_accounts.to_vec(), remaining_accounts].concat();
-
-            let remove_all_ix = Instruction {
-                program_id: dlmm_ID,
-                accounts,
-                data,
-            };
+                transfer_hook_accounts.clone(),
+                position_state.lower_bin_id,
+                position_state.upper_bin_id,
+                BASIS_POINT_MAX as u16,
+            )?;
 
             instructions.push(remove_all_ix);
 
-            let main_accounts = dlmm::client::accounts::ClaimFee2 {
-                lb_pair,
+            let claim_fee_ix = commons::instructions::claim_fee2(
                 position,
-                sender: payer.pubkey(),
-                event_authority,
-                program: dlmm_ID,
-                reserve_x: lb_pair_state.reserve_x,
-                reserve_y: lb_pair_state.reserve_y,
-                token_x_mint: lb_pair_state.token_x_mint,
-                token_y_mint: lb_pair_state.token_y_mint,
-                token_program_x: token_x_program,
-                token_program_y: token_y_program,
-                memo_program: spl_memo::ID,
+                lb_pair,
+                &lb_pair_state,
+                payer.pubkey(),
                 user_token_x,
                 user_token_y,
-            }
-            .to_account_metas(None);
-
-            let remaining_accounts = [
-                transfer_hook_remaining_accounts.clone(),
-                bin_arrays_account_meta.clone(),
-            ]
-            .concat();
-
-            let data = dlmm::client::args::ClaimFee2 {
-                min_bin_id: position_state.lower_bin_id,
-                max_bin_id: position_state.upper_bin_id,
-                remaining_accounts_info: remaining_account_info.clone(),
-            }
-            .data();
-
-            let accounts = [main_accounts.to_vec(), remaining_accounts].concat();
-
-            let claim_fee_ix = Instruction {
-                program_id: dlmm_ID,
-                accounts,
-                data,
-            };
+                transfer_hook_accounts.clone(),
+                position_state.lower_bin_id,
+                position_state.upper_bin_id,
+            )?;
 
             instructions.push(claim_fee_ix);
 
-            let accounts = dlmm::client::accounts::ClosePosition2 {
+            let close_position_ix = commons::instructions::close_position2(
                 position,
-                sender: payer.pubkey(),
-                rent_receiver: payer.pubkey(),
-                event_authority,
-                program: dlmm_ID,
-            }
-            .to_account_metas(None);
-
-            let data = dlmm::client::args::ClosePosition2 {}.data();
-
-            let close_position_ix = Instruction {
-                program_id: dlmm_ID,
-                accounts: accounts.to_vec(),
-                data,
-            };
+                position_state,
+                payer.pubkey(),
+                payer.pubkey(),
+            )?;
 
             instructions.push(close_position_ix);
 
@@ -369,33 +290,21 @@ impl Core {
 
         let payer = self.wallet.clone().context("Requires keypair")?;
 
-        let (event_authority, _bump) = derive_event_authority_pda();
         let (bin_array_bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
 
-        let account = rpc_client.get_account(&bin_array_bitmap_extension).await;
-
-        let (bin_array_bitmap_extension, bin_array_bitmap_extension_state) =
-            if let std::result::Result::Ok(account) = account {
-                let bin_array_bitmap_extension_state =
-                    bytemuck::pod_read_unaligned(&account.data[8..]);
-                (
-                    bin_array_bitmap_extension,
-                    Some(bin_array_bitmap_extension_state),
-                )
-            } else {
-                (dlmm_ID, None)
-            };
+        let bin_array_bitmap_extension_state: Option<BinArrayBitmapExtension> = rpc_client
+            .get_account(&bin_array_bitmap_extension)
+            .await
+            .ok()
+            .map(|account| bytemuck::pod_read_unaligned(&account.data[8..]));
 
-        let bin_arrays_account_meta = get_bin_array_pubkeys_for_swap(
+        let bin_arrays = get_bin_array_pubkeys_for_swap(
             lb_pair,
             &lb_pair_state,
             bin_array_bitmap_extension_state.as_ref(),
             swap_for_y,
             3,
-        )?
-        .into_iter()
-        .map(|key| AccountMeta::new(key, false))
-        .collect::<Vec<_>>();
+        )?;
 
         let (user_token_in, user_token_out) = if swap_for_y {
             (
@@ -425,57 +334,26 @@ impl Core {
             )
         };
 
-        let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-        let mut remaining_accounts = vec![];
-
-        if let Some((slices, transfer_hook_remaining_accounts)) =
-            get_potential_token_2022_related_ix_data_and_accounts(
-                &lb_pair_state,
-                RpcClient::new(self.provider.url().to_owned()),
-                ActionType::Liquidity,
-            )
-            .await?
-        {
-            remaining_accounts_info.slices = slices;
-            remaining_accounts.extend(transfer_hook_remaining_accounts);
-        }
-
-        remaining_accounts.extend(bin_arrays_account_meta);
+        let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+            &lb_pair_state,
+            &RpcClient::new(self.provider.url().to_owned()),
+            ActionType::Liquidity,
+        )
+        .await?;
 
-        let main_accounts = dlmm::client::accounts::Swap2 {
+        let swap_ix = commons::instructions::swap2(
             lb_pair,
-            bin_array_bitmap_extension: Some(bin_array_bitmap_extension),
-            reserve_x: lb_pair_state.reserve_x,
-            reserve_y: lb_pair_state.reserve_y,
-            token_x_mint: lb_pair_state.token_x_mint,
-            token_y_mint: lb_pair_state.token_y_mint,
-            token_x_program,
-            token_y_program,
-            user: payer.pubkey(),
+            &lb_pair_state,
+            bin_array_bitmap_extension_state.is_some(),
+            payer.pubkey(),
             user_token_in,
             user_token_out,
-            oracle: lb_pair_state.oracle,
-            host_fee_in: Some(dlmm_ID),
-            event_authority,
-            program: dlmm_ID,
-            memo_program: spl_memo::ID,
-        }
-        .to_account_metas(None);
-
-        let data = dlmm::client::args::Swap2 {
+            None,
+            &bin_arrays,
+            transfer_hook_accounts,
             amount_in,
-            min_amount_out: state.get_min_out_amount_with_slippage_rate(amount_in, swap_for_y)?,
-            remaining_accounts_info,
-        }
-        .data();
-
-        let accounts = [main_accounts.to_vec(), remaining_accounts].concat();
-
-        let swap_ix = Instruction {
-            program_id: dlmm_ID,
-            accounts,
-            data,
-        };
+            state.get_min_out_amount_with_slippage_rate(amount_in, swap_for_y)?,
+        )?;
 
         let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
 
@@ -523,8 +401,6 @@ impl Core {
 
         let lb_pair = state.lb_pair;
 
-        let (event_authority, _bump) = derive_event_authority_pda();
-
         let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(1_400_000)];
 
         for idx in lower_bin_array_idx..=upper_bin_array_idx {
@@ -532,66 +408,27 @@ impl Core {
             let (bin_array, _bump) = derive_bin_array_pda(lb_pair, idx.into());
 
             if rpc_client.get_account_data(&bin_array).await.is_err() {
-                let accounts = dlmm::client::accounts::InitializeBinArray {
-                    bin_array,
-                    funder: payer.pubkey(),
+                instructions.push(commons::instructions::initialize_bin_array(
                     lb_pair,
-                    system_program: system_program::ID,
-                }
-                .to_account_metas(None);
-
-                let data = dlmm::client::args::InitializeBinArray { index: idx.into() }.data();
-
-                let instruction = Instruction {
-                    program_id: dlmm_ID,
-                    accounts: accounts.to_vec(),
-                    data,
-                };
-
-                instructions.push(instruction)
+                    payer.pubkey(),
+                    idx.into(),
+                )?);
             }
         }
 
         let position_kp = Keypair::new();
         let position = position_kp.pubkey();
 
-        let accounts = dlmm::client::accounts::InitializePosition {
+        instructions.push(commons::instructions::initialize_position(
             lb_pair,
-            payer: payer.pubkey(),
+            payer.pubkey(),
             position,
-            owner: payer.pubkey(),
-            rent: sysvar::rent::ID,
-            system_program: system_program::ID,
-            event_authority,
-            program: dlmm_ID,
-        }
-        .to_account_metas(None);
-
-        let data = dlmm::client::args::InitializePosition {
+            payer.pubkey(),
             lower_bin_id,
-            width: DEFAULT_BIN_PER_POSITION as i32,
-        }
-        .data();
-
-        let instruction = Instruction {
-            program_id: dlmm_ID,
-            accounts: accounts.to_vec(),
-            data,
-        };
-
-        instructions.push(instruction);
+            DEFAULT_BIN_PER_POSITION as i32,
+        )?);
 
         // TODO implement add liquidity by strategy imbalance
-        let (bin_array_bitmap_extension, _bump) = derive_bin_array_bitmap_extension(lb_pair);
-        let bin_array_bitmap_extension = rpc_client
-            .get_account(&bin_array_bitmap_extension)
-            .await
-            .map(|_| bin_array_bitmap_extension)
-            .unwrap_or(dlmm_ID);
-
-        let (bin_array_lower, _bump) = derive_bin_array_pda(lb_pair, lower_bin_array_idx.into());
-        let (bin_array_upper, _bump) = derive_bin_array_pda(lb_pair, upper_bin_array_idx.into());
-
         let lb_pair_state = state.lb_pair_state.context("Missing lb pair state")?;
         let [token_x_program, token_y_program] = lb_pair_state.get_token_programs()?;
 
@@ -607,47 +444,22 @@ impl Core {
             &token_y_program,
         );
 
-        let mut remaining_accounts_info = RemainingAccountsInfo { slices: vec![] };
-        let mut remaining_accounts = vec![];
-
-        if let Some((slices, transfer_hook_remaining_accounts)) =
-            get_potential_token_2022_related_ix_data_and_accounts(
-                &lb_pair_state,
-                RpcClient::new(self.provider.url().to_owned()),
-                ActionType::Liquidity,
-            )
-            .await?
-        {
-            remaining_accounts_info.slices = slices;
-            remaining_accounts.extend(transfer_hook_remaining_accounts);
-        }
-
-        remaining_accounts.extend(
-            [bin_array_lower, bin_array_upper]
-                .into_iter()
-                .map(|k| AccountMeta::new(k, false)),
-        );
+        let transfer_hook_accounts = get_potential_token_2022_related_ix_data_and_accounts(
+            &lb_pair_state,
+            &RpcClient::new(self.provider.url().to_owned()),
+            ActionType::Liquidity,
+        )
+        .await?;
 
-        let main_accounts = dlmm::client::accounts::AddLiquidityByStrategy2 {
-            lb_pair,
+        let instruction = commons::instructions::add_liquidity_by_strategy2(
             position,
-            bin_array_bitmap_extension: Some(bin_array_bitmap_extension),
-            sender: payer.pubkey(),
-            event_authority,
-            program: dlmm_ID,
-            reserve_x: lb_pair_state.reserve_x,
-            reserve_y: lb_pair_state.reserve_y,
-            token_x_mint: lb_pair_state.token_x_mint,
-            token_y_mint: lb_pair_state.token_y_mint,
+            lb_pair,
+            &lb_pair_state,
+            payer.pubkey(),
             user_token_x,
             user_token_y,
-            token_x_program,
-            token_y_program,
-        }
-        .to_account_metas(None);
-
-        let data = dlmm::client::args::AddLiquidityByStrategy2 {
-            liquidity_parameter: LiquidityParameterByStrategy {
+            transfer_hook_accounts,
+            LiquidityParameterByStrategy {
                 amount_x,
                 amount_y,
                 active_id: lb_pair_state.active_id,
@@ -659,17 +471,7 @@ impl Core {
                     parameteres: [0u8; 64],
                 },
             },
-            remaining_accounts_info,
-        }
-        .data();
-
-        let accounts = [main_accounts.to_vec(), remaining_accounts].concat();
-
-        let instruction = Instruction {
-            program_id: dlmm_ID,
-            accounts,
-            data,
-        };
+        )?;
 
         instructions.push(instruction);
 
@@ -41,9 +41,6 @@ use std::sync::Mutex;
 use std::time::Duration;
 
 use anchor_lang::AccountDeserialize;
-use anchor_lang::AnchorDeserialize;
-use anchor_lang::InstructionData;
-use anchor_lang::ToAccountMetas;
 
 #[macro_use]
 extern crate log;
@@ -4,8 +4,7 @@ use commitment_config::CommitmentConfig;
 use dlmm::events::Swap as SwapEvent;
 use solana_client::rpc_response::{Response, RpcSimulateTransactionResult};
 use solana_sdk::instruction::Instruction;
-use solana_transaction_status::option_serializer::OptionSerializer;
-use solana_transaction_status::{UiInstruction, UiTransactionEncoding};
+use solana_transaction_status::UiTransactionEncoding;
 use spl_associated_token_account::instruction::create_associated_token_account;
 use std::time::*;
 use transaction::Transaction;
@@ -72,7 +71,10 @@ pub async fn send_tx(
     );
     tx.sign(&[payer], latest_blockhash);
 
-    let signature = rpc_client.send_and_confirm_transaction(&tx).await?;
+    let signature = rpc_client
+        .send_and_confirm_transaction(&tx)
+        .await
+        .map_err(|err| add_dlmm_error_context(err.into()))?;
 
     Ok(signature)
 }
@@ -88,6 +90,14 @@ pub async fn simulate_transaction(
     let tx =
         Transaction::new_signed_with_payer(&instructions, Some(&payer), keypairs, latest_blockhash);
     let simulation = rpc_client.simulate_transaction(&tx).await?;
+    if let Some(dlmm_error) = simulation
+        .value
+        .logs
+        .as_deref()
+        .and_then(get_dlmm_error_from_logs)
+    {
+        warn!("Simulation failed with {}", dlmm_error);
+    }
 
     Ok(simulation)
 }
@@ -104,24 +114,9 @@ pub async fn parse_swap_event(rpc_client: &RpcClient, signature: Signature) -> R
         )
         .await?;
 
-    if let Some(meta) = &tx.transaction.meta {
-        if let OptionSerializer::Some(inner_instructions) = meta.inner_instructions.as_ref() {
-            let inner_ixs = inner_instructions
-                .iter()
-                .flat_map(|ix| ix.instructions.as_slice());
-
-            for ix in inner_ixs {
-                match ix {
-                    UiInstruction::Compiled(compiled_ix) => {
-                        if let std::result::Result::Ok(ix_data) =
-                            bs58::decode(compiled_ix.data.as_str()).into_vec()
-                        {
-                            return Ok(SwapEvent::deserialize(&mut ix_data.as_ref())?);
-                        };
-                    }
-                    _ => {}
-                }
-            }
+    for event in parse_events_from_transaction(&tx)? {
+        if let DlmmEvent::Swap(swap_event) = event {
+            return Ok(swap_event);
         }
     }
     Err(Error::msg("Cannot find swap event"))
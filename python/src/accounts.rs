@@ -0,0 +1,56 @@
+use commons::*;
+use pyo3::{exceptions::PyValueError, prelude::*};
+use serde::Serialize;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::collections::HashMap;
+
+/// Raw accounts keyed by their address, as `(owner, data)`.
+pub type RawAccounts = HashMap<String, (String, Vec<u8>)>;
+
+pub(crate) fn py_error(error: anyhow::Error) -> PyErr {
+    PyValueError::new_err(format!("{error:#}"))
+}
+
+pub(crate) fn parse_pubkey(pubkey: &str) -> PyResult<Pubkey> {
+    pubkey
+        .parse()
+        .map_err(|_| PyValueError::new_err(format!("Invalid pubkey {pubkey}")))
+}
+
+pub(crate) fn to_py<'py, T: Serialize>(py: Python<'py>, value: &T) -> PyResult<Bound<'py, PyAny>> {
+    Ok(pythonize::pythonize(py, value)?)
+}
+
+pub(crate) fn decode_accounts(accounts: RawAccounts) -> PyResult<HashMap<Pubkey, Account>> {
+    accounts
+        .into_iter()
+        .map(|(key, (owner, data))| {
+            Ok((
+                parse_pubkey(&key)?,
+                Account {
+                    data,
+                    owner: parse_pubkey(&owner)?,
+                    ..Default::default()
+                },
+            ))
+        })
+        .collect()
+}
+
+pub(crate) fn get_account<'a>(
+    accounts: &'a HashMap<Pubkey, Account>,
+    key: &Pubkey,
+    name: &str,
+) -> PyResult<&'a Account> {
+    accounts
+        .get(key)
+        .ok_or_else(|| PyValueError::new_err(format!("Missing {name} account {key}")))
+}
+
+pub(crate) fn get_dlmm_account<T: bytemuck::AnyBitPattern>(
+    accounts: &HashMap<Pubkey, Account>,
+    key: &Pubkey,
+    name: &str,
+) -> PyResult<T> {
+    pod_read_unaligned_skip_disc(&get_account(accounts, key, name)?.data).map_err(py_error)
+}
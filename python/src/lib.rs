@@ -0,0 +1,50 @@
+//! Python bindings of the `commons` quote core: swap quotes, PDA derivation, and position and
+//! limit order parsing. Accounts are passed as raw bytes, keyed by their address, and the results
+//! are the `serde` encoding of the `commons` types as dicts, with pubkeys and 128 bits integers as
+//! strings.
+
+use pyo3::prelude::*;
+
+pub mod accounts;
+pub use accounts::*;
+
+pub mod pda;
+pub use pda::*;
+
+pub mod quote;
+pub use quote::*;
+
+pub mod position;
+pub use position::*;
+
+#[pymodule]
+fn dlmm_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(quote_exact_in, m)?)?;
+    m.add_function(wrap_pyfunction!(quote_exact_out, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_position, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_limit_order, m)?)?;
+
+    m.add_function(wrap_pyfunction!(
+        derive_lb_pair_with_preset_parameter_key,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(derive_lb_pair_pda2, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        derive_customizable_permissionless_lb_pair,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(derive_permission_lb_pair_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_position_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_oracle_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_bin_array_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_bin_array_bitmap_extension, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_reserve_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_reward_vault_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_event_authority_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_preset_parameter_pda2, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_preset_parameter_pda_v2, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_token_badge_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(derive_operator_pda, m)?)?;
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use crate::*;
+use solana_sdk::pubkey::Pubkey;
+
+/// Program derived address as `(address, bump)`.
+trait IntoPda {
+    fn into_pda(self) -> (String, u8);
+}
+
+impl IntoPda for (Pubkey, u8) {
+    fn into_pda(self) -> (String, u8) {
+        (self.0.to_string(), self.1)
+    }
+}
+
+#[pyfunction]
+pub fn derive_lb_pair_with_preset_parameter_key(
+    preset_parameter: &str,
+    token_x_mint: &str,
+    token_y_mint: &str,
+) -> PyResult<(String, u8)> {
+    Ok(commons::derive_lb_pair_with_preset_parameter_key(
+        parse_pubkey(preset_parameter)?,
+        parse_pubkey(token_x_mint)?,
+        parse_pubkey(token_y_mint)?,
+    )
+    .into_pda())
+}
+
+#[pyfunction]
+pub fn derive_lb_pair_pda2(
+    token_x_mint: &str,
+    token_y_mint: &str,
+    bin_step: u16,
+    base_factor: u16,
+) -> PyResult<(String, u8)> {
+    Ok(commons::derive_lb_pair_pda2(
+        parse_pubkey(token_x_mint)?,
+        parse_pubkey(token_y_mint)?,
+        bin_step,
+        base_factor,
+    )
+    .into_pda())
+}
+
+#[pyfunction]
+pub fn derive_customizable_permissionless_lb_pair(
+    token_x_mint: &str,
+    token_y_mint: &str,
+) -> PyResult<(String, u8)> {
+    Ok(commons::derive_customizable_permissionless_lb_pair(
+        parse_pubkey(token_x_mint)?,
+        parse_pubkey(token_y_mint)?,
+    )
+    .into_pda())
+}
+
+#[pyfunction]
+pub fn derive_permission_lb_pair_pda(
+    base: &str,
+    token_x_mint: &str,
+    token_y_mint: &str,
+    bin_step: u16,
+) -> PyResult<(String, u8)> {
+    Ok(commons::derive_permission_lb_pair_pda(
+        parse_pubkey(base)?,
+        parse_pubkey(token_x_mint)?,
+        parse_pubkey(token_y_mint)?,
+        bin_step,
+    )
+    .into_pda())
+}
+
+#[pyfunction]
+pub fn derive_position_pda(
+    lb_pair: &str,
+    base: &str,
+    lower_bin_id: i32,
+    width: i32,
+) -> PyResult<(String, u8)> {
+    Ok(commons::derive_position_pda(
+        parse_pubkey(lb_pair)?,
+        parse_pubkey(base)?,
+        lower_bin_id,
+        width,
+    )
+    .into_pda())
+}
+
+#[pyfunction]
+pub fn derive_oracle_pda(lb_pair: &str) -> PyResult<(String, u8)> {
+    Ok(commons::derive_oracle_pda(parse_pubkey(lb_pair)?).into_pda())
+}
+
+#[pyfunction]
+pub fn derive_bin_array_pda(lb_pair: &str, bin_array_index: i64) -> PyResult<(String, u8)> {
+    Ok(commons::derive_bin_array_pda(parse_pubkey(lb_pair)?, bin_array_index).into_pda())
+}
+
+#[pyfunction]
+pub fn derive_bin_array_bitmap_extension(lb_pair: &str) -> PyResult<(String, u8)> {
+    Ok(commons::derive_bin_array_bitmap_extension(parse_pubkey(lb_pair)?).into_pda())
+}
+
+#[pyfunction]
+pub fn derive_reserve_pda(token_mint: &str, lb_pair: &str) -> PyResult<(String, u8)> {
+    Ok(commons::derive_reserve_pda(parse_pubkey(token_mint)?, parse_pubkey(lb_pair)?).into_pda())
+}
+
+#[pyfunction]
+pub fn derive_reward_vault_pda(lb_pair: &str, reward_index: u64) -> PyResult<(String, u8)> {
+    Ok(commons::derive_reward_vault_pda(parse_pubkey(lb_pair)?, reward_index).into_pda())
+}
+
+#[pyfunction]
+pub fn derive_event_authority_pda() -> (String, u8) {
+    commons::derive_event_authority_pda().into_pda()
+}
+
+#[pyfunction]
+pub fn derive_preset_parameter_pda2(bin_step: u16, base_factor: u16) -> (String, u8) {
+    commons::derive_preset_parameter_pda2(bin_step, base_factor).into_pda()
+}
+
+#[pyfunction]
+pub fn derive_preset_parameter_pda_v2(index: u16) -> (String, u8) {
+    commons::derive_preset_parameter_pda_v2(index).into_pda()
+}
+
+#[pyfunction]
+pub fn derive_token_badge_pda(mint: &str) -> PyResult<(String, u8)> {
+    Ok(commons::derive_token_badge_pda(parse_pubkey(mint)?).into_pda())
+}
+
+#[pyfunction]
+pub fn derive_operator_pda(whitelisted_signer: &str) -> PyResult<(String, u8)> {
+    Ok(commons::derive_operator_pda(parse_pubkey(whitelisted_signer)?).into_pda())
+}
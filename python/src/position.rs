@@ -0,0 +1,55 @@
+use crate::*;
+use commons::dlmm::accounts::*;
+use commons::{get_bin_array_map_from_account_map, DynamicPosition, ParsedLimitOrder};
+
+/// Parse the position `position` with its amounts, claimable fees and rewards at
+/// `current_timestamp`. `accounts` holds the position, its pair and the bin arrays covering the
+/// position.
+#[pyfunction]
+pub fn parse_position<'py>(
+    py: Python<'py>,
+    position: &str,
+    accounts: RawAccounts,
+    current_timestamp: i64,
+) -> PyResult<Bound<'py, PyAny>> {
+    let accounts = decode_accounts(accounts)?;
+    let position_pubkey = parse_pubkey(position)?;
+    let position_account = get_account(&accounts, &position_pubkey, "position")?;
+    let position: PositionV2 = get_dlmm_account(&accounts, &position_pubkey, "position")?;
+    let lb_pair: LbPair = get_dlmm_account(&accounts, &position.lb_pair, "lb pair")?;
+
+    let dynamic_position = DynamicPosition::parse(
+        &position,
+        &position_account.data,
+        &lb_pair,
+        &get_bin_array_map_from_account_map(&accounts, position.lb_pair),
+        current_timestamp,
+    )
+    .map_err(py_error)?;
+
+    to_py(py, &dynamic_position)
+}
+
+/// Parse the limit order `limit_order` with the filled and open amounts of its bins. `accounts`
+/// holds the limit order, its pair and the bin arrays of its bins.
+#[pyfunction]
+pub fn parse_limit_order<'py>(
+    py: Python<'py>,
+    limit_order: &str,
+    accounts: RawAccounts,
+) -> PyResult<Bound<'py, PyAny>> {
+    let accounts = decode_accounts(accounts)?;
+    let limit_order_pubkey = parse_pubkey(limit_order)?;
+    let limit_order_account = get_account(&accounts, &limit_order_pubkey, "limit order")?;
+    let limit_order: LimitOrder = get_dlmm_account(&accounts, &limit_order_pubkey, "limit order")?;
+    let lb_pair: LbPair = get_dlmm_account(&accounts, &limit_order.lb_pair, "lb pair")?;
+
+    let parsed_limit_order = ParsedLimitOrder::parse(
+        &limit_order_account.data,
+        &get_bin_array_map_from_account_map(&accounts, limit_order.lb_pair),
+        lb_pair.parameters.collect_fee_mode,
+    )
+    .map_err(py_error)?;
+
+    to_py(py, &parsed_limit_order)
+}
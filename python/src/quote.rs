@@ -0,0 +1,201 @@
+use crate::*;
+use commons::{PoolSnapshot, QuoteOptions};
+
+fn parse_quote_options(host_fee_bps: Option<u16>, user: Option<&str>) -> PyResult<QuoteOptions> {
+    Ok(QuoteOptions {
+        host_fee_bps,
+        user: user.map(parse_pubkey).transpose()?,
+    })
+}
+
+/// Quote an exact in swap of the pair `lb_pair`. `accounts` holds the pair, the clock sysvar,
+/// both mints, the bin arrays the swap may traverse and the bitmap extension when the pair has
+/// one. See `commons::quote_exact_in`.
+#[pyfunction]
+#[pyo3(signature = (lb_pair, accounts, amount_in, swap_for_y, host_fee_bps = None, user = None))]
+pub fn quote_exact_in<'py>(
+    py: Python<'py>,
+    lb_pair: &str,
+    accounts: RawAccounts,
+    amount_in: u64,
+    swap_for_y: bool,
+    host_fee_bps: Option<u16>,
+    user: Option<&str>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let accounts = decode_accounts(accounts)?;
+    let snapshot =
+        PoolSnapshot::from_account_map(parse_pubkey(lb_pair)?, &accounts).map_err(py_error)?;
+    let quote = snapshot
+        .quote_exact_in(
+            amount_in,
+            swap_for_y,
+            parse_quote_options(host_fee_bps, user)?,
+        )
+        .map_err(py_error)?;
+
+    to_py(py, &quote)
+}
+
+/// Quote an exact out swap of the pair `lb_pair`, see [`quote_exact_in`] for `accounts`.
+#[pyfunction]
+#[pyo3(signature = (lb_pair, accounts, amount_out, swap_for_y, host_fee_bps = None, user = None))]
+pub fn quote_exact_out<'py>(
+    py: Python<'py>,
+    lb_pair: &str,
+    accounts: RawAccounts,
+    amount_out: u64,
+    swap_for_y: bool,
+    host_fee_bps: Option<u16>,
+    user: Option<&str>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let accounts = decode_accounts(accounts)?;
+    let snapshot =
+        PoolSnapshot::from_account_map(parse_pubkey(lb_pair)?, &accounts).map_err(py_error)?;
+    let quote = snapshot
+        .quote_exact_out(
+            amount_out,
+            swap_for_y,
+            parse_quote_options(host_fee_bps, user)?,
+        )
+        .map_err(py_error)?;
+
+    to_py(py, &quote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commons::dlmm::accounts::*;
+    use commons::pod_read_unaligned_skip_disc;
+    use commons::test_utils::*;
+    use solana_sdk::pubkey::Pubkey;
+    use std::collections::HashMap;
+
+    fn raw_accounts(accounts: &PoolFixtureAccounts) -> RawAccounts {
+        accounts
+            .to_account_map()
+            .into_iter()
+            .map(|(key, account)| (key.to_string(), (account.owner.to_string(), account.data)))
+            .collect()
+    }
+
+    fn decode_bin_arrays(accounts: &PoolFixtureAccounts) -> HashMap<Pubkey, BinArray> {
+        accounts
+            .bin_arrays
+            .iter()
+            .map(|(key, account)| (*key, pod_read_unaligned_skip_disc(&account.data).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_quotes_match_commons() {
+        pyo3::prepare_freethreaded_python();
+
+        let fixture = PoolFixture::sample();
+        let accounts = fixture.build().unwrap();
+        let lb_pair_state: LbPair = pod_read_unaligned_skip_disc(&accounts.lb_pair.1.data).unwrap();
+        let bitmap_extension: Option<BinArrayBitmapExtension> = accounts
+            .bitmap_extension
+            .as_ref()
+            .map(|(_, account)| pod_read_unaligned_skip_disc(&account.data).unwrap());
+        let lb_pair = fixture.lb_pair();
+
+        Python::with_gil(|py| {
+            for (amount, swap_for_y, host_fee_bps) in [
+                (1_000, true, None),
+                (5_000_000, true, Some(1_500)),
+                (1_000_000_000, false, None),
+                (4_000_000_000, false, Some(2_000)),
+            ] {
+                let quote = quote_exact_in(
+                    py,
+                    &lb_pair.to_string(),
+                    raw_accounts(&accounts),
+                    amount,
+                    swap_for_y,
+                    host_fee_bps,
+                    None,
+                )
+                .unwrap();
+                let expected = commons::quote_exact_in(
+                    lb_pair,
+                    &lb_pair_state,
+                    amount,
+                    swap_for_y,
+                    QuoteOptions {
+                        host_fee_bps,
+                        ..Default::default()
+                    },
+                    decode_bin_arrays(&accounts),
+                    bitmap_extension.as_ref(),
+                    &fixture.clock,
+                    &accounts.mint_x.1,
+                    &accounts.mint_y.1,
+                )
+                .unwrap();
+                assert_eq!(
+                    pythonize::depythonize::<serde_json::Value>(&quote).unwrap(),
+                    serde_json::to_value(&expected).unwrap()
+                );
+
+                let quote = quote_exact_out(
+                    py,
+                    &lb_pair.to_string(),
+                    raw_accounts(&accounts),
+                    amount,
+                    swap_for_y,
+                    host_fee_bps,
+                    None,
+                )
+                .unwrap();
+                let expected = commons::quote_exact_out(
+                    lb_pair,
+                    &lb_pair_state,
+                    amount,
+                    swap_for_y,
+                    QuoteOptions {
+                        host_fee_bps,
+                        ..Default::default()
+                    },
+                    decode_bin_arrays(&accounts),
+                    bitmap_extension.as_ref(),
+                    &fixture.clock,
+                    &accounts.mint_x.1,
+                    &accounts.mint_y.1,
+                )
+                .unwrap();
+                assert_eq!(
+                    pythonize::depythonize::<serde_json::Value>(&quote).unwrap(),
+                    serde_json::to_value(&expected).unwrap()
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn test_quote_without_mint_fails() {
+        pyo3::prepare_freethreaded_python();
+
+        let fixture = PoolFixture::sample();
+        let accounts = fixture.build().unwrap();
+        let mut raw_accounts = raw_accounts(&accounts);
+        raw_accounts.remove(&accounts.mint_y.0.to_string());
+
+        Python::with_gil(|py| {
+            let err = quote_exact_in(
+                py,
+                &fixture.lb_pair().to_string(),
+                raw_accounts,
+                1_000,
+                true,
+                None,
+                None,
+            )
+            .unwrap_err();
+            assert!(err.to_string().contains(&format!(
+                "Missing token y mint account {}",
+                accounts.mint_y.0
+            )));
+        });
+    }
+}
@@ -0,0 +1,82 @@
+use commons::*;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ACCOUNTS_TYPE: &'static str = r#"
+export interface RawAccount {
+    owner: string;
+    data: Uint8Array;
+}
+
+export type Accounts = Record<string, RawAccount> | Map<string, RawAccount>;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Fetched accounts keyed by their address.
+    #[wasm_bindgen(typescript_type = "Accounts")]
+    pub type Accounts;
+}
+
+#[derive(Deserialize)]
+struct RawAccount {
+    owner: String,
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+}
+
+pub(crate) fn js_error(error: anyhow::Error) -> JsError {
+    JsError::new(&format!("{error:#}"))
+}
+
+pub(crate) fn parse_pubkey(pubkey: &str) -> Result<Pubkey, JsError> {
+    pubkey
+        .parse()
+        .map_err(|_| JsError::new(&format!("Invalid pubkey {pubkey}")))
+}
+
+pub(crate) fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    let serializer = serde_wasm_bindgen::Serializer::new()
+        .serialize_large_number_types_as_bigints(true)
+        .serialize_maps_as_objects(true);
+    Ok(value.serialize(&serializer)?)
+}
+
+pub(crate) fn decode_accounts(accounts: Accounts) -> Result<HashMap<Pubkey, Account>, JsError> {
+    let accounts: HashMap<String, RawAccount> = serde_wasm_bindgen::from_value(accounts.into())?;
+
+    accounts
+        .into_iter()
+        .map(|(key, account)| {
+            Ok((
+                parse_pubkey(&key)?,
+                Account {
+                    data: account.data,
+                    owner: parse_pubkey(&account.owner)?,
+                    ..Default::default()
+                },
+            ))
+        })
+        .collect()
+}
+
+pub(crate) fn get_account<'a>(
+    accounts: &'a HashMap<Pubkey, Account>,
+    key: &Pubkey,
+    name: &str,
+) -> Result<&'a Account, JsError> {
+    accounts
+        .get(key)
+        .ok_or_else(|| JsError::new(&format!("Missing {name} account {key}")))
+}
+
+pub(crate) fn get_dlmm_account<T: bytemuck::AnyBitPattern>(
+    accounts: &HashMap<Pubkey, Account>,
+    key: &Pubkey,
+    name: &str,
+) -> Result<T, JsError> {
+    pod_read_unaligned_skip_disc(&get_account(accounts, key, name)?.data).map_err(js_error)
+}
@@ -0,0 +1,16 @@
+//! WebAssembly bindings of the `commons` quote core: swap quotes, PDA derivation, and position
+//! and limit order parsing. Accounts are passed as fetched, keyed by their address, and the
+//! results are plain JS objects, with `u64` amounts as `bigint` and pubkeys and 128 bits integers
+//! as strings.
+
+pub mod accounts;
+pub use accounts::*;
+
+pub mod pda;
+pub use pda::*;
+
+pub mod quote;
+pub use quote::*;
+
+pub mod position;
+pub use position::*;
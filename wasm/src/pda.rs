@@ -0,0 +1,153 @@
+use crate::*;
+use solana_sdk::pubkey::Pubkey;
+use wasm_bindgen::prelude::*;
+
+/// Program derived address with its bump.
+#[wasm_bindgen]
+pub struct Pda {
+    address: Pubkey,
+    bump: u8,
+}
+
+#[wasm_bindgen]
+impl Pda {
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> String {
+        self.address.to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+}
+
+impl From<(Pubkey, u8)> for Pda {
+    fn from((address, bump): (Pubkey, u8)) -> Self {
+        Self { address, bump }
+    }
+}
+
+#[wasm_bindgen(js_name = deriveLbPairWithPresetParameterKey)]
+pub fn derive_lb_pair_with_preset_parameter_key(
+    preset_parameter: &str,
+    token_x_mint: &str,
+    token_y_mint: &str,
+) -> Result<Pda, JsError> {
+    Ok(commons::derive_lb_pair_with_preset_parameter_key(
+        parse_pubkey(preset_parameter)?,
+        parse_pubkey(token_x_mint)?,
+        parse_pubkey(token_y_mint)?,
+    )
+    .into())
+}
+
+#[wasm_bindgen(js_name = deriveLbPairPda2)]
+pub fn derive_lb_pair_pda2(
+    token_x_mint: &str,
+    token_y_mint: &str,
+    bin_step: u16,
+    base_factor: u16,
+) -> Result<Pda, JsError> {
+    Ok(commons::derive_lb_pair_pda2(
+        parse_pubkey(token_x_mint)?,
+        parse_pubkey(token_y_mint)?,
+        bin_step,
+        base_factor,
+    )
+    .into())
+}
+
+#[wasm_bindgen(js_name = deriveCustomizablePermissionlessLbPair)]
+pub fn derive_customizable_permissionless_lb_pair(
+    token_x_mint: &str,
+    token_y_mint: &str,
+) -> Result<Pda, JsError> {
+    Ok(commons::derive_customizable_permissionless_lb_pair(
+        parse_pubkey(token_x_mint)?,
+        parse_pubkey(token_y_mint)?,
+    )
+    .into())
+}
+
+#[wasm_bindgen(js_name = derivePermissionLbPairPda)]
+pub fn derive_permission_lb_pair_pda(
+    base: &str,
+    token_x_mint: &str,
+    token_y_mint: &str,
+    bin_step: u16,
+) -> Result<Pda, JsError> {
+    Ok(commons::derive_permission_lb_pair_pda(
+        parse_pubkey(base)?,
+        parse_pubkey(token_x_mint)?,
+        parse_pubkey(token_y_mint)?,
+        bin_step,
+    )
+    .into())
+}
+
+#[wasm_bindgen(js_name = derivePositionPda)]
+pub fn derive_position_pda(
+    lb_pair: &str,
+    base: &str,
+    lower_bin_id: i32,
+    width: i32,
+) -> Result<Pda, JsError> {
+    Ok(commons::derive_position_pda(
+        parse_pubkey(lb_pair)?,
+        parse_pubkey(base)?,
+        lower_bin_id,
+        width,
+    )
+    .into())
+}
+
+#[wasm_bindgen(js_name = deriveOraclePda)]
+pub fn derive_oracle_pda(lb_pair: &str) -> Result<Pda, JsError> {
+    Ok(commons::derive_oracle_pda(parse_pubkey(lb_pair)?).into())
+}
+
+#[wasm_bindgen(js_name = deriveBinArrayPda)]
+pub fn derive_bin_array_pda(lb_pair: &str, bin_array_index: i64) -> Result<Pda, JsError> {
+    Ok(commons::derive_bin_array_pda(parse_pubkey(lb_pair)?, bin_array_index).into())
+}
+
+#[wasm_bindgen(js_name = deriveBinArrayBitmapExtension)]
+pub fn derive_bin_array_bitmap_extension(lb_pair: &str) -> Result<Pda, JsError> {
+    Ok(commons::derive_bin_array_bitmap_extension(parse_pubkey(lb_pair)?).into())
+}
+
+#[wasm_bindgen(js_name = deriveReservePda)]
+pub fn derive_reserve_pda(token_mint: &str, lb_pair: &str) -> Result<Pda, JsError> {
+    Ok(commons::derive_reserve_pda(parse_pubkey(token_mint)?, parse_pubkey(lb_pair)?).into())
+}
+
+#[wasm_bindgen(js_name = deriveRewardVaultPda)]
+pub fn derive_reward_vault_pda(lb_pair: &str, reward_index: u64) -> Result<Pda, JsError> {
+    Ok(commons::derive_reward_vault_pda(parse_pubkey(lb_pair)?, reward_index).into())
+}
+
+#[wasm_bindgen(js_name = deriveEventAuthorityPda)]
+pub fn derive_event_authority_pda() -> Pda {
+    commons::derive_event_authority_pda().into()
+}
+
+#[wasm_bindgen(js_name = derivePresetParameterPda2)]
+pub fn derive_preset_parameter_pda2(bin_step: u16, base_factor: u16) -> Pda {
+    commons::derive_preset_parameter_pda2(bin_step, base_factor).into()
+}
+
+#[wasm_bindgen(js_name = derivePresetParameterPdaV2)]
+pub fn derive_preset_parameter_pda_v2(index: u16) -> Pda {
+    commons::derive_preset_parameter_pda_v2(index).into()
+}
+
+#[wasm_bindgen(js_name = deriveTokenBadgePda)]
+pub fn derive_token_badge_pda(mint: &str) -> Result<Pda, JsError> {
+    Ok(commons::derive_token_badge_pda(parse_pubkey(mint)?).into())
+}
+
+#[wasm_bindgen(js_name = deriveOperatorPda)]
+pub fn derive_operator_pda(whitelisted_signer: &str) -> Result<Pda, JsError> {
+    Ok(commons::derive_operator_pda(parse_pubkey(whitelisted_signer)?).into())
+}
@@ -0,0 +1,493 @@
+use crate::*;
+use commons::{
+    PoolSnapshot, QuoteOptions, SwapExactInQuote, SwapExactOutQuote, SwapWithPriceImpactQuote,
+};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+fn parse_quote_options(
+    host_fee_bps: Option<u16>,
+    user: Option<String>,
+) -> Result<QuoteOptions, JsError> {
+    Ok(QuoteOptions {
+        host_fee_bps,
+        user: user.as_deref().map(parse_pubkey).transpose()?,
+    })
+}
+
+/// Quote an exact in swap of the pair `lb_pair`. `accounts` holds the pair, the clock sysvar,
+/// both mints, the bin arrays the swap may traverse and the bitmap extension when the pair has
+/// one. See `commons::quote_exact_in`.
+#[wasm_bindgen(js_name = quoteExactIn)]
+pub fn quote_exact_in(
+    lb_pair: &str,
+    accounts: Accounts,
+    amount_in: u64,
+    swap_for_y: bool,
+    host_fee_bps: Option<u16>,
+    user: Option<String>,
+) -> Result<JsValue, JsError> {
+    let quote = get_quote_exact_in(
+        parse_pubkey(lb_pair)?,
+        &decode_accounts(accounts)?,
+        amount_in,
+        swap_for_y,
+        parse_quote_options(host_fee_bps, user)?,
+    )
+    .map_err(js_error)?;
+
+    to_js(&quote)
+}
+
+pub(crate) fn get_quote_exact_in(
+    lb_pair: Pubkey,
+    accounts: &HashMap<Pubkey, Account>,
+    amount_in: u64,
+    swap_for_y: bool,
+    options: QuoteOptions,
+) -> anyhow::Result<SwapExactInQuote> {
+    PoolSnapshot::from_account_map(lb_pair, accounts)?
+        .quote_exact_in(amount_in, swap_for_y, options)
+}
+
+/// Quote an exact out swap of the pair `lb_pair`, see [`quote_exact_in`] for `accounts`.
+#[wasm_bindgen(js_name = quoteExactOut)]
+pub fn quote_exact_out(
+    lb_pair: &str,
+    accounts: Accounts,
+    amount_out: u64,
+    swap_for_y: bool,
+    host_fee_bps: Option<u16>,
+    user: Option<String>,
+) -> Result<JsValue, JsError> {
+    let quote = get_quote_exact_out(
+        parse_pubkey(lb_pair)?,
+        &decode_accounts(accounts)?,
+        amount_out,
+        swap_for_y,
+        parse_quote_options(host_fee_bps, user)?,
+    )
+    .map_err(js_error)?;
+
+    to_js(&quote)
+}
+
+pub(crate) fn get_quote_exact_out(
+    lb_pair: Pubkey,
+    accounts: &HashMap<Pubkey, Account>,
+    amount_out: u64,
+    swap_for_y: bool,
+    options: QuoteOptions,
+) -> anyhow::Result<SwapExactOutQuote> {
+    PoolSnapshot::from_account_map(lb_pair, accounts)?
+        .quote_exact_out(amount_out, swap_for_y, options)
+}
+
+/// Quote a `swap_with_price_impact` of the pair `lb_pair`, see [`quote_exact_in`] for
+/// `accounts`.
+#[wasm_bindgen(js_name = quoteExactInWithPriceImpact)]
+#[allow(clippy::too_many_arguments)]
+pub fn quote_exact_in_with_price_impact(
+    lb_pair: &str,
+    accounts: Accounts,
+    amount_in: u64,
+    swap_for_y: bool,
+    active_id: Option<i32>,
+    max_price_impact_bps: u16,
+    host_fee_bps: Option<u16>,
+    user: Option<String>,
+) -> Result<JsValue, JsError> {
+    let quote = get_quote_exact_in_with_price_impact(
+        parse_pubkey(lb_pair)?,
+        &decode_accounts(accounts)?,
+        amount_in,
+        swap_for_y,
+        active_id,
+        max_price_impact_bps,
+        parse_quote_options(host_fee_bps, user)?,
+    )
+    .map_err(js_error)?;
+
+    to_js(&quote)
+}
+
+pub(crate) fn get_quote_exact_in_with_price_impact(
+    lb_pair: Pubkey,
+    accounts: &HashMap<Pubkey, Account>,
+    amount_in: u64,
+    swap_for_y: bool,
+    active_id: Option<i32>,
+    max_price_impact_bps: u16,
+    options: QuoteOptions,
+) -> anyhow::Result<SwapWithPriceImpactQuote> {
+    PoolSnapshot::from_account_map(lb_pair, accounts)?.quote_exact_in_with_price_impact(
+        amount_in,
+        swap_for_y,
+        active_id,
+        max_price_impact_bps,
+        options,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commons::dlmm::accounts::*;
+    use commons::pod_read_unaligned_skip_disc;
+    use commons::test_utils::*;
+
+    struct PoolStates {
+        lb_pair: LbPair,
+        bin_arrays: HashMap<Pubkey, BinArray>,
+        bitmap_extension: Option<BinArrayBitmapExtension>,
+    }
+
+    fn decode_states(accounts: &PoolFixtureAccounts) -> PoolStates {
+        PoolStates {
+            lb_pair: pod_read_unaligned_skip_disc(&accounts.lb_pair.1.data).unwrap(),
+            bin_arrays: accounts
+                .bin_arrays
+                .iter()
+                .map(|(key, account)| (*key, pod_read_unaligned_skip_disc(&account.data).unwrap()))
+                .collect(),
+            bitmap_extension: accounts
+                .bitmap_extension
+                .as_ref()
+                .map(|(_, account)| pod_read_unaligned_skip_disc(&account.data).unwrap()),
+        }
+    }
+
+    fn to_json<T: serde::Serialize>(value: &T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_quote_exact_in_matches_commons() {
+        let fixture = PoolFixture::sample();
+        let accounts = fixture.build().unwrap();
+        let states = decode_states(&accounts);
+        let user = Some(Pubkey::new_unique());
+
+        for (amount_in, swap_for_y, host_fee_bps) in [
+            (1_000_000, true, None),
+            (2_500_000_000, true, Some(1_000)),
+            (1_000_000_000, false, None),
+            (3_000_000_000, false, Some(2_000)),
+        ] {
+            let quote = get_quote_exact_in(
+                fixture.lb_pair(),
+                &accounts.to_account_map(),
+                amount_in,
+                swap_for_y,
+                QuoteOptions { host_fee_bps, user },
+            )
+            .unwrap();
+            let expected = commons::quote_exact_in(
+                fixture.lb_pair(),
+                &states.lb_pair,
+                amount_in,
+                swap_for_y,
+                QuoteOptions { host_fee_bps, user },
+                states.bin_arrays.clone(),
+                states.bitmap_extension.as_ref(),
+                &fixture.clock,
+                &accounts.mint_x.1,
+                &accounts.mint_y.1,
+            )
+            .unwrap();
+
+            assert_eq!(to_json(&quote), to_json(&expected));
+        }
+    }
+
+    #[test]
+    fn test_quote_exact_out_matches_commons() {
+        let fixture = PoolFixture::sample();
+        let accounts = fixture.build().unwrap();
+        let states = decode_states(&accounts);
+
+        for (amount_out, swap_for_y, host_fee_bps) in [
+            (1_000_000, true, None),
+            (2_000_000_000, true, Some(1_000)),
+            (1_000_000_000, false, None),
+            (2_000_000_000, false, Some(2_000)),
+        ] {
+            let quote = get_quote_exact_out(
+                fixture.lb_pair(),
+                &accounts.to_account_map(),
+                amount_out,
+                swap_for_y,
+                QuoteOptions {
+                    host_fee_bps,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let expected = commons::quote_exact_out(
+                fixture.lb_pair(),
+                &states.lb_pair,
+                amount_out,
+                swap_for_y,
+                QuoteOptions {
+                    host_fee_bps,
+                    ..Default::default()
+                },
+                states.bin_arrays.clone(),
+                states.bitmap_extension.as_ref(),
+                &fixture.clock,
+                &accounts.mint_x.1,
+                &accounts.mint_y.1,
+            )
+            .unwrap();
+
+            assert_eq!(to_json(&quote), to_json(&expected));
+        }
+    }
+
+    #[test]
+    fn test_quote_exact_in_with_price_impact_matches_commons() {
+        let fixture = PoolFixture::sample();
+        let accounts = fixture.build().unwrap();
+        let states = decode_states(&accounts);
+
+        let quote = get_quote_exact_in_with_price_impact(
+            fixture.lb_pair(),
+            &accounts.to_account_map(),
+            2_000_000_000,
+            true,
+            None,
+            50,
+            QuoteOptions::default(),
+        )
+        .unwrap();
+        let expected = commons::quote_exact_in_with_price_impact(
+            fixture.lb_pair(),
+            &states.lb_pair,
+            2_000_000_000,
+            true,
+            None,
+            50,
+            QuoteOptions::default(),
+            states.bin_arrays,
+            states.bitmap_extension.as_ref(),
+            &fixture.clock,
+            &accounts.mint_x.1,
+            &accounts.mint_y.1,
+        )
+        .unwrap();
+
+        assert_eq!(to_json(&quote), to_json(&expected));
+    }
+
+    #[test]
+    fn test_quote_without_clock_fails() {
+        let fixture = PoolFixture::sample();
+        let mut accounts = fixture.build().unwrap().to_account_map();
+        accounts.remove(&solana_sdk::sysvar::clock::ID);
+
+        let err = get_quote_exact_in(
+            fixture.lb_pair(),
+            &accounts,
+            1_000_000,
+            true,
+            QuoteOptions::default(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Missing clock account"));
+    }
+
+    /// Accounts object passed by JS callers, addresses to owner and `Uint8Array` data.
+    #[cfg(target_arch = "wasm32")]
+    fn to_js_accounts(accounts: &PoolFixtureAccounts) -> Accounts {
+        use serde::Serialize;
+        use wasm_bindgen::JsCast;
+
+        #[derive(Serialize)]
+        struct JsAccount {
+            owner: String,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let accounts: HashMap<String, JsAccount> = accounts
+            .to_account_map()
+            .into_iter()
+            .map(|(key, account)| {
+                (
+                    key.to_string(),
+                    JsAccount {
+                        owner: account.owner.to_string(),
+                        data: account.data,
+                    },
+                )
+            })
+            .collect();
+        let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+
+        accounts.serialize(&serializer).unwrap().unchecked_into()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn get_field(value: &JsValue, field: &str) -> JsValue {
+        js_sys::Reflect::get(value, &field.into()).unwrap()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn assert_bigint(value: &JsValue, field: &str, expected: u64) {
+        let field_value = get_field(value, field);
+        assert!(field_value.is_bigint(), "{field} is not a bigint");
+        assert_eq!(field_value, JsValue::from(expected), "{field}");
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn assert_prices(value: &JsValue, expected: &commons::SwapQuotePrices) {
+        let prices = get_field(value, "prices");
+        assert_eq!(
+            get_field(&prices, "start_active_id").as_f64(),
+            Some(expected.start_active_id.into())
+        );
+        assert_eq!(
+            get_field(&prices, "end_active_id").as_f64(),
+            Some(expected.end_active_id.into())
+        );
+        for (field, expected) in [
+            ("start_price", expected.start_price),
+            ("end_price", expected.end_price),
+            ("execution_price", expected.execution_price),
+            ("effective_price", expected.effective_price),
+        ] {
+            assert_eq!(
+                get_field(&prices, field).as_string(),
+                Some(expected.to_string()),
+                "{field}"
+            );
+        }
+        assert_bigint(&prices, "price_impact_bps", expected.price_impact_bps);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn test_quote_exact_in_js() {
+        let fixture = PoolFixture::sample();
+        let accounts = fixture.build().unwrap();
+        let user = Pubkey::new_unique();
+
+        let quote = quote_exact_in(
+            &fixture.lb_pair().to_string(),
+            to_js_accounts(&accounts),
+            2_500_000_000,
+            true,
+            Some(1_000),
+            Some(user.to_string()),
+        )
+        .unwrap();
+        let expected = get_quote_exact_in(
+            fixture.lb_pair(),
+            &accounts.to_account_map(),
+            2_500_000_000,
+            true,
+            QuoteOptions {
+                host_fee_bps: Some(1_000),
+                user: Some(user),
+            },
+        )
+        .unwrap();
+
+        assert_bigint(&quote, "amount_out", expected.amount_out);
+        assert_bigint(&quote, "fee", expected.fee);
+        assert_bigint(&quote, "protocol_fee", expected.protocol_fee);
+        assert_bigint(&quote, "host_fee", expected.host_fee);
+        assert!(expected.host_fee > 0);
+        assert_prices(&quote, &expected.prices);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn test_quote_exact_out_js() {
+        let fixture = PoolFixture::sample();
+        let accounts = fixture.build().unwrap();
+
+        let quote = quote_exact_out(
+            &fixture.lb_pair().to_string(),
+            to_js_accounts(&accounts),
+            2_000_000_000,
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+        let expected = get_quote_exact_out(
+            fixture.lb_pair(),
+            &accounts.to_account_map(),
+            2_000_000_000,
+            false,
+            QuoteOptions::default(),
+        )
+        .unwrap();
+
+        assert_bigint(&quote, "amount_in", expected.amount_in);
+        assert_bigint(&quote, "fee", expected.fee);
+        assert_bigint(&quote, "protocol_fee", expected.protocol_fee);
+        assert_prices(&quote, &expected.prices);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn test_quote_exact_in_with_price_impact_js() {
+        let fixture = PoolFixture::sample();
+        let accounts = fixture.build().unwrap();
+
+        let quote = quote_exact_in_with_price_impact(
+            &fixture.lb_pair().to_string(),
+            to_js_accounts(&accounts),
+            2_000_000_000,
+            true,
+            None,
+            50,
+            None,
+            None,
+        )
+        .unwrap();
+        let expected = get_quote_exact_in_with_price_impact(
+            fixture.lb_pair(),
+            &accounts.to_account_map(),
+            2_000_000_000,
+            true,
+            None,
+            50,
+            QuoteOptions::default(),
+        )
+        .unwrap();
+
+        assert_bigint(&quote, "amount_in", expected.amount_in);
+        assert_bigint(&quote, "amount_left", expected.amount_left);
+        assert_bigint(&quote, "amount_out", expected.amount_out);
+        assert_bigint(&quote, "min_amount_out", expected.min_amount_out);
+        assert_bigint(&quote, "fee", expected.fee);
+        assert_prices(&quote, &expected.prices);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn test_quote_with_malformed_account_js() {
+        let fixture = PoolFixture::sample();
+        let js_accounts = to_js_accounts(&fixture.build().unwrap());
+        js_sys::Reflect::set(
+            &js_accounts,
+            &solana_sdk::sysvar::clock::ID.to_string().into(),
+            &JsValue::from_str("clock"),
+        )
+        .unwrap();
+
+        assert!(quote_exact_in(
+            &fixture.lb_pair().to_string(),
+            js_accounts,
+            1_000_000,
+            true,
+            None,
+            None,
+        )
+        .is_err());
+    }
+}